@@ -121,6 +121,14 @@ impl Introspector for BundleIntrospector {
         self.child(location)?.position(location)
     }
 
+    fn column(&self, location: Location) -> Option<usize> {
+        self.child(location)?.column(location)
+    }
+
+    fn placement(&self, location: Location) -> Option<DocumentPosition> {
+        self.child(location)?.placement(location)
+    }
+
     fn page_numbering(&self, location: Location) -> Option<&Numbering> {
         self.child(location)?.page_numbering(location)
     }
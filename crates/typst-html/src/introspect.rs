@@ -112,6 +112,14 @@ impl Introspector for HtmlIntrospector {
         self.position(location).map(DocumentPosition::Html)
     }
 
+    fn column(&self, _: Location) -> Option<usize> {
+        None
+    }
+
+    fn placement(&self, _: Location) -> Option<DocumentPosition> {
+        None
+    }
+
     fn page_numbering(&self, _: Location) -> Option<&Numbering> {
         None
     }
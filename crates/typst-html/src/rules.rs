@@ -13,7 +13,7 @@ use typst_library::introspection::{
     Counter, DocumentIntrospection, Locator, QueryIntrospection,
 };
 use typst_library::layout::resolve::{Cell, CellGrid, Entry, Header};
-use typst_library::layout::{BlockElem, HElem, OuterVAlignment, Sizing};
+use typst_library::layout::{BlockElem, HElem, KeepElem, OuterVAlignment, Sizing};
 use typst_library::math::EquationElem;
 use typst_library::math::ir::resolve_equation;
 use typst_library::model::{
@@ -79,6 +79,9 @@ pub fn register(rules: &mut NativeRuleMap) {
     rules.register(Html, RAW_RULE);
     rules.register(Html, RAW_LINE_RULE);
 
+    // Layout.
+    rules.register::<KeepElem>(Html, |elem, _, _| Ok(elem.body.clone()));
+
     // Visualize.
     rules.register(Html, IMAGE_RULE);
 
@@ -330,6 +333,15 @@ const QUOTE_RULE: ShowFn<QuoteElem> = |elem, _, styles| {
 const FOOTNOTE_RULE: ShowFn<FootnoteElem> = |elem, engine, styles| {
     let span = elem.span();
 
+    // Indicates the presence of a default footnote rule to emit an error when
+    // no footnote container is available.
+    let marker = FootnoteMarker::new().pack().spanned(span);
+
+    // An unnumbered footnote has no reference in the text, only its entry.
+    if !elem.is_ref() && !elem.numbered.get(styles) {
+        return Ok(marker);
+    }
+
     // The footnote number that links to the footnote entry.
     let link = elem.realize(engine, styles)?;
     let sup = SuperElem::new(link)
@@ -337,10 +349,6 @@ const FOOTNOTE_RULE: ShowFn<FootnoteElem> = |elem, engine, styles| {
         .styled(HtmlElem::role.set(Some("doc-noteref".into())))
         .spanned(span);
 
-    // Indicates the presence of a default footnote rule to emit an error when
-    // no footnote container is available.
-    let marker = FootnoteMarker::new().pack().spanned(span);
-
     Ok(HElem::hole().clone() + sup + marker)
 };
 
@@ -419,7 +427,10 @@ const FOOTNOTE_ENTRY_RULE: ShowFn<FootnoteEntry> = |elem, engine, styles| {
     // <https://www.w3.org/TR/dpub-aria-1.1/#doc-footnote>). Our footnotes more
     // appropriately modelled as ARIA endnotes. This is also in line with how
     // Pandoc handles footnotes.
-    Ok(prefix + body)
+    Ok(match elem.separator_between_number_and_text.get_cloned(styles) {
+        Smart::Custom(separator) => prefix + separator + body,
+        Smart::Auto => prefix + body,
+    })
 };
 
 const OUTLINE_RULE: ShowFn<OutlineElem> = |elem, engine, styles| {
@@ -1,19 +1,22 @@
 use std::cell::LazyCell;
 
 use smallvec::SmallVec;
-use typst_library::diag::SourceResult;
+use typst_library::diag::{SourceResult, warning};
 use typst_library::engine::Engine;
-use typst_library::foundations::{Packed, Resolve, StyleChain};
-use typst_library::introspection::Locator;
+use typst_library::foundations::{Content, Packed, Resolve, StyleChain};
+use typst_library::introspection::{Locator, SplitLocator};
 use typst_library::layout::{
-    Abs, Axes, BlockBody, BlockElem, Fragment, Frame, FrameKind, Region, Regions, Rel,
-    Sides, Size, Sizing,
+    Abs, Axes, BlockBody, BlockElem, Fragment, Frame, FrameKind, Point, Ratio, Region,
+    Regions, Rel, Sides, Size, Sizing, Transform,
 };
 use typst_library::visualize::Stroke;
 use typst_utils::Numeric;
 
 use crate::shapes::{clip_rect, fill_and_stroke};
 
+/// The scale factor below which fitted content may become hard to read.
+const MIN_FIT_SCALE: f64 = 0.5;
+
 /// Lay this out as an unbreakable block.
 #[typst_macros::time(name = "block", span = elem.span())]
 pub fn layout_single_block(
@@ -38,6 +41,11 @@ pub fn layout_single_block(
         // adjusted below.
         None => Frame::hard(Size::zero()),
 
+        // If the content should be fitted, it may need to be scaled down.
+        Some(BlockBody::Content(body)) if elem.fit_width.get(styles) => {
+            layout_fitted(elem, engine, body, locator, styles, pod)?
+        }
+
         // If we have content as our body, just layout it.
         Some(BlockBody::Content(body)) => {
             crate::layout_frame(engine, body, locator.relayout(), styles, pod)?
@@ -72,6 +80,12 @@ pub fn layout_single_block(
         crate::pad::grow(&mut frame, &inset);
     }
 
+    // Extend the frame to the minimum height.
+    let min_height = elem.min_height.resolve(styles).relative_to(region.base().y);
+    if min_height.is_finite() && frame.height() < min_height {
+        frame.size_mut().y = min_height;
+    }
+
     // Prepare fill and stroke.
     let fill = elem.fill.get_cloned(styles);
     let stroke = elem
@@ -102,6 +116,47 @@ pub fn layout_single_block(
     Ok(frame)
 }
 
+/// Lays out the content of a block with `fit-width`, scaling it down uniformly
+/// if it is wider than the pod.
+fn layout_fitted(
+    elem: &Packed<BlockElem>,
+    engine: &mut Engine,
+    body: &Content,
+    locator: Locator,
+    styles: StyleChain,
+    pod: Region,
+) -> SourceResult<Frame> {
+    // Measure the natural width of the content. If it fits, we lay it out
+    // normally such that it is aligned within the full width.
+    let measure = Region::new(pod.size, Axes::new(false, pod.expand.y));
+    let mut frame =
+        crate::layout_frame(engine, body, locator.relayout(), styles, measure)?;
+    if !pod.size.x.is_finite() || frame.width() <= pod.size.x {
+        return crate::layout_frame(engine, body, locator.relayout(), styles, pod);
+    }
+
+    let factor = pod.size.x / frame.width();
+    if factor < MIN_FIT_SCALE {
+        engine.sink.warn(warning!(
+            elem.span(),
+            "block content was scaled down to {:.0}% to fit",
+            factor * 100.0;
+            hint: "the content may be hard to read";
+            hint: "try reducing the width of the content instead";
+        ));
+    }
+
+    let baseline = frame.has_baseline().then(|| frame.baseline() * factor);
+    let size = Size::new(pod.size.x, frame.height() * factor);
+    frame.transform(Transform::scale(Ratio::new(factor), Ratio::new(factor)));
+    frame.set_size(size);
+    if let Some(baseline) = baseline {
+        frame.set_baseline(baseline);
+    }
+
+    Ok(frame)
+}
+
 /// Lay this out as a breakable block.
 #[typst_macros::time(name = "block", span = elem.span())]
 pub fn layout_multi_block(
@@ -120,7 +175,50 @@ pub fn layout_multi_block(
     let mut buf = SmallVec::<[Abs; 2]>::new();
 
     // Build the pod regions.
-    let pod = breakable_pod(&width.into(), &height, &inset, styles, regions, &mut buf);
+    let mut pod =
+        breakable_pod(&width.into(), &height, &inset, styles, regions, &mut buf);
+
+    // Lay out the header, footer, and split marker and reserve space for them
+    // at the top and bottom of each region.
+    let mut locator = locator;
+    let mut reserved_backlog = SmallVec::<[Abs; 2]>::new();
+    let header_body = elem.header.get_ref(styles);
+    let footer_body = elem.footer.get_ref(styles);
+    let marker_body = elem.on_split.get_ref(styles);
+    let reserve = header_body.is_some() || footer_body.is_some() || marker_body.is_some();
+    let mut parts = None;
+    let (header, footer, marker) = if reserve {
+        let mut split = locator.split();
+        let region = Region::new(pod.base(), Axes::new(pod.expand.x, false));
+        let mut layout = |content: &Content| {
+            crate::layout_frame(
+                engine,
+                content,
+                split.next(&content.span()),
+                styles,
+                region,
+            )
+        };
+        let header = header_body.as_ref().map(&mut layout).transpose()?;
+        let footer = footer_body.as_ref().map(&mut layout).transpose()?;
+        let marker = marker_body.as_ref().map(&mut layout).transpose()?;
+        locator = split.next(&());
+        parts = Some(BlockParts { split, region, styles });
+
+        let height: Abs =
+            header.iter().chain(&footer).chain(&marker).map(Frame::height).sum();
+        reserved_backlog.extend(pod.backlog.iter().map(|&h| h - height));
+        pod = Regions {
+            size: Size::new(pod.size.x, pod.size.y - height),
+            full: pod.full - height,
+            backlog: &reserved_backlog,
+            last: pod.last.map(|h| h - height),
+            ..pod
+        };
+        (header, footer, marker)
+    } else {
+        (None, None, None)
+    };
 
     // Layout the body.
     let body = elem.body.get_ref(styles);
@@ -213,7 +311,13 @@ pub fn layout_multi_block(
         skip_first = first.is_empty() && rest.iter().any(|frame| !frame.is_empty());
     }
 
+    // Resolve the minimum height, which applies to all frames together.
+    let min_height = elem.min_height.resolve(styles).relative_to(regions.base().y);
+    let mut used = Abs::zero();
+    let mut extended = false;
+
     // Post-process to apply insets, clipping, fills, and strokes.
+    let count = fragment.len();
     for (i, (frame, region)) in fragment.iter_mut().zip(pod.iter()).enumerate() {
         // Explicit blocks are boundaries for gradient relativeness.
         if is_explicit {
@@ -224,11 +328,68 @@ pub fn layout_multi_block(
         // applying the inset, since the pod shrunk.
         frame.set_size(pod.expand.select(region, frame.size()));
 
-        // Apply the inset.
+        // Add the split marker into the space reserved for it, but only if
+        // the block actually continues in the next region. An empty orphan
+        // frame isn't a split, since the block just moves on.
+        if let (Some(marker), Some(body), Some(parts)) =
+            (&marker, marker_body, &mut parts)
+        {
+            let y = frame.height();
+            if i + 1 < count && !(i == 0 && skip_first) {
+                let part = parts.layout(engine, marker, body, i)?;
+                frame.size_mut().y += marker.height();
+                frame.push_frame(Point::with_y(y), part);
+            } else if pod.expand.y {
+                frame.size_mut().y += marker.height();
+            }
+        }
+
+        // Add the footer into the space reserved for it.
+        if let (Some(footer), Some(body), Some(parts)) =
+            (&footer, footer_body, &mut parts)
+        {
+            let part = parts.layout(engine, footer, body, i)?;
+            let y = frame.height();
+            frame.size_mut().y += footer.height();
+            frame.push_frame(Point::with_y(y), part);
+        }
+
+        // Add the header into the space reserved for it, moving the body
+        // down. An empty orphan frame stays empty.
+        if let (Some(header), Some(body), Some(parts)) =
+            (&header, header_body, &mut parts)
+        {
+            frame.size_mut().y += header.height();
+            if !(i == 0 && skip_first) {
+                let part = parts.layout(engine, header, body, i)?;
+                frame.translate(Point::with_y(header.height()));
+                frame.prepend_frame(Point::zero(), part);
+            }
+        }
+
+        // Apply the inset. Like for the regions, the top inset only applies to
+        // the first fragment and the bottom inset only to the last one, such
+        // that a split block is only padded at its outer edges.
         if has_inset {
+            let mut inset = inset;
+            if i > 0 {
+                inset.top = Rel::zero();
+            }
+            if i + 1 < count {
+                inset.bottom = Rel::zero();
+            }
             crate::pad::grow(frame, &inset);
         }
 
+        // Extend the last frame such that all frames together reach the
+        // minimum height.
+        if i + 1 == count && min_height.is_finite() && used + frame.height() < min_height
+        {
+            frame.size_mut().y = min_height - used;
+            extended = true;
+        }
+        used += frame.height();
+
         // Clip the contents, if requested.
         if clip {
             frame.clip(clip_rect(frame.size(), &radius, &stroke, &outset));
@@ -248,6 +409,21 @@ pub fn layout_multi_block(
         }
     }
 
+    // If the block's content fits into the remaining space, but its minimum
+    // height doesn't, move the block to the next region as a whole, provided
+    // that it fits there. This is done with an empty orphan frame, just like
+    // for content that doesn't fit.
+    if extended
+        && let [frame] = fragment.as_slice()
+        && !frame.height().fits(regions.size.y)
+        && regions.may_progress()
+        && regions.iter().nth(1).is_some_and(|next| next.y.fits(frame.height()))
+    {
+        let orphan = Frame::soft(Size::with_x(frame.width()));
+        let frame = frame.clone();
+        fragment = Fragment::frames(vec![orphan, frame]);
+    }
+
     Ok(fragment)
 }
 
@@ -290,6 +466,45 @@ pub(crate) fn unbreakable_pod(
     Region::new(size, expand)
 }
 
+/// The header, footer, and split marker of a breakable block, which are
+/// repeated in the block's regions.
+struct BlockParts<'a, 's> {
+    /// Provides the locators for the parts in each region.
+    split: SplitLocator<'a>,
+    /// The region the parts are laid out in.
+    region: Region,
+    /// The styles of the block.
+    styles: StyleChain<'s>,
+}
+
+impl BlockParts<'_, '_> {
+    /// Provides a part for the region with the given index, given the frame it
+    /// was laid out into when its space was reserved.
+    ///
+    /// That frame is used in the first region. The other regions get a fresh
+    /// layout with a new locator, such that the elements within are located
+    /// once per region instead of being repeated with the same locations.
+    fn layout(
+        &mut self,
+        engine: &mut Engine,
+        first: &Frame,
+        body: &Content,
+        i: usize,
+    ) -> SourceResult<Frame> {
+        if i == 0 {
+            return Ok(first.clone());
+        }
+
+        crate::layout_frame(
+            engine,
+            body,
+            self.split.next(&body.span()),
+            self.styles,
+            self.region,
+        )
+    }
+}
+
 /// Builds the pod regions for a breakable sized container.
 fn breakable_pod<'a>(
     width: &Sizing,
@@ -347,10 +562,31 @@ fn breakable_pod<'a>(
     );
 
     // Take the inset, if any, into account, applying it to the
-    // individual region components.
+    // individual region components. The top inset only applies to the first
+    // region. The bottom inset applies to the last region or, if we don't know
+    // in advance which one that is, to all of them.
     let (mut full, mut last) = (full, last);
     if !inset.is_zero() {
-        crate::pad::shrink_multiple(&mut size, &mut full, backlog, &mut last, inset);
+        let known = matches!(height, Sizing::Rel(_));
+        let count = backlog.len();
+        let shrink = |height: Abs, top: bool, bottom: bool| {
+            let mut shrunk = height;
+            if top {
+                shrunk -= inset.top.relative_to(height);
+            }
+            if bottom {
+                shrunk -= inset.bottom.relative_to(height);
+            }
+            shrunk
+        };
+
+        size.x -= (inset.left + inset.right).relative_to(size.x);
+        size.y = shrink(size.y, true, !known || count == 0);
+        full = shrink(full, true, true);
+        for (i, height) in backlog.iter_mut().enumerate() {
+            *height = shrink(*height, false, !known || i + 1 == count);
+        }
+        last = last.map(|height| shrink(height, false, true));
     }
 
     // If the child is manually, the size is forced and we should enable
@@ -1,33 +1,44 @@
 use std::cell::{LazyCell, RefCell};
 use std::fmt::{self, Debug, Formatter};
 use std::hash::Hash;
+use std::num::NonZeroUsize;
 
 use bumpalo::Bump;
 use bumpalo::boxed::Box as BumpBox;
 use comemo::{Track, Tracked, TrackedMut};
+use rustc_hash::FxHashMap;
 use typst_library::diag::{SourceResult, bail, warning};
 use typst_library::engine::{Engine, Route, Sink, Traced};
-use typst_library::foundations::{Packed, Resolve, Smart, StyleChain};
+use typst_library::foundations::{
+    Content, NativeElement, Packed, Resolve, Selector, Smart, StyleChain,
+};
 use typst_library::introspection::{
-    Introspector, Location, Locator, LocatorLink, SplitLocator, Tag, TagElem,
+    Introspector, Location, Locator, LocatorLink, PositionIntrospection,
+    QueryIntrospection, SplitLocator, Tag, TagElem,
 };
 use typst_library::layout::{
-    Abs, AlignElem, Alignment, Axes, BlockElem, ColbreakElem, FixedAlignment, FlushElem,
-    Fr, Fragment, Frame, FrameParent, Inherit, PagebreakElem, PlaceElem, PlacementScope,
-    Ratio, Region, Regions, Rel, Size, Sizing, Spacing, VElem,
+    Abs, AlignElem, Alignment, Angle, Axes, BalancePointElem, BlockElem, ColbreakElem,
+    FixedAlignment, FloatPage, FloatPlacement, FlushElem, Fr, Fragment, Frame,
+    FrameParent, Inherit, KeepBoundary, PageElem, PageRegion, PagebreakElem, PlaceElem,
+    PlacementScope, Ratio, Region, Regions, Rel, RotateElem, Size, Sizing, Spacing,
+    VAlignment, VElem,
 };
 use typst_library::model::ParElem;
 use typst_library::routines::Pair;
 use typst_library::text::TextElem;
 use typst_library::{Library, World};
-use typst_utils::{LazyHash, Protected, SliceExt};
+use typst_syntax::Span;
+use typst_utils::{LazyHash, Numeric, Protected, SliceExt};
 
 use super::{FlowMode, layout_multi_block, layout_single_block};
-use crate::inline::ParSituation;
+use crate::inline::{ParSituation, ParWrap};
 use crate::modifiers::layout_and_modify;
 
 /// Collects all elements of the flow into prepared children. These are much
 /// simpler to handle than the raw elements.
+///
+/// If `starts` is given, the index of the first child of each top-level
+/// paragraph and block is recorded into it.
 #[typst_macros::time]
 pub fn collect<'a>(
     engine: &mut Engine,
@@ -37,6 +48,7 @@ pub fn collect<'a>(
     base: Size,
     expand: bool,
     mode: FlowMode,
+    starts: Option<&mut Vec<usize>>,
 ) -> SourceResult<Vec<Child<'a>>> {
     Collector {
         engine,
@@ -45,8 +57,12 @@ pub fn collect<'a>(
         locator: locator.split(),
         base,
         expand,
+        root: mode == FlowMode::Root,
+        starts,
         output: Vec::with_capacity(children.len()),
         par_situation: ParSituation::First,
+        wrap: None,
+        page_regions: None,
     }
     .run(mode)
 }
@@ -58,9 +74,45 @@ struct Collector<'a, 'x, 'y> {
     children: &'x [Pair<'a>],
     base: Size,
     expand: bool,
+    root: bool,
+    starts: Option<&'x mut Vec<usize>>,
     locator: SplitLocator<'a>,
     output: Vec<Child<'a>>,
     par_situation: ParSituation,
+    wrap: Option<Wrap>,
+    /// The top and height of the content area of each page, as recorded by
+    /// the page regions of the previous layout pass. Built once it is first
+    /// needed.
+    page_regions: Option<FxHashMap<NonZeroUsize, (Abs, Abs)>>,
+}
+
+/// A float that following paragraphs wrap around.
+#[derive(Debug, Copy, Clone)]
+struct Wrap {
+    /// The side of the column at which the float is.
+    side: FixedAlignment,
+    /// The width of the float, including its clearance.
+    width: Abs,
+    /// The height of the float that isn't yet covered by wrapping content.
+    height: Abs,
+    /// The float's clearance, which is kept below it.
+    clearance: Abs,
+}
+
+/// An area at the side of the region that paragraphs flow around, with its
+/// vertical extent measured from the top of a paragraph.
+#[derive(Debug, Copy, Clone)]
+struct Exclusion {
+    /// The side of the region at which the area is.
+    side: FixedAlignment,
+    /// The width of the area.
+    width: Abs,
+    /// The distance from the paragraph's top to the area's top edge.
+    top: Abs,
+    /// The distance from the paragraph's top to the area's bottom edge.
+    bottom: Abs,
+    /// The distance from the paragraph's top to the region's bottom edge.
+    end: Abs,
 }
 
 impl<'a> Collector<'a, '_, '_> {
@@ -74,22 +126,49 @@ impl<'a> Collector<'a, '_, '_> {
 
     /// Perform collection for block-level children.
     fn run_block(mut self) -> SourceResult<Vec<Child<'a>>> {
-        for &(child, styles) in self.children {
-            if let Some(elem) = child.to_packed::<TagElem>() {
+        let children = self.children;
+        let anchored = self.anchored();
+        for (i, &(child, styles)) in children.iter().enumerate() {
+            // Floats anchored at this child are collected in front of it
+            // instead of at their own position.
+            for &(anchor, float) in &anchored {
+                if anchor == i {
+                    let (float, styles) = children[float];
+                    self.place(float.to_packed::<PlaceElem>().unwrap(), styles)?;
+                }
+            }
+
+            if anchored.iter().any(|&(_, float)| float == i) {
+                continue;
+            } else if let Some(elem) = child.to_packed::<TagElem>() {
                 self.output.push(Child::Tag(&elem.tag));
             } else if let Some(elem) = child.to_packed::<VElem>() {
                 self.v(elem, styles);
             } else if let Some(elem) = child.to_packed::<ParElem>() {
+                self.start();
                 self.par(elem, styles)?;
             } else if let Some(elem) = child.to_packed::<BlockElem>() {
+                self.start();
                 self.block(elem, styles);
             } else if let Some(elem) = child.to_packed::<PlaceElem>() {
                 self.place(elem, styles)?;
             } else if child.is::<FlushElem>() {
+                self.clear_wrap();
                 self.output.push(Child::Flush);
             } else if let Some(elem) = child.to_packed::<ColbreakElem>() {
+                // A continuous page has just a single region.
+                if self.root && styles.get(PageElem::continuous) {
+                    continue;
+                }
+                if self.wrap.take().is_some() {
+                    self.output.push(Child::Keep(false));
+                }
                 self.output.push(Child::Break(elem.weak.get(styles)));
                 self.par_situation = ParSituation::First;
+            } else if child.is::<BalancePointElem>() {
+                self.output.push(Child::Balance);
+            } else if let Some(elem) = child.to_packed::<KeepBoundary>() {
+                self.output.push(Child::Keep(elem.start));
             } else if child.is::<PagebreakElem>() {
                 bail!(
                     child.span(), "pagebreaks are not allowed inside of containers";
@@ -104,9 +183,47 @@ impl<'a> Collector<'a, '_, '_> {
             }
         }
 
+        // Make sure that the flow covers the full extent of a wrapping float.
+        self.clear_wrap();
+
         Ok(self.output)
     }
 
+    /// Finds the floats that are anchored at an earlier child of the flow and
+    /// returns the indices of the anchors and the floats.
+    fn anchored(&mut self) -> Vec<(usize, usize)> {
+        let children = self.children;
+        let mut anchored = vec![];
+        for (i, &(child, styles)) in children.iter().enumerate() {
+            let Some(elem) = child.to_packed::<PlaceElem>() else { continue };
+            let Some(label) = elem.anchor.get(styles) else { continue };
+            if !elem.float.get(styles) {
+                continue;
+            }
+
+            // The anchor is either a child itself or, for elements that are
+            // realized into other ones, found through its start tag.
+            let labelled = |c: &Content| match c.to_packed::<TagElem>() {
+                Some(tag) => match &tag.tag {
+                    Tag::Start(elem, _) => elem.label() == Some(label),
+                    Tag::End(..) => false,
+                },
+                None => c.label() == Some(label),
+            };
+
+            match children[..i].iter().position(|&(c, _)| labelled(c)) {
+                Some(anchor) => anchored.push((anchor, i)),
+                None => self.engine.sink.warn(warning!(
+                    elem.span(),
+                    "float anchor was not found before the float";
+                    hint: "the anchor must be part of the same flow and precede the float";
+                    hint: "the float is placed at its own position instead";
+                )),
+            }
+        }
+        anchored
+    }
+
     /// Perform collection for inline-level children.
     fn run_inline(mut self) -> SourceResult<Vec<Child<'a>>> {
         // Extract leading and trailing tags.
@@ -143,11 +260,22 @@ impl<'a> Collector<'a, '_, '_> {
         Ok(self.output)
     }
 
+    /// Records that a top-level paragraph or block starts with the next child.
+    fn start(&mut self) {
+        if let Some(starts) = &mut self.starts {
+            starts.push(self.output.len());
+        }
+    }
+
     /// Collect vertical spacing into a relative or fractional child.
     fn v(&mut self, elem: &'a Packed<VElem>, styles: StyleChain<'a>) {
         self.output.push(match elem.amount {
             Spacing::Rel(rel) => {
-                Child::Rel(rel.resolve(styles), elem.weak.get(styles) as u8)
+                let rel = rel.resolve(styles);
+                if let Some(wrap) = &mut self.wrap {
+                    wrap.height -= rel.abs;
+                }
+                Child::Rel(rel, elem.weak.get(styles) as u8)
             }
             Spacing::Fr(fr) => Child::Fr(fr, elem.weak.get(styles) as u8),
         });
@@ -160,30 +288,258 @@ impl<'a> Collector<'a, '_, '_> {
         elem: &'a Packed<ParElem>,
         styles: StyleChain<'a>,
     ) -> SourceResult<()> {
-        let lines = crate::inline::layout_par(
-            elem,
-            self.engine,
-            self.locator.next(&elem.span()),
-            styles,
-            self.base,
-            self.expand,
-            self.par_situation,
-        )?
-        .into_frames();
-
+        let locator = self.locator.next(&elem.span());
         let spacing = elem.spacing.resolve(styles);
         let leading = elem.leading.resolve(styles);
 
+        // A wrapping float takes precedence over the page's excluded area.
+        let lines = if let Some(wrap) = self.wrap {
+            self.wrapped_par(elem, locator, styles, leading, wrap)?
+        } else if let Some(exclusion) = self.exclusion(elem, styles) {
+            self.excluded_par(elem, locator, styles, leading, exclusion)?
+        } else {
+            crate::inline::layout_par(
+                elem,
+                self.engine,
+                locator,
+                styles,
+                self.base,
+                self.expand,
+                self.par_situation,
+                None,
+            )?
+            .into_frames()
+        };
+
+        // Account for the space the paragraph takes up next to a wrapping
+        // float. If the float ends alongside the paragraph, determine how many
+        // of its lines are next to it.
+        let mut alongside = None;
+        if let Some(wrap) = &mut self.wrap {
+            let mut y = Abs::zero();
+            let mut count = 0;
+            for line in &lines {
+                if y >= wrap.height {
+                    break;
+                }
+                count += 1;
+                y += line.height() + leading;
+            }
+
+            let used: Abs = lines.iter().map(Frame::height).sum::<Abs>()
+                + leading * lines.len().saturating_sub(1) as f64;
+            wrap.height -= used + spacing;
+            if wrap.height <= Abs::zero() {
+                alongside = Some(count);
+                self.wrap = None;
+            }
+        }
+
         self.output.push(Child::Rel(spacing.into(), 4));
 
+        let start = self.output.len();
         self.lines(lines, leading, styles);
 
+        // The float is kept together with the lines next to it, so that they
+        // aren't narrowed in a region without the float.
+        if let Some(count) = alongside {
+            let end = if count == 0 { start } else { start + 2 * count - 1 };
+            self.output.insert(end, Child::Keep(false));
+        }
+
         self.output.push(Child::Rel(spacing.into(), 4));
         self.par_situation = ParSituation::Consecutive;
 
         Ok(())
     }
 
+    /// Lays out a paragraph that wraps around a float, narrowing the lines
+    /// that run alongside it.
+    ///
+    /// Which lines run alongside the float depends on the heights of the
+    /// lines, which in turn depend on the line breaks. Thus, we narrow more
+    /// and more lines until all lines starting above the float's bottom edge
+    /// are narrowed.
+    fn wrapped_par(
+        &mut self,
+        elem: &'a Packed<ParElem>,
+        locator: Locator<'a>,
+        styles: StyleChain<'a>,
+        leading: Abs,
+        wrap: Wrap,
+    ) -> SourceResult<Vec<Frame>> {
+        let layout = |engine: &mut Engine, narrowed: usize| {
+            crate::inline::layout_par(
+                elem,
+                engine,
+                locator.relayout(),
+                styles,
+                self.base,
+                self.expand,
+                self.par_situation,
+                (narrowed > 0).then_some(ParWrap {
+                    skip: 0,
+                    lines: narrowed,
+                    width: wrap.width,
+                    side: wrap.side,
+                }),
+            )
+        };
+
+        // The intermediate layouts are only trials, so their warnings must not
+        // surface.
+        let mut narrowed = 0;
+        loop {
+            let lines =
+                self.engine.trial(|engine| layout(engine, narrowed))?.into_frames();
+
+            let mut y = Abs::zero();
+            let mut overlapping = 0;
+            for line in &lines {
+                if y >= wrap.height {
+                    break;
+                }
+                overlapping += 1;
+                y += line.height() + leading;
+            }
+
+            if overlapping <= narrowed {
+                break;
+            }
+
+            narrowed = overlapping;
+        }
+
+        Ok(layout(self.engine, narrowed)?.into_frames())
+    }
+
+    /// Determines where the page's excluded area is relative to a paragraph.
+    ///
+    /// The paragraph's position is taken from the previous layout pass. In
+    /// the first pass, there is none yet and the paragraph isn't affected.
+    fn exclusion(
+        &mut self,
+        elem: &Packed<ParElem>,
+        styles: StyleChain,
+    ) -> Option<Exclusion> {
+        if !self.root {
+            return None;
+        }
+
+        let exclusion = styles.get(PageElem::exclusion)?;
+        let span = elem.span();
+        let position =
+            self.engine.introspect(PositionIntrospection(elem.location()?, span));
+
+        // Measure from the top of the content area of the paragraph's page.
+        let &(start, height) = self.page_regions(span).get(&position.page)?;
+        let offset = (position.point.y - start).max(Abs::zero());
+        let top = exclusion.top.resolve(styles) - offset;
+        Some(Exclusion {
+            side: exclusion.side.resolve(styles),
+            width: exclusion.width.resolve(styles),
+            top,
+            bottom: top + exclusion.height.resolve(styles),
+            end: height - offset,
+        })
+    }
+
+    /// Indexes the page region records of the previous layout pass by page,
+    /// so that each paragraph only needs a lookup.
+    fn page_regions(&mut self, span: Span) -> &FxHashMap<NonZeroUsize, (Abs, Abs)> {
+        self.page_regions.get_or_insert_with(|| {
+            let mut index = FxHashMap::default();
+            let regions = self
+                .engine
+                .introspect(QueryIntrospection(PageRegion::ELEM.select(), span));
+            for region in regions {
+                let Some(loc) = region.location() else { continue };
+                let Some(record) = region.to_packed::<PageRegion>() else { continue };
+                let start = self.engine.introspect(PositionIntrospection(loc, span));
+                index.entry(start.page).or_insert((start.point.y, record.height.abs));
+            }
+            index
+        })
+    }
+
+    /// Lays out a paragraph that runs alongside the page's excluded area,
+    /// narrowing the lines next to it.
+    ///
+    /// Like for wrapping floats, which lines are next to the area depends on
+    /// the line breaks. Thus, we lay out the paragraph until the narrowed
+    /// lines are exactly those next to the area.
+    fn excluded_par(
+        &mut self,
+        elem: &'a Packed<ParElem>,
+        locator: Locator<'a>,
+        styles: StyleChain<'a>,
+        leading: Abs,
+        exclusion: Exclusion,
+    ) -> SourceResult<Vec<Frame>> {
+        let layout = |engine: &mut Engine, wrap: Option<ParWrap>| {
+            crate::inline::layout_par(
+                elem,
+                engine,
+                locator.relayout(),
+                styles,
+                self.base,
+                self.expand,
+                self.par_situation,
+                wrap,
+            )
+        };
+
+        // As for wrapping floats, the intermediate layouts are only trials.
+        let mut wrap = None;
+        loop {
+            let lines = self.engine.trial(|engine| layout(engine, wrap))?.into_frames();
+
+            // Lines that don't fit into the region anymore end up in the next
+            // one and are thus not next to the area.
+            let mut y = Abs::zero();
+            let mut skip = 0;
+            let mut narrowed = 0;
+            for line in &lines {
+                let bottom = y + line.height();
+                if y >= exclusion.bottom || bottom > exclusion.end {
+                    break;
+                } else if bottom <= exclusion.top {
+                    skip += 1;
+                } else {
+                    narrowed += 1;
+                }
+                y = bottom + leading;
+            }
+
+            // Narrowing lines changes the line breaks, so we repeat until the
+            // narrowed lines settle. To ensure termination, we only ever narrow
+            // more lines.
+            match wrap {
+                Some(prev) if prev.skip != skip || prev.lines >= narrowed => break,
+                None if narrowed == 0 => break,
+                _ => {}
+            }
+
+            wrap = Some(ParWrap {
+                skip,
+                lines: narrowed,
+                width: exclusion.width,
+                side: exclusion.side,
+            });
+        }
+
+        Ok(layout(self.engine, wrap)?.into_frames())
+    }
+
+    /// Moves the following content below a float that paragraphs wrap
+    /// around, if any.
+    fn clear_wrap(&mut self) {
+        if let Some(wrap) = self.wrap.take() {
+            self.output.push(Child::Keep(false));
+            self.output.push(Child::Rel((wrap.height + wrap.clearance).into(), 0));
+        }
+    }
+
     /// Collect laid-out lines.
     fn lines(&mut self, lines: Vec<Frame>, leading: Abs, styles: StyleChain<'a>) {
         let align = styles.resolve(AlignElem::alignment);
@@ -224,19 +580,25 @@ impl<'a> Collector<'a, '_, '_> {
                 frame.height()
             };
 
-            self.output
-                .push(Child::Line(self.boxed(LineChild { frame, align, need })));
+            self.output.push(Child::Line(self.boxed(LineChild {
+                frame,
+                align,
+                need,
+                leading,
+            })));
         }
     }
 
     /// Collect a block into a [`SingleChild`] or [`MultiChild`] depending on
     /// whether it is breakable.
     fn block(&mut self, elem: &'a Packed<BlockElem>, styles: StyleChain<'a>) {
+        self.clear_wrap();
         let locator = self.locator.next(&elem.span());
         let align = styles.resolve(AlignElem::alignment);
         let alone = self.children.len() == 1;
         let sticky = elem.sticky.get(styles);
-        let breakable = elem.breakable.get(styles);
+        // Content that is scaled to fit is measured as a whole.
+        let breakable = elem.breakable.get(styles) && !elem.fit_width.get(styles);
         let fr = match elem.height.get(styles) {
             Sizing::Fr(fr) => Some(fr),
             _ => None,
@@ -291,9 +653,31 @@ impl<'a> Collector<'a, '_, '_> {
         let align_y = alignment.map(|align| align.y().map(|y| y.resolve(styles)));
         let scope = elem.scope.get(styles);
         let float = elem.float.get(styles);
+        let wrap = float && elem.wrap.get(styles);
+
+        // On a continuous page, floats are placed inline instead.
+        if float && self.root && styles.get(PageElem::continuous) {
+            return self.inline_float(elem, styles, align_x);
+        }
+
+        if wrap {
+            if align_x == FixedAlignment::Center {
+                bail!(
+                    elem.span(),
+                    "wrapping floats must be aligned to the left or right";
+                    hint: "try `place(left, float: true, wrap: true, ..)`";
+                );
+            }
+            if align_y != Smart::Custom(None) {
+                bail!(elem.span(), "wrapping floats cannot be aligned vertically");
+            }
+            if scope == PlacementScope::Parent {
+                bail!(elem.span(), "wrapping floats cannot be parent-scoped");
+            }
+        }
 
         match (float, align_y) {
-            (true, Smart::Custom(None | Some(FixedAlignment::Center))) => bail!(
+            (true, Smart::Custom(None | Some(FixedAlignment::Center))) if !wrap => bail!(
                 elem.span(),
                 "vertical floating placement must be `auto`, `top`, or `bottom`"
             ),
@@ -305,6 +689,59 @@ impl<'a> Collector<'a, '_, '_> {
             _ => {}
         }
 
+        let fallback = match elem.fallback.get(styles) {
+            Some(VAlignment::Horizon) => {
+                bail!(elem.span(), "fallback placement must be `top` or `bottom`")
+            }
+            fallback => fallback.filter(|_| float).map(|y| y.resolve(styles)),
+        };
+
+        let spacious = float && elem.placement.get(styles) == FloatPlacement::Spacious;
+        let eager = float && !wrap && elem.eager.get(styles);
+
+        // A float for the next region always ends up at its top, so options
+        // that choose a different spot for it are ignored.
+        let next = float && !wrap && elem.page.get(styles) == FloatPage::Next;
+        if next {
+            let conflict = if align_y == Smart::Custom(Some(FixedAlignment::End)) {
+                Some("bottom alignment")
+            } else if fallback.is_some() {
+                Some("a fallback")
+            } else if spacious {
+                Some("spacious placement")
+            } else if eager {
+                Some("eager placement")
+            } else if elem.same_page_as.get(styles).is_some() {
+                Some("`same-page-as`")
+            } else {
+                None
+            };
+
+            if let Some(conflict) = conflict {
+                self.engine.sink.warn(warning!(
+                    elem.span(),
+                    "float for the next page cannot be combined with {conflict}";
+                    hint: "it is placed at the top of the next region instead";
+                ));
+            }
+        }
+
+        let align_y =
+            if next { Smart::Custom(Some(FixedAlignment::Start)) } else { align_y };
+        let fallback = fallback.filter(|_| !next);
+        let spacious = spacious && !next;
+        let eager = eager && !next;
+
+        // Spacious placement chooses the side itself, so an explicit vertical
+        // alignment has no effect.
+        if spacious && matches!(align_y, Smart::Custom(Some(_))) {
+            self.engine.sink.warn(warning!(
+                elem.span(),
+                "spacious placement ignores the vertical alignment";
+                hint: "use `auto` or a horizontal alignment instead";
+            ));
+        }
+
         if !float && scope == PlacementScope::Parent {
             bail!(
                 elem.span(),
@@ -313,26 +750,114 @@ impl<'a> Collector<'a, '_, '_> {
             );
         }
 
+        // Resolve the element the float should share a page with. This is only
+        // relevant if the element comes after the float, as the float can
+        // otherwise simply be placed as usual.
+        let same_page_as = elem
+            .same_page_as
+            .get(styles)
+            .filter(|_| float && !next)
+            .and_then(|label| self.engine.introspector.query_label(label).ok())
+            .and_then(Content::location)
+            .filter(|&target| {
+                let selector = Selector::Location(target);
+                let location = elem.location().unwrap();
+                self.engine.introspector.query_count_before(&selector, location) == 0
+            });
+
         let locator = self.locator.next(&elem.span());
         let clearance = elem.clearance.resolve(styles);
+        let gap = elem.gap.resolve(styles).unwrap_or(clearance);
         let delta = Axes::new(elem.dx.get(styles), elem.dy.get(styles)).resolve(styles);
-        self.output.push(Child::Placed(self.boxed(PlacedChild {
+        let child = self.boxed(PlacedChild {
             align_x,
             align_y,
+            fallback,
+            spacious,
+            eager,
+            next,
             scope,
-            float,
+            // A wrapping float stays where it is in the flow. The wrapping
+            // itself happens during collection.
+            float: float && !wrap,
             clearance,
+            gap,
             delta,
+            same_page_as,
+            rotate: elem.rotate.get(styles),
             elem,
             styles,
             locator,
             alignment,
             cell: CachedCell::new(),
-        })));
+        });
+
+        if wrap {
+            // Start wrapping around the new float below the previous one.
+            self.clear_wrap();
+
+            let frame = child.layout(self.engine, self.base)?;
+            let width = frame.width() + clearance;
+            let height = frame.height();
+            if width < self.base.x {
+                self.wrap = Some(Wrap { side: align_x, width, height, clearance });
+                self.output.push(Child::Keep(true));
+            } else {
+                // There is no room to wrap around the float, so the following
+                // content directly moves below it.
+                self.output.push(Child::Placed(child));
+                self.output.push(Child::Rel((height + clearance).into(), 0));
+                return Ok(());
+            }
+        }
+
+        self.output.push(Child::Placed(child));
 
         Ok(())
     }
 
+    /// Collects a float on a continuous page. It is placed at its position in
+    /// the flow, followed by spacing that moves the following content below
+    /// it.
+    fn inline_float(
+        &mut self,
+        elem: &'a Packed<PlaceElem>,
+        styles: StyleChain<'a>,
+        align_x: FixedAlignment,
+    ) -> SourceResult<()> {
+        self.clear_wrap();
+
+        let locator = self.locator.next(&elem.span());
+        let clearance = elem.clearance.resolve(styles);
+        let gap = elem.gap.resolve(styles).unwrap_or(clearance);
+        let delta = Axes::new(elem.dx.get(styles), elem.dy.get(styles)).resolve(styles);
+        let child = self.boxed(PlacedChild {
+            align_x,
+            align_y: Smart::Custom(None),
+            fallback: None,
+            spacious: false,
+            eager: false,
+            next: false,
+            scope: PlacementScope::Column,
+            float: false,
+            clearance,
+            gap,
+            delta,
+            same_page_as: None,
+            rotate: elem.rotate.get(styles),
+            elem,
+            styles,
+            locator,
+            alignment: elem.alignment.get(styles),
+            cell: CachedCell::new(),
+        });
+
+        let height = child.layout(self.engine, self.base)?.height();
+        self.output.push(Child::Placed(child));
+        self.output.push(Child::Rel((height + clearance).into(), 0));
+        Ok(())
+    }
+
     /// Wraps a value in a bump-allocated box to reduce its footprint in the
     /// [`Child`] enum.
     fn boxed<T>(&self, value: T) -> BumpBox<'a, T> {
@@ -363,6 +888,11 @@ pub enum Child<'a> {
     Flush,
     /// An explicit column break.
     Break(bool),
+    /// A manual balance point for balanced columns.
+    Balance,
+    /// The start (`true`) or end (`false`) of a group of children that are
+    /// kept together.
+    Keep(bool),
 }
 
 /// A child that encapsulates a layouted line of a paragraph.
@@ -371,6 +901,7 @@ pub struct LineChild {
     pub frame: Frame,
     pub align: Axes<FixedAlignment>,
     pub need: Abs,
+    pub leading: Abs,
 }
 
 /// A child that encapsulates a prepared unbreakable block.
@@ -406,6 +937,11 @@ impl SingleChild<'_> {
             )
         })
     }
+
+    /// The element's span.
+    pub fn span(&self) -> Span {
+        self.elem.span()
+    }
 }
 
 /// The cached, internal implementation of [`SingleChild::layout`].
@@ -482,6 +1018,11 @@ impl<'a> MultiChild<'a> {
         Ok((frame, spill))
     }
 
+    /// The element's span.
+    pub fn span(&self) -> Span {
+        self.elem.span()
+    }
+
     /// The shared internal implementation of [`Self::layout`] and
     /// [`MultiSpill::layout`].
     fn layout_full(
@@ -541,6 +1082,11 @@ fn layout_multi_impl(
 }
 
 /// The spilled remains of a `MultiChild` that broke across two regions.
+///
+/// The block is always laid out into all of its regions at once, with the
+/// already committed regions recorded in the backlog. This way, breakable
+/// content within it, like a table with a repeated header, sees every region
+/// boundary and can handle its continuations itself.
 #[derive(Debug, Clone)]
 pub struct MultiSpill<'a, 'b> {
     pub(super) exist_non_empty_frame: bool,
@@ -551,7 +1097,7 @@ pub struct MultiSpill<'a, 'b> {
     min_backlog_len: usize,
 }
 
-impl MultiSpill<'_, '_> {
+impl<'a, 'b> MultiSpill<'a, 'b> {
     /// Build the spill's frames given regions.
     pub fn layout(
         mut self,
@@ -615,6 +1161,46 @@ impl MultiSpill<'_, '_> {
     pub fn align(&self) -> Axes<FixedAlignment> {
         self.multi.align
     }
+
+    /// The breakable block this spill belongs to.
+    pub fn multi(&self) -> &'b MultiChild<'a> {
+        self.multi
+    }
+
+    /// Detach the spill from its breakable block, so that it can outlive it.
+    pub fn detach(&self) -> DetachedSpill {
+        DetachedSpill {
+            exist_non_empty_frame: self.exist_non_empty_frame,
+            first: self.first,
+            full: self.full,
+            backlog: self.backlog.clone(),
+            min_backlog_len: self.min_backlog_len,
+        }
+    }
+}
+
+/// The state of a `MultiSpill`, detached from its breakable block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetachedSpill {
+    exist_non_empty_frame: bool,
+    first: Abs,
+    full: Abs,
+    backlog: Vec<Abs>,
+    min_backlog_len: usize,
+}
+
+impl DetachedSpill {
+    /// Reattach the spill to its breakable block.
+    pub fn attach<'a, 'b>(&self, multi: &'b MultiChild<'a>) -> MultiSpill<'a, 'b> {
+        MultiSpill {
+            exist_non_empty_frame: self.exist_non_empty_frame,
+            multi,
+            first: self.first,
+            full: self.full,
+            backlog: self.backlog.clone(),
+            min_backlog_len: self.min_backlog_len,
+        }
+    }
 }
 
 /// A child that encapsulates a prepared placed element.
@@ -622,10 +1208,17 @@ impl MultiSpill<'_, '_> {
 pub struct PlacedChild<'a> {
     pub align_x: FixedAlignment,
     pub align_y: Smart<Option<FixedAlignment>>,
+    pub fallback: Option<FixedAlignment>,
+    pub spacious: bool,
+    pub eager: bool,
+    pub next: bool,
     pub scope: PlacementScope,
     pub float: bool,
     pub clearance: Abs,
+    pub gap: Abs,
     pub delta: Axes<Rel<Abs>>,
+    pub same_page_as: Option<Location>,
+    rotate: Angle,
     elem: &'a Packed<PlaceElem>,
     styles: StyleChain<'a>,
     locator: Locator<'a>,
@@ -641,10 +1234,25 @@ impl PlacedChild<'_> {
             let aligned = AlignElem::alignment.set(align).wrap();
             let styles = self.styles.chain(&aligned);
 
+            // Rotated content is laid out with reflow such that the frame
+            // covers the rotated bounding box and the right amount of space is
+            // reserved for it.
+            let rotated;
+            let body = if self.rotate.is_zero() {
+                &self.elem.body
+            } else {
+                rotated = RotateElem::new(self.elem.body.clone())
+                    .with_angle(self.rotate)
+                    .with_reflow(true)
+                    .pack()
+                    .spanned(self.elem.span());
+                &rotated
+            };
+
             let mut frame = layout_and_modify(styles, |styles| {
                 crate::layout_frame(
                     engine,
-                    &self.elem.body,
+                    body,
                     self.locator.relayout(),
                     styles,
                     Region::new(base, Axes::splat(false)),
@@ -666,6 +1274,11 @@ impl PlacedChild<'_> {
     pub fn location(&self) -> Location {
         self.elem.location().unwrap()
     }
+
+    /// The element's span.
+    pub fn span(&self) -> Span {
+        self.elem.span()
+    }
 }
 
 /// Wraps a parameterized computation and caches its latest output.
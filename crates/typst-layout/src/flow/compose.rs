@@ -1,25 +1,36 @@
 use std::num::NonZeroUsize;
 
-use typst_library::diag::SourceResult;
+use comemo::Track;
+use ecow::{EcoVec, eco_format, eco_vec};
+use rustc_hash::FxHashSet;
+use typst_library::diag::{SourceResult, error};
 use typst_library::engine::Engine;
-use typst_library::foundations::{Content, NativeElement, Packed, Resolve, Smart};
+use typst_library::foundations::{
+    ClosureParamInfo, Content, Context, LocatableSelector, NativeElement, Packed,
+    ParamInfo, Resolve, Selector, Smart, StyleChain, Value,
+};
 use typst_library::introspection::{
     Counter, CounterDisplayElem, CounterState, CounterUpdate, Location, Locator,
-    SplitLocator, Tag,
+    QueryFirstIntrospection, SplitLocator, Tag,
 };
 use typst_library::layout::{
-    Abs, Axes, Dir, FixedAlignment, Fragment, Frame, FrameItem, FrameParent, Inherit,
-    OuterHAlignment, PlacementScope, Point, Region, Regions, Rel, Size,
+    Abs, Axes, BottomOrder, ColumnRegion, Dir, FixedAlignment, FloatDeferral, Fragment,
+    Frame, FrameItem, FrameParent, GutterFillExtent, Inherit, OuterHAlignment,
+    PlacementScope, Point, Region, Regions, Rel, Size,
 };
 use typst_library::model::{
-    FootnoteElem, FootnoteEntry, LineNumberingScope, Numbering, ParLineMarker,
+    FootnoteArea, FootnoteElem, FootnoteEntry, FootnoteLine, FootnoteTally,
+    LineNumberingScope, Numbering, ParLineMarker, ParLineTotal,
 };
 use typst_library::pdf::ArtifactKind;
+use typst_library::text::TextElem;
+use typst_library::visualize::{Curve, Geometry};
 use typst_syntax::Span;
 use typst_utils::{NonZeroExt, Numeric};
 
 use super::{
-    Config, FlowMode, FlowResult, LineNumberConfig, PlacedChild, Stop, Work, distribute,
+    Child, Config, FlowMode, FlowResult, LineNumberConfig, PlacedChild, Stop, Work,
+    distribute, marker_tags,
 };
 
 /// Composes the contents of a single page/region. A region can have multiple
@@ -32,6 +43,7 @@ use super::{
 ///
 /// To lay out the in-flow contents of individual subregions, the composer
 /// invokes [distribution](distribute()).
+#[typst_macros::time]
 pub fn compose(
     engine: &mut Engine,
     work: &mut Work,
@@ -47,9 +59,13 @@ pub fn compose(
         page_insertions: Insertions::default(),
         column_insertions: Insertions::default(),
         column_balancing_height: None,
+        balance_point: None,
         work,
         footnote_spill: None,
         footnote_queue: vec![],
+        footnote_depth: 0,
+        footnote_tally: (0, 0),
+        trailing_spacing: Abs::zero(),
     }
     .page(locator, regions)
 }
@@ -71,12 +87,22 @@ pub struct Composer<'a, 'b, 'x, 'y> {
     page_insertions: Insertions<'a, 'b>,
     column_insertions: Insertions<'a, 'b>,
     column_balancing_height: Option<Abs>,
+    // The column with the region's first manual balance point and the height
+    // used in it up to that point.
+    balance_point: Option<(usize, Abs)>,
     // These are here because they have to survive relayout (we could lose the
     // footnotes otherwise). For floats, we revisit them anyway, so it's okay to
     // use `work.floats` directly. This is not super clean; probably there's a
     // better way.
     footnote_spill: Option<std::vec::IntoIter<Frame>>,
-    footnote_queue: Vec<Packed<FootnoteElem>>,
+    footnote_queue: Vec<(Packed<FootnoteElem>, usize)>,
+    // How deeply the footnote that is currently being laid out is nested in
+    // other footnotes.
+    footnote_depth: usize,
+    // The number of new and continued footnote entries in the region so far.
+    footnote_tally: (usize, usize),
+    // The spacing at the end of the most recently distributed column.
+    trailing_spacing: Abs,
 }
 
 impl<'a, 'b> Composer<'a, 'b, '_, '_> {
@@ -85,17 +111,22 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         // This loop can restart region layout when requested to do so by a
         // `Stop`. This happens when there is a parent-scoped float.
         let checkpoint = self.work.clone();
-        let output = loop {
+        let mut output = loop {
             // Shrink the available space by the space used by page
             // insertions.
             let mut pod = regions;
             pod.size.y -= self.page_insertions.height();
 
+            // The columns are laid out from scratch, so their footnotes are
+            // counted again.
+            self.footnote_tally = (0, 0);
+
             match self.page_contents(locator.relayout(), pod) {
                 Ok(frame) => break frame,
                 Err(Stop::Finish(_)) => unreachable!(),
                 Err(Stop::Relayout(PlacementScope::Column)) => unreachable!(),
-                Err(Stop::Relayout(PlacementScope::Parent)) => {
+                Err(stop @ Stop::Relayout(PlacementScope::Parent)) => {
+                    self.config.trace(&stop);
                     *self.work = checkpoint.clone();
                 }
                 Err(Stop::Error(err)) => return Err(err),
@@ -103,16 +134,97 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         };
         drop(checkpoint);
 
-        Ok(self.page_insertions.finalize(self.work, self.config, output, None))
+        self.push_footnote_tally(&locator, &mut output);
+        self.push_column_region(&locator, &mut output);
+        let insertions = std::mem::take(&mut self.page_insertions);
+        self.push_deferrals(&locator, &insertions, &mut output);
+        let (output, _) = insertions.finalize(self.work, self.config, output, None);
+        Ok(output)
+    }
+
+    /// Records the number of footnote entries in the region through a tally
+    /// element, such that it can be queried.
+    fn push_footnote_tally(&mut self, locator: &Locator, frame: &mut Frame) {
+        let (new, continued) = self.footnote_tally;
+        if new == 0 && continued == 0 {
+            return;
+        }
+
+        let tally = Packed::new(FootnoteTally::new(new, continued));
+        let key = typst_utils::hash128(&tally);
+        let mut locator = locator.relayout().split();
+        let loc = locator.next_location(self.engine, key, Span::detached());
+        frame.prepend_multiple(marker_tags(tally, loc, key, Point::zero()));
+    }
+
+    /// Records where the footnote area of the column ended up through a marker
+    /// element, such that it can be queried.
+    fn push_footnote_area(
+        &mut self,
+        locator: &Locator,
+        pos: Point,
+        size: Size,
+        frame: &mut Frame,
+    ) {
+        let area = Packed::new(FootnoteArea::new(size.x.into(), size.y.into()));
+        let key = typst_utils::hash128(&("footnote-area", self.work.region, self.column));
+        let mut locator = locator.relayout().split();
+        let loc = locator.next_location(self.engine, key, Span::detached());
+        frame.push_multiple(marker_tags(area, loc, key, pos));
+    }
+
+    /// Records for each float in the insertions that was queued before being
+    /// placed how many regions it was deferred across.
+    fn push_deferrals(
+        &mut self,
+        locator: &Locator,
+        insertions: &Insertions,
+        frame: &mut Frame,
+    ) {
+        let floats = insertions.top_floats.iter().chain(&insertions.bottom_floats);
+        let mut locator = locator.relayout().split();
+        for (placed, _) in floats {
+            let float = placed.location();
+            let Some(&regions) = self.work.deferrals.get(&float) else { continue };
+            let deferral = Packed::new(FloatDeferral::new(float, regions));
+            let key = typst_utils::hash128(&("deferral", float));
+            let loc = locator.next_location(self.engine, key, Span::detached());
+            frame.prepend_multiple(marker_tags(deferral, loc, key, Point::zero()));
+        }
+    }
+
+    /// Records the column width of a page or columns region through a marker
+    /// element, such that it can be queried.
+    fn push_column_region(&mut self, locator: &Locator, frame: &mut Frame) {
+        if self.config.mode != FlowMode::Root && self.config.columns.count == 1 {
+            return;
+        }
+
+        let region = Packed::new(ColumnRegion::new(
+            self.config.columns.width.into(),
+            frame.width().into(),
+            frame.height().into(),
+        ));
+        let key = typst_utils::hash128(&"column-region");
+        let mut locator = locator.relayout().split();
+        let loc = locator.next_location(self.engine, key, Span::detached());
+        frame.prepend_multiple(marker_tags(region, loc, key, Point::zero()));
     }
 
     /// Lay out the inner contents of a container/page.
     fn page_contents(&mut self, locator: Locator, regions: Regions) -> FlowResult<Frame> {
         // No point in create column regions, if there's just one!
         if self.config.columns.count == 1 {
-            return self.column(locator, regions).map(|(frame, _)| frame);
+            let (mut frame, _) = self.column(locator, regions)?;
+            let width = frame.width();
+            self.fill_columns(&mut frame, &[(Abs::zero(), width)])?;
+            return Ok(frame);
         }
 
+        // The columns are laid out from scratch, so the balance point is
+        // found again.
+        self.balance_point = None;
+
         // Create a backlog for multi-column layout.
         let column_height = regions.size.y;
         let backlog: Vec<_> = std::iter::once(&column_height)
@@ -139,11 +251,13 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         let mut offset = Abs::zero();
         let mut locator = locator.split();
         let mut total_used_height = Abs::zero();
+        let mut columns = Vec::with_capacity(self.config.columns.count);
+        let mut heights = Vec::with_capacity(self.config.columns.count);
 
         // Lay out the columns and stitch them together.
         for i in 0..self.config.columns.count {
             self.column = i;
-            let (frame, used_height) = self.column(locator.next(&()), inner)?;
+            let (mut frame, used_height) = self.column(locator.next(&()), inner)?;
             total_used_height += used_height;
 
             if !regions.expand.y {
@@ -151,12 +265,15 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
             }
 
             let width = frame.width();
-            let x = if self.config.columns.dir == Dir::LTR {
+            let x = if self.config.columns.dir.x == Dir::LTR {
                 offset
             } else {
                 regions.size.x - offset - width
             };
-            offset += width + self.config.columns.gutter;
+            offset +=
+                width + self.config.columns.gutter.after(i, self.config.columns.count);
+            columns.push((x, width));
+            heights.push(used_height);
 
             // During distribution, the baseline of the region is set to the
             // baseline of the first frame - e.g., the first paragraph line.
@@ -166,22 +283,111 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
                 output.set_baseline(frame.baseline());
             }
 
+            // Record the column such that queries can follow the reading
+            // order.
+            frame.set_column(i);
             output.push_frame(Point::with_x(x), frame);
             inner.next();
         }
 
-        // Column balancing with re-layout
+        // Column balancing with re-layout. A manual balance point takes
+        // precedence over the automatically determined height.
         if self.config.columns.balanced && self.work.done() {
-            let height = total_used_height / self.config.columns.count as f64;
+            let height = match self.balance_point {
+                Some((_, height)) => height,
+                None => total_used_height / self.config.columns.count as f64,
+            };
             if self.column_balancing_height.is_none_or(|h| h < height) {
                 self.column_balancing_height = Some(height);
                 return Err(Stop::Relayout(PlacementScope::Parent));
             }
         }
 
+        self.fill_columns(&mut output, &columns)?;
+        self.fill_gutters(locator.next(&"gutter"), &mut output, &columns, &heights)?;
         Ok(output)
     }
 
+    /// Fills the background of each column with the configured paint. The
+    /// columns are given by their horizontal offset and width.
+    fn fill_columns(
+        &mut self,
+        output: &mut Frame,
+        columns: &[(Abs, Abs)],
+    ) -> FlowResult<()> {
+        let height = output.height();
+        for (i, &(x, width)) in columns.iter().enumerate() {
+            let fill = self.config.columns.fill.resolve(
+                self.engine,
+                self.config.shared,
+                i,
+                self.work.region,
+            )?;
+            if let Some(paint) = fill {
+                let rect = Geometry::Rect(Size::new(width, height)).filled(paint);
+                output
+                    .prepend(Point::with_x(x), FrameItem::Shape(rect, Span::detached()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Tiles the configured gutter fill vertically into each gutter between
+    /// the columns, which are given by their horizontal offset and width and
+    /// the height of their content. The last tile of each gutter is clipped at
+    /// the bottom of the columns or, if configured, where the content of one
+    /// of the adjacent columns ends.
+    fn fill_gutters(
+        &mut self,
+        locator: Locator,
+        output: &mut Frame,
+        columns: &[(Abs, Abs)],
+        heights: &[Abs],
+    ) -> FlowResult<()> {
+        let Some(fill) = &self.config.columns.gutter_fill else { return Ok(()) };
+        let mut locator = locator.split();
+        for (pair, used) in columns.windows(2).zip(heights.windows(2)) {
+            let height = match self.config.columns.gutter_fill_extent {
+                GutterFillExtent::Full => output.height(),
+                GutterFillExtent::ContentOverlap => {
+                    output.height().min(used[0]).min(used[1])
+                }
+            };
+
+            // The columns may be ordered right-to-left.
+            let (left, right) = if pair[0].0 <= pair[1].0 {
+                (pair[0], pair[1])
+            } else {
+                (pair[1], pair[0])
+            };
+            let x = left.0 + left.1;
+            let size = Size::new(right.0 - x, height);
+            let tile = crate::layout_frame(
+                self.engine,
+                fill,
+                locator.next(&()),
+                self.config.shared,
+                Region::new(Size::new(size.x, Abs::inf()), Axes::new(true, false)),
+            )?;
+            if tile.height() <= Abs::zero()
+                || height <= Abs::zero()
+                || !height.is_finite()
+            {
+                continue;
+            }
+
+            let mut gutter = Frame::soft(size);
+            let mut y = Abs::zero();
+            while y < height {
+                gutter.push_frame(Point::with_y(y), tile.clone());
+                y += tile.height();
+            }
+            gutter.clip(Curve::rect(size));
+            output.push_frame(Point::with_x(x), gutter);
+        }
+        Ok(())
+    }
+
     /// Lay out a column, including column insertions.
     ///
     /// Returns a `FlowResult` containing a tuple of
@@ -191,6 +397,11 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         // Reset column insertion when starting a new column.
         self.column_insertions = Insertions::default();
 
+        // Footnotes that continue in the same column resume here.
+        if self.config.footnote.same_column {
+            self.work.unpark_footnotes(self.column);
+        }
+
         // Process footnote spill.
         if let Some(spill) = self.work.footnote_spill.take() {
             self.footnote_spill(spill, regions.base())?;
@@ -199,7 +410,13 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         // This loop can restart column layout when requested to do so by a
         // `Stop`. This happens when there is a column-scoped float.
         let checkpoint = self.work.clone();
-        let (inner, used_height) = loop {
+        let (mut inner, used_height) = loop {
+            // A balance point found in a discarded layout of this column is
+            // found again.
+            if self.balance_point.is_some_and(|(column, _)| column == self.column) {
+                self.balance_point = None;
+            }
+
             // Shrink the available space by the space used by column
             // insertions.
             let mut pod = regions;
@@ -216,7 +433,8 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
             match self.column_contents(pod, balancing_target) {
                 Ok((frame, used_height)) => break (frame, used_height + float_height),
                 Err(Stop::Finish(_)) => unreachable!(),
-                Err(Stop::Relayout(PlacementScope::Column)) => {
+                Err(stop @ Stop::Relayout(PlacementScope::Column)) => {
+                    self.config.trace(&stop);
                     *self.work = checkpoint.clone();
                 }
                 err => return err,
@@ -228,14 +446,24 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         if let Some(spill) = self.footnote_spill.take() {
             self.work.footnote_spill = Some(spill);
         }
+        if self.config.footnote.same_column {
+            self.work.park_footnotes(self.column);
+        }
 
-        let insertions = std::mem::take(&mut self.column_insertions);
-        let mut output = insertions.finalize(
+        let mut insertions = std::mem::take(&mut self.column_insertions);
+        insertions.trailing_spacing = self.trailing_spacing;
+        self.push_deferrals(&locator, &insertions, &mut inner);
+        self.footnote_tally.0 += insertions.new_footnotes;
+        self.footnote_tally.1 += insertions.continued_footnotes;
+        let (mut output, area) = insertions.finalize(
             self.work,
             self.config,
             inner,
             self.column_balancing_height,
         );
+        if let Some((pos, size)) = area {
+            self.push_footnote_area(&locator, pos, size, &mut output);
+        }
 
         // Lay out per-column line numbers.
         if let Some(line_config) = &self.config.line_numbers {
@@ -266,18 +494,97 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         balancing_target: Option<Abs>,
     ) -> FlowResult<(Frame, Abs)> {
         // Process pending footnotes.
-        for note in std::mem::take(&mut self.work.footnotes) {
-            self.footnote(note, &mut regions.clone(), Abs::zero(), false)?;
+        for (note, depth) in std::mem::take(&mut self.work.footnotes) {
+            self.footnote_depth = depth;
+            let result = self.footnote(note, &mut regions.clone(), Abs::zero(), false);
+            self.footnote_depth = 0;
+            result?;
         }
 
-        // Process pending floats.
-        for placed in std::mem::take(&mut self.work.floats) {
+        // Process pending floats. Those deferred to this region come first, so
+        // that they end up at its very top. Floats that span the parent are
+        // only deferred to the next region rather than the next column, so
+        // they wait until its first column.
+        let (deferred, waiting): (EcoVec<_>, EcoVec<_>) =
+            std::mem::take(&mut self.work.deferred)
+                .into_iter()
+                .partition(|placed| {
+                    self.column == 0 || placed.scope == PlacementScope::Column
+                });
+        self.work.deferred = waiting;
+        let queued = std::mem::take(&mut self.work.floats);
+        for &placed in &queued {
+            self.work.defer(placed.location());
+        }
+        for placed in deferred.into_iter().chain(queued) {
             self.float(placed, &regions, false, false)?;
         }
 
+        // Pull eager floats from later in the flow into this region.
+        self.eager_floats(&regions)?;
+
         distribute(self, regions, balancing_target)
     }
 
+    /// Places eager floats that appear later in the flow into the current
+    /// region if they fit there.
+    ///
+    /// Only floats near the current position are considered, i.e. those that
+    /// follow within about two regions worth of content. This way, a float
+    /// from the end of the flow can't be pulled all the way to its start. The
+    /// estimate stops at breakable blocks that spill and at explicit breaks.
+    ///
+    /// Eager floats that don't fit aren't queued, but simply stay where they
+    /// are in the flow. They are tried again in the next region. To not
+    /// disrupt the order of queued floats, nothing is pulled forward while
+    /// there are any.
+    fn eager_floats(&mut self, regions: &Regions) -> FlowResult<()> {
+        if !self.work.floats.is_empty() {
+            return Ok(());
+        }
+
+        let base = regions.base();
+        let limit = regions.full * 2.0;
+        let mut ahead = Abs::zero();
+
+        let children = self.work.children;
+        for child in children {
+            match child {
+                Child::Rel(amount, _) => ahead += amount.relative_to(base.y),
+                Child::Line(line) => ahead += line.leading + line.frame.height(),
+                Child::Single(single) => {
+                    let region = Region::new(base, regions.expand);
+                    ahead += single.layout(self.engine, region)?.height();
+                }
+                Child::Multi(multi) => {
+                    let (frame, spill) = multi.layout(self.engine, *regions)?;
+                    if spill.is_some() {
+                        break;
+                    }
+                    ahead += frame.height();
+                }
+                Child::Break(_) => break,
+                Child::Placed(placed)
+                    if placed.eager && placed.same_page_as.is_none() =>
+                {
+                    let result = self.float(placed, regions, false, false);
+
+                    // If the float didn't fit, it was queued. Since the queue
+                    // was empty before, we can simply clear it again.
+                    self.work.floats.clear();
+                    result?;
+                }
+                _ => {}
+            }
+
+            if ahead > limit {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Lays out an item with floating placement.
     ///
     /// This is called from within [`distribute()`]. When the float fits, this
@@ -339,8 +646,58 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         };
 
         // We only require clearance if there is other content.
-        let clearance = if clearance { placed.clearance } else { Abs::zero() };
-        let need = frame.height() + clearance;
+        let spacing = if clearance { placed.clearance } else { Abs::zero() };
+        let need = frame.height() + spacing;
+
+        // Determine the float's vertical alignment. With spacious placement,
+        // the side with more free space wins. Otherwise, we can unwrap the
+        // inner `Option` because `Custom(None)` is checked for during
+        // collection.
+        let mut align_y = if placed.spacious {
+            match placed.scope {
+                PlacementScope::Column => self.column_insertions.spacious_side(),
+                PlacementScope::Parent => self.page_insertions.spacious_side(),
+            }
+        } else {
+            placed.align_y.map(Option::unwrap).unwrap_or_else(|| {
+                // When the float's vertical midpoint would be above the middle
+                // of the page if it were layouted in-flow, we use top
+                // alignment. Otherwise, we use bottom alignment.
+                let used = base.y - remaining;
+                let half = need / 2.0;
+                let ratio = (used + half) / base.y;
+                if ratio <= 0.5 { FixedAlignment::Start } else { FixedAlignment::End }
+            })
+        };
+
+        // If the preferred side is already taken, move to the fallback side or,
+        // if that one is taken as well, defer the float to the next region.
+        if let Some(fallback) = placed.fallback {
+            let area = match placed.scope {
+                PlacementScope::Column => &self.column_insertions,
+                PlacementScope::Parent => &self.page_insertions,
+            };
+            if area.has_floats(align_y) {
+                if !area.has_floats(fallback) {
+                    align_y = fallback;
+                } else if regions.may_progress() {
+                    self.work.floats.push(placed);
+                    return Ok(());
+                }
+            }
+        }
+
+        // Once the side is known, the float's spacing is known as well: It
+        // is stacked with the gap if there are other floats at the side.
+        let area = match placed.scope {
+            PlacementScope::Column => &self.column_insertions,
+            PlacementScope::Parent => &self.page_insertions,
+        };
+        let need = if clearance {
+            area.float_need(placed, frame.height(), align_y)
+        } else {
+            need
+        };
 
         // If the float doesn't fit, queue it for the next region.
         if !remaining.fits(need) && regions.may_progress() {
@@ -351,18 +708,6 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         // Handle footnotes in the float.
         self.footnotes(regions, &frame, need, false, migratable)?;
 
-        // Determine the float's vertical alignment. We can unwrap the inner
-        // `Option` because `Custom(None)` is checked for during collection.
-        let align_y = placed.align_y.map(Option::unwrap).unwrap_or_else(|| {
-            // When the float's vertical midpoint would be above the middle of
-            // the page if it were layouted in-flow, we use top alignment.
-            // Otherwise, we use bottom alignment.
-            let used = base.y - remaining;
-            let half = need / 2.0;
-            let ratio = (used + half) / base.y;
-            if ratio <= 0.5 { FixedAlignment::Start } else { FixedAlignment::End }
-        });
-
         // Select the insertion area where we'll put this float.
         let area = match placed.scope {
             PlacementScope::Column => &mut self.column_insertions,
@@ -394,8 +739,9 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         breakable: bool,
         migratable: bool,
     ) -> FlowResult<()> {
-        // Footnotes are only supported at the root level.
-        if self.config.mode != FlowMode::Root {
+        // Footnotes are only supported at the root level. In a draft, their
+        // entries are skipped altogether.
+        if self.config.mode != FlowMode::Root || self.config.footnote.draft {
             return Ok(());
         }
 
@@ -452,6 +798,12 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         Ok(())
     }
 
+    /// Whether queued footnotes continue in a later column of the current
+    /// region, i.e. still on the same page.
+    fn footnote_column_follows(&self) -> bool {
+        !self.config.footnote.same_column && self.column + 1 < self.config.columns.count
+    }
+
     /// Handles a single footnote.
     fn footnote(
         &mut self,
@@ -469,8 +821,17 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         // If there is already a queued spill or footnote, queue this one as
         // well. We don't want to disrupt the order.
         let area = &mut self.column_insertions;
+        let strict = elem.strict_page.get(StyleChain::default());
         if self.footnote_spill.is_some() || !self.footnote_queue.is_empty() {
-            self.footnote_queue.push(elem);
+            // A strict footnote can only be queued if it still ends up on the
+            // same page. Otherwise, we try to move its origin frame along.
+            if strict && !self.footnote_column_follows() {
+                if migratable && regions.may_progress() {
+                    return Err(Stop::Finish(false));
+                }
+                return Err(strict_footnote_error(&elem));
+            }
+            self.footnote_queue.push((elem, self.footnote_depth));
             return Ok(());
         }
 
@@ -485,13 +846,40 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
             separator = Some(frame);
         }
 
-        // Prepare regions for the footnote.
+        // Lay out the rule preceding the entry, if any.
+        let rule = match &self.config.footnote.rule {
+            Some(rule) => Some(layout_footnote_rule(
+                self.engine,
+                self.config,
+                rule,
+                regions.base(),
+            )?),
+            None => None,
+        };
+        let rule_need = rule.as_ref().map_or(Abs::zero(), Frame::height);
+
+        // Nested footnotes are indented according to their depth.
+        let width = self.config.footnote.column_width(regions.size.x);
+        let indent = (self.config.footnote.nested_indent * self.footnote_depth as f64)
+            .min(width)
+            .max(Abs::zero());
+
+        // Prepare regions for the footnote. With multiple footnote columns,
+        // the entry is as wide as one of them.
         let mut pod = *regions;
         pod.expand.y = false;
-        pod.size.y -= flow_need + separator_need + self.config.footnote.gap;
+        pod.size.x = width - indent;
+        pod.size.y -= flow_need + separator_need + rule_need + self.config.footnote.gap;
 
         // Layout the footnote entry.
-        let frames = layout_footnote(self.engine, self.config, &elem, pod)?.into_frames();
+        let mut frames =
+            layout_footnote(self.engine, self.config, &elem, pod)?.into_frames();
+        if !indent.is_zero() {
+            for frame in &mut frames {
+                frame.size_mut().x += indent;
+                frame.translate(Point::with_x(indent));
+            }
+        }
 
         // Find nested footnotes in the entry.
         let nested = find_in_frames::<FootnoteElem>(&frames);
@@ -502,7 +890,6 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         // Extract the first frame.
         let mut iter = frames.into_iter();
         let first = iter.next().unwrap();
-        let note_need = self.config.footnote.gap + first.height();
 
         // If the first frame is empty, then none of its content fit. If
         // possible, we then migrate the origin frame to the next region to
@@ -536,30 +923,76 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
             if migratable && regions.may_progress() {
                 return Err(Stop::Finish(false));
             } else if regions.may_progress() || !flow_need.is_zero() {
-                self.footnote_queue.push(elem);
+                if strict && !self.footnote_column_follows() {
+                    return Err(strict_footnote_error(&elem));
+                }
+                self.footnote_queue.push((elem, self.footnote_depth));
                 return Ok(());
             }
         }
 
+        // A strict footnote must not break across pages either. If it would,
+        // we try to migrate the origin frame such that the whole entry fits on
+        // the next page.
+        if strict && !iter.as_slice().is_empty() {
+            if migratable && regions.may_progress() {
+                return Err(Stop::Finish(false));
+            }
+            return Err(strict_footnote_error(&elem));
+        }
+
+        // An unbreakable entry that would break across regions instead moves
+        // to the next region together with its origin frame, but only if it
+        // then fits in full.
+        if !self.config.footnote.breakable
+            && !iter.as_slice().is_empty()
+            && migratable
+            && regions.may_progress()
+        {
+            let entry_need: Abs =
+                first.height() + iter.as_slice().iter().map(Frame::height).sum::<Abs>();
+            let need = flow_need
+                + separator_need
+                + rule_need
+                + self.config.footnote.gap
+                + entry_need;
+            if regions.iter().nth(1).is_some_and(|region| region.y.fits(need)) {
+                return Err(Stop::Finish(false));
+            }
+        }
+
         // Save the separator.
+        let before = area.footnote_size;
         if let Some(frame) = separator {
             area.push_footnote_separator(self.config, frame);
-            regions.size.y -= separator_need;
         }
 
-        // Save the footnote's frame.
+        // Attach the rule to the entry. If the entry breaks across regions,
+        // only its first part is preceded by the rule.
+        let first = match rule {
+            Some(rule) => stack_footnote_rule(rule, first),
+            None => first,
+        };
+
+        // Save the footnote's frame. With multiple footnote columns, the area
+        // grows by less than the entry's height.
         area.push_footnote(self.config, first);
+        area.new_footnotes += 1;
         area.skips.push(loc);
-        regions.size.y -= note_need;
+        regions.size.y -= area.footnote_size - before;
 
         // Save the spill.
         if !iter.as_slice().is_empty() {
             self.footnote_spill = Some(iter);
         }
 
-        // Lay out nested footnotes.
+        // Lay out nested footnotes. They directly follow their parent's entry,
+        // one level deeper.
         for (_, note) in nested {
-            match self.footnote(note, regions, flow_need, migratable) {
+            self.footnote_depth += 1;
+            let result = self.footnote(note, regions, flow_need, migratable);
+            self.footnote_depth -= 1;
+            match result {
                 // This footnote was already processed or queued.
                 Ok(()) => {}
                 // Footnotes always request a relayout when processed for the
@@ -594,6 +1027,7 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
         // Save the footnote's frame.
         let frame = iter.next().unwrap();
         area.push_footnote(self.config, frame);
+        area.continued_footnotes += 1;
 
         // Save the spill.
         if !iter.as_slice().is_empty() {
@@ -605,44 +1039,235 @@ impl<'a, 'b> Composer<'a, 'b, '_, '_> {
 
     /// Checks whether an insertion was already processed and doesn't need to be
     /// handled again.
-    fn skipped(&self, loc: Location) -> bool {
+    pub fn skipped(&self, loc: Location) -> bool {
         self.work.skips.contains(&loc)
             || self.page_insertions.skips.contains(&loc)
             || self.column_insertions.skips.contains(&loc)
     }
 
+    /// Whether the current column continues content from a previous column or
+    /// region.
+    pub fn continuation(&self) -> bool {
+        self.column > 0 || self.work.region > 0
+    }
+
+    /// Whether the current column starts directly below a parent-scoped float
+    /// at the top of the region. The float's clearance already separates it
+    /// from the column's content.
+    pub fn below_spanning_float(&self) -> bool {
+        !self.page_insertions.top_floats.is_empty()
+    }
+
+    /// Records a manual balance point after the given height of distributed
+    /// content. Returns whether it determines the height of balanced columns,
+    /// which is only the case for the first balance point of a region that
+    /// isn't in the last column.
+    pub fn balance_point(&mut self, used: Abs) -> bool {
+        let columns = &self.config.columns;
+        if !columns.balanced
+            || self.column + 1 >= columns.count
+            || self.balance_point.is_some()
+        {
+            return false;
+        }
+
+        let height = used + self.column_insertions.float_height();
+        self.balance_point = Some((self.column, height));
+        true
+    }
+
+    /// Records the spacing at the end of the column that was just distributed,
+    /// which footnotes may collapse their clearance with.
+    pub fn trailing_spacing(&mut self, spacing: Abs) {
+        self.trailing_spacing = spacing;
+    }
+
     /// The amount of width needed by insertions.
     pub fn insertion_width(&self) -> Abs {
         self.column_insertions.width.max(self.page_insertions.width)
     }
 }
 
-/// Lay out the footnote separator, typically a line.
+/// The error for a footnote with `strict-page` whose entry doesn't fit on the
+/// page of its reference.
+fn strict_footnote_error(elem: &Packed<FootnoteElem>) -> Stop {
+    let mut diag = error!(
+        elem.span(),
+        "footnote entry does not fit on the same page as its reference";
+        hint: "try shortening the footnote or disabling `strict-page`";
+    );
+    if let Some(body) = elem.body_content()
+        && !body.span().is_detached()
+    {
+        diag.spanned_hint("the entry's content is defined here", body.span());
+    }
+    Stop::Error(eco_vec![diag])
+}
+
+/// Cross-checks the footnote markers in the finished regions of a root flow
+/// against the entries placed in them.
+///
+/// Label-based footnotes whose target is declared in a different flow are
+/// skipped, as their entry is placed there.
+pub fn validate_footnotes(engine: &mut Engine, frames: &[Frame]) -> SourceResult<()> {
+    let notes = find_in_frames::<FootnoteElem>(frames);
+    let entries = find_in_frames::<FootnoteEntry>(frames);
+    let declared: FxHashSet<Location> = notes
+        .iter()
+        .filter(|(_, note)| !note.is_ref())
+        .filter_map(|(_, note)| note.location())
+        .collect();
+    let placed: FxHashSet<Location> = entries
+        .iter()
+        .filter_map(|(_, entry)| entry.note.location())
+        .collect();
+
+    let mut errors = EcoVec::new();
+    for (_, note) in &notes {
+        let target = match note.declaration_location(engine) {
+            Ok(target) => target,
+            Err(message) => {
+                errors.push(error!(note.span(), "{message}"));
+                continue;
+            }
+        };
+
+        if note.is_ref() && !declared.contains(&target) {
+            continue;
+        }
+
+        if !placed.contains(&target) {
+            errors.push(error!(
+                note.span(),
+                "footnote has no matching entry";
+                hint: "its entry was not placed in the same page run";
+            ));
+        }
+    }
+
+    for (_, entry) in &entries {
+        if entry.note.location().is_none_or(|loc| !declared.contains(&loc)) {
+            errors.push(error!(
+                entry.note.span(),
+                "footnote entry has no matching reference";
+                hint: "its footnote marker was not laid out in the same page run";
+            ));
+        }
+    }
+
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+/// Lay out the footnote separator, typically a line, including its inset.
 fn layout_footnote_separator(
     engine: &mut Engine,
     config: &Config,
     base: Size,
 ) -> SourceResult<Frame> {
-    crate::layout_frame(
+    let inset = config.footnote.separator_inset;
+    let pod = Size::new(base.x - inset.sum_by_axis().x, base.y);
+    let frame = crate::layout_frame(
         engine,
         &config.footnote.separator,
         Locator::root(),
         config.shared,
+        Region::new(pod, Axes::new(config.footnote.expand, false)),
+    )?;
+
+    if inset.is_zero() {
+        return Ok(frame);
+    }
+
+    let mut output = Frame::soft(frame.size() + inset.sum_by_axis());
+    output.push_frame(Point::new(inset.left, inset.top), frame);
+    Ok(output)
+}
+
+/// Lay out the rule preceding each footnote entry.
+fn layout_footnote_rule(
+    engine: &mut Engine,
+    config: &Config,
+    rule: &Content,
+    base: Size,
+) -> SourceResult<Frame> {
+    crate::layout_frame(
+        engine,
+        rule,
+        Locator::root(),
+        config.shared,
         Region::new(base, Axes::new(config.footnote.expand, false)),
     )
 }
 
+/// Stack a footnote entry's rule on top of the entry's first frame.
+fn stack_footnote_rule(rule: Frame, entry: Frame) -> Frame {
+    let size = Size::new(rule.width().max(entry.width()), rule.height() + entry.height());
+    let mut output = Frame::soft(size);
+    let y = rule.height();
+    output.push_frame(Point::zero(), rule);
+    output.push_frame(Point::with_y(y), entry);
+    output
+}
+
 /// Lay out a footnote.
+///
+/// The regions available to a footnote entry depend on the position of its
+/// marker, so they change whenever the in-flow content above it changes. To
+/// still reuse the cached layout of unchanged entries in that case, we first
+/// lay the entry out into regions that only depend on the dimensions of the
+/// page: The actual regions, but with a full first region. Only if the result
+/// doesn't fit into the space that is actually available, we lay it out again
+/// into the exact regions.
+///
+/// If the probe's result is a single frame that fits, it is the same as that
+/// of the exact regions: All content fits in either case and as the regions
+/// only differ in the height of the first one, breaks and other decisions
+/// that depend on the following regions come out the same. Content whose size
+/// depends on the region's height, like fractional spacing, makes the frame
+/// too tall to fit unless the heights are equal.
+#[typst_macros::time(name = "footnote", span = elem.span())]
 fn layout_footnote(
     engine: &mut Engine,
     config: &Config,
     elem: &Packed<FootnoteElem>,
     pod: Regions,
+) -> SourceResult<Fragment> {
+    let probe = Regions { size: Size::new(pod.size.x, pod.full), ..pod };
+    let fragment = layout_footnote_entry(engine, config, elem, probe)?;
+    if let [frame] = fragment.as_slice()
+        && pod.size.y.fits(frame.height())
+    {
+        return Ok(fragment);
+    }
+
+    layout_footnote_entry(engine, config, elem, pod)
+}
+
+/// Lay out a footnote's entry into the given regions.
+fn layout_footnote_entry(
+    engine: &mut Engine,
+    config: &Config,
+    elem: &Packed<FootnoteElem>,
+    pod: Regions,
 ) -> SourceResult<Fragment> {
     let loc = elem.location().unwrap();
+
+    // The number of the line holding the marker was recorded in the previous
+    // layout iteration, if the line is numbered.
+    let mut entry = FootnoteEntry::new(elem.clone());
+    entry.line = engine
+        .introspect(QueryFirstIntrospection(
+            Selector::Location(FootnoteLine::location_of(loc)),
+            elem.span(),
+        ))
+        .and_then(|record| record.to_packed::<FootnoteLine>().map(|record| record.line));
+
+    // The entry is laid out with the flow's shared styles rather than those
+    // at the footnote's marker. Thus, styles for the marker and the entry
+    // don't leak into each other.
     crate::layout_fragment(
         engine,
-        &FootnoteEntry::new(elem.clone())
+        &entry
             .pack()
             .spanned(elem.span())
             // We attach a well-known derived location to the entry so that the
@@ -670,6 +1295,9 @@ struct Insertions<'a, 'b> {
     top_size: Abs,
     bottom_size: Abs,
     footnote_size: Abs,
+    new_footnotes: usize,
+    continued_footnotes: usize,
+    trailing_spacing: Abs,
     width: Abs,
     skips: Vec<Location>,
 }
@@ -684,7 +1312,7 @@ impl<'a, 'b> Insertions<'a, 'b> {
     ) {
         self.width.set_max(frame.width());
 
-        let amount = frame.height() + placed.clearance;
+        let amount = self.float_need(placed, frame.height(), align_y);
         let pair = (placed, frame);
 
         if align_y == FixedAlignment::Start {
@@ -696,18 +1324,52 @@ impl<'a, 'b> Insertions<'a, 'b> {
         }
     }
 
+    /// The amount of space that a float of the given height takes up when
+    /// pushed to the given side, including its spacing.
+    fn float_need(
+        &self,
+        placed: &PlacedChild,
+        height: Abs,
+        align_y: FixedAlignment,
+    ) -> Abs {
+        // Each float is separated from the flow by its clearance. Once another
+        // float comes between them, the gap applies instead.
+        if align_y == FixedAlignment::Start {
+            let prev = self
+                .top_floats
+                .last()
+                .map_or(Abs::zero(), |(prev, _)| prev.gap - prev.clearance);
+            prev + height + placed.clearance
+        } else if self.bottom_floats.is_empty() {
+            height + placed.clearance
+        } else {
+            height + placed.gap
+        }
+    }
+
     /// Add a footnote to the bottom area.
     fn push_footnote(&mut self, config: &Config, frame: Frame) {
         self.width.set_max(frame.width());
-        self.footnote_size += config.footnote.gap + frame.height();
         self.footnotes.push(frame);
+        self.update_footnote_size(config);
     }
 
     /// Add a footnote separator to the bottom area.
     fn push_footnote_separator(&mut self, config: &Config, frame: Frame) {
         self.width.set_max(frame.width());
-        self.footnote_size += config.footnote.clearance + frame.height();
         self.footnote_separator = Some(frame);
+        self.update_footnote_size(config);
+    }
+
+    /// Recompute the height of the footnote area. With multiple footnote
+    /// columns, this is the height of the tallest balanced column.
+    fn update_footnote_size(&mut self, config: &Config) {
+        let heights: Vec<Abs> = self.footnotes.iter().map(Frame::height).collect();
+        self.footnote_size = balance_footnotes(config, &heights).0
+            + self
+                .footnote_separator
+                .as_ref()
+                .map_or(Abs::zero(), |frame| config.footnote.clearance + frame.height());
     }
 
     /// The combined height of the top and bottom area (including clearances).
@@ -717,6 +1379,24 @@ impl<'a, 'b> Insertions<'a, 'b> {
         self.top_size + self.bottom_size + self.footnote_size
     }
 
+    /// Whether there already are floats at the given side.
+    fn has_floats(&self, align_y: FixedAlignment) -> bool {
+        match align_y {
+            FixedAlignment::Start => !self.top_floats.is_empty(),
+            _ => !self.bottom_floats.is_empty(),
+        }
+    }
+
+    /// The side whose band has more free space, i.e. whose insertions take up
+    /// less space. Ties go to the top.
+    fn spacious_side(&self) -> FixedAlignment {
+        if self.top_size <= self.bottom_size + self.footnote_size {
+            FixedAlignment::Start
+        } else {
+            FixedAlignment::End
+        }
+    }
+
     /// The combined height of the top and bottom area for floats (including
     /// clearances) but excluding footnotes.
     fn float_height(&self) -> Abs {
@@ -725,13 +1405,16 @@ impl<'a, 'b> Insertions<'a, 'b> {
 
     /// Produce a frame for the full region based on the `inner` frame produced
     /// by distribution or column layout.
+    ///
+    /// Also returns the position and size of the footnote area in the
+    /// resulting frame if there are any footnotes.
     fn finalize(
         self,
         work: &mut Work,
         config: &Config,
         inner: Frame,
         column_height: Option<Abs>,
-    ) -> Frame {
+    ) -> (Frame, Option<(Point, Size)>) {
         work.extend_skips(&self.skips);
 
         if self.top_floats.is_empty()
@@ -739,18 +1422,36 @@ impl<'a, 'b> Insertions<'a, 'b> {
             && self.footnote_separator.is_none()
             && self.footnotes.is_empty()
         {
-            return inner;
+            return (inner, None);
         }
 
-        let size = inner.size() + Size::with_y(self.height());
+        // If the footnotes directly follow the flow, the clearance may collapse
+        // with the flow's trailing spacing.
+        let overlap = if config.footnote.collapse_clearance
+            && self.footnote_separator.is_some()
+            && (self.bottom_floats.is_empty()
+                || config.footnote.bottom_order == BottomOrder::FootnotesFirst)
+        {
+            self.trailing_spacing.min(config.footnote.clearance)
+        } else {
+            Abs::zero()
+        };
+
+        let size = inner.size() + Size::with_y(self.height() - overlap);
         let mut output = Frame::soft(size);
 
+        // Floats are marked with their location such that introspection can
+        // tell where they ended up.
         let mut offset_top = Abs::zero();
-        for (placed, frame) in self.top_floats {
+        let mut top_floats = self.top_floats.into_iter().peekable();
+        while let Some((placed, mut frame)) = top_floats.next() {
+            frame.set_placement(placed.location());
             let x = placed.align_x.position(size.x - frame.width());
             let y = offset_top;
             let delta = placed.delta.zip_map(size, Rel::relative_to).to_point();
-            offset_top += frame.height() + placed.clearance;
+            let spacing =
+                if top_floats.peek().is_some() { placed.gap } else { placed.clearance };
+            offset_top += frame.height() + spacing;
             output.push_frame(Point::new(x, y) + delta, frame);
         }
 
@@ -765,19 +1466,31 @@ impl<'a, 'b> Insertions<'a, 'b> {
 
         output.push_frame(Point::with_y(self.top_size), inner);
 
-        // We put floats first and then footnotes. This differs from what LaTeX
-        // does and is a little inconsistent w.r.t column vs page floats (page
-        // floats are below footnotes because footnotes are per column), but
-        // it's what most people (including myself) seem to intuitively expect.
-        // We experimented with the LaTeX ordering in 0.12.0-rc1, but folks were
-        // surprised and considered this strange. In LaTeX, it can be changed
-        // with `\usepackage[bottom]{footmisc}`. We could also consider adding
-        // configuration in the future.
-
-        let mut float_offset_bottom =
-            column_height.unwrap_or(size.y - self.footnote_size) - self.bottom_size;
-        for (placed, frame) in self.bottom_floats {
-            float_offset_bottom += placed.clearance;
+        // By default, we put floats first and then footnotes. This differs
+        // from what LaTeX does and is a little inconsistent w.r.t column vs
+        // page floats (page floats are below footnotes because footnotes are
+        // per column), but it's what most people (including myself) seem to
+        // intuitively expect. We experimented with the LaTeX ordering in
+        // 0.12.0-rc1, but folks were surprised and considered this strange. In
+        // LaTeX, it can be changed with `\usepackage[bottom]{footmisc}`, here
+        // with `page.bottom-order`. Floats below the footnotes always sit at the
+        // very bottom, even if the columns are balanced.
+        let (mut float_offset_bottom, mut footnote_offset_bottom) =
+            match config.footnote.bottom_order {
+                BottomOrder::FloatsFirst => (
+                    column_height.unwrap_or(size.y - self.footnote_size)
+                        - self.bottom_size,
+                    size.y - self.footnote_size,
+                ),
+                BottomOrder::FootnotesFirst => (
+                    size.y - self.bottom_size,
+                    size.y - self.bottom_size - self.footnote_size,
+                ),
+            };
+
+        for (i, (placed, mut frame)) in self.bottom_floats.into_iter().enumerate() {
+            frame.set_placement(placed.location());
+            float_offset_bottom += if i == 0 { placed.clearance } else { placed.gap };
             let x = placed.align_x.position(size.x - frame.width());
             let y = float_offset_bottom;
             let delta = placed.delta.zip_map(size, Rel::relative_to).to_point();
@@ -785,21 +1498,114 @@ impl<'a, 'b> Insertions<'a, 'b> {
             output.push_frame(Point::new(x, y) + delta, frame);
         }
 
-        let mut footnote_offset_bottom = size.y - self.footnote_size;
+        // An aligned footnote area is as wide as its widest frame. An area
+        // with multiple columns always spans the full width.
+        let area_align =
+            config.footnote.area_align.filter(|_| config.footnote.columns == 1);
+        let area_width = match area_align {
+            Some(_) => self
+                .footnote_separator
+                .iter()
+                .chain(&self.footnotes)
+                .map(Frame::width)
+                .fold(Abs::zero(), Abs::max),
+            None => size.x,
+        };
+        let footnote_x =
+            area_align.map_or(Abs::zero(), |align| align.position(size.x - area_width));
+
+        // The area spans from the separator, or the first entry if there is
+        // none, to the lowest entry.
+        let mut area_top = None;
+        let mut area_bottom = Abs::zero();
+
         if let Some(frame) = self.footnote_separator {
             footnote_offset_bottom += config.footnote.clearance;
             let y = footnote_offset_bottom;
+            area_top = Some(y);
             footnote_offset_bottom += frame.height();
-            output.push_frame(Point::with_y(y), frame);
+            area_bottom = footnote_offset_bottom;
+            output.push_frame(Point::new(footnote_x, y), frame);
         }
-        for frame in self.footnotes {
+
+        // The entries fill the footnote columns one after another. Each column
+        // starts right below the separator.
+        let heights: Vec<Abs> = self.footnotes.iter().map(Frame::height).collect();
+        let (_, assignment) = balance_footnotes(config, &heights);
+        let count = config.footnote.columns;
+        let column_width = config.footnote.column_width(size.x);
+        let column_top = footnote_offset_bottom;
+        let mut current = 0;
+        for (frame, column) in self.footnotes.into_iter().zip(assignment) {
+            if column != current {
+                current = column;
+                footnote_offset_bottom = column_top;
+            }
+
+            let index = if config.columns.dir.x.is_positive() {
+                column
+            } else {
+                count - 1 - column
+            };
+            let x = footnote_x
+                + index as f64 * (column_width + config.footnote.column_gutter);
+
             footnote_offset_bottom += config.footnote.gap;
             let y = footnote_offset_bottom;
+            area_top.get_or_insert(y);
             footnote_offset_bottom += frame.height();
-            output.push_frame(Point::with_y(y), frame);
+            area_bottom.set_max(footnote_offset_bottom);
+            output.push_frame(Point::new(x, y), frame);
         }
 
-        output
+        let area = area_top.map(|top| {
+            (Point::new(footnote_x, top), Size::new(area_width, area_bottom - top))
+        });
+
+        (output, area)
+    }
+}
+
+/// Distributes footnote entries with the given heights over the footnote
+/// area's columns such that the tallest column is as short as possible.
+/// Entries are kept in order and aren't broken.
+///
+/// Returns the height of the tallest column, including the gap above each
+/// entry, and the column of each entry.
+fn balance_footnotes(config: &Config, heights: &[Abs]) -> (Abs, Vec<usize>) {
+    let needs: Vec<Abs> = heights.iter().map(|&h| config.footnote.gap + h).collect();
+    let total: Abs = needs.iter().copied().sum();
+    let count = config.footnote.columns;
+    if count <= 1 {
+        return (total, vec![0; needs.len()]);
+    }
+
+    // Start with the smallest height that could possibly work and grow it by
+    // the smallest amount that moves an entry up to a previous column until
+    // the entries fit into the columns.
+    let mut height = needs.iter().copied().fold(total / count as f64, Abs::max);
+    loop {
+        let mut assignment = Vec::with_capacity(needs.len());
+        let mut column = 0;
+        let mut used = Abs::zero();
+        let mut tallest = Abs::zero();
+        let mut excess = Abs::inf();
+        for &need in &needs {
+            if !used.is_zero() && !height.fits(used + need) {
+                excess.set_min(used + need - height);
+                column += 1;
+                used = Abs::zero();
+            }
+            used += need;
+            tallest.set_max(used);
+            assignment.push(column);
+        }
+
+        if column < count {
+            return (tallest, assignment);
+        }
+
+        height += excess;
     }
 }
 
@@ -842,19 +1648,99 @@ fn layout_line_numbers(
     // This is used to skip lines that are too close together.
     let mut prev_bottom = None;
 
+    // The value of the line counter before this column's lines and the number
+    // of lines numbered in this column so far. These are only needed to
+    // display every n-th number and for numbering functions that take the
+    // absolute line number.
+    let stepped = lines.iter().any(|(_, marker)| marker.number_step.get() > 1);
+    let absolute = lines.iter().any(|(_, marker)| takes_absolute(&marker.numbering));
+    let padded = lines.iter().any(|(_, marker)| padding(marker).is_some());
+    let notes: Vec<_> = find_in_frame::<FootnoteElem>(output)
+        .into_iter()
+        .filter(|(_, note)| !note.is_ref())
+        .collect();
+    let base = if stepped || absolute || padded || !notes.is_empty() {
+        line_count_before(engine, line_config, column, &lines[0].1)?
+    } else {
+        0
+    };
+    let total_base =
+        if absolute { total_line_count_before(engine, &lines[0].1)? } else { 0 };
+    let mut numbered = 0;
+
     // Buffer line number frames so we can align them horizontally later before
     // placing, based on the width of the largest line number.
     let mut line_numbers = vec![];
 
+    // The width of a fixed-width gutter, along with its number of digits.
+    let mut fixed_width: Option<(NonZeroUsize, Abs)> = None;
+
+    // The number of each line, by position, so that footnotes can learn the
+    // line they are on. Lines that aren't numbered have no number.
+    let mut numbers_by_y = vec![];
+
     // Layout the lines.
     for &(y, ref marker) in &lines {
         if prev_bottom.is_some_and(|bottom| y < bottom) {
             // Lines are too close together. Display as the same line number.
+            numbers_by_y.push((y, Some(base + numbered)));
             continue;
         }
 
+        // Skip lines that shouldn't be numbered. They don't advance the line
+        // counter either.
+        if let Smart::Custom(filter) = &marker.number_lines
+            && !filter.accepts(
+                engine,
+                config.shared,
+                marker.width.unwrap_or_default(),
+                marker.full.unwrap_or_default(),
+                marker.text.as_deref().unwrap_or_default(),
+            )?
+        {
+            numbers_by_y.push((y, None));
+            continue;
+        }
+
+        // Lines whose number isn't a multiple of the step still advance the
+        // line counter, but don't display their number.
+        numbered += 1;
+        let display = (base + numbered) % marker.number_step.get() as u64 == 0;
+
         // Layout the number and record its width in search of the maximum.
-        let frame = layout_line_number(engine, config, &mut locator, &marker.numbering)?;
+        let number = base + numbered;
+        numbers_by_y.push((y, Some(number)));
+        let numbers = if takes_absolute(&marker.numbering) {
+            Some(vec![number, total_base + numbered])
+        } else if padding(marker).is_some() {
+            Some(vec![number])
+        } else {
+            None
+        };
+        let frame = layout_line_number(
+            engine,
+            config,
+            &mut locator,
+            &marker.numbering,
+            display,
+            numbers.as_deref(),
+            padding(marker),
+        )?;
+
+        // A fixed-width gutter is at least as wide as the given number of
+        // zero digits.
+        if let Some(digits) = marker.numbering_width {
+            let width = match fixed_width {
+                Some((prev, width)) if prev == digits => width,
+                _ => {
+                    let width =
+                        layout_digits_width(engine, config, &mut locator, digits)?;
+                    fixed_width = Some((digits, width));
+                    width
+                }
+            };
+            max_number_width.set_max(width);
+        }
 
         // Note that this line.y is larger than the previous due to sorting.
         // Therefore, the check at the top of the loop ensures no line numbers
@@ -907,9 +1793,104 @@ fn layout_line_numbers(
         output.push_frame(pos, frame);
     }
 
+    // Record the line of each footnote marker. Line markers sit at the
+    // baseline, so a footnote marker belongs to the first line whose baseline
+    // is at or below it. Footnotes below all lines, e.g. in entries, aren't
+    // on a line of this column.
+    for (y, note) in notes {
+        let Some(&(_, Some(number))) = numbers_by_y.iter().find(|&&(line, _)| line >= y)
+        else {
+            continue;
+        };
+        let Ok(number) = usize::try_from(number) else { continue };
+
+        let loc = FootnoteLine::location_of(note.location().unwrap());
+        let record = Packed::new(FootnoteLine::new(number));
+        let key = typst_utils::hash128(&loc);
+        output.push_multiple(marker_tags(record, loc, key, Point::with_y(y)));
+    }
+
     Ok(())
 }
 
+/// Determines the value of the line number counter before the lines of the
+/// current column, given one of the column's line markers.
+fn line_count_before(
+    engine: &mut Engine,
+    line_config: &LineNumberConfig,
+    column: usize,
+    marker: &Packed<ParLineMarker>,
+) -> SourceResult<u64> {
+    // Page-scoped line numbers are reset in the first column. The reset comes
+    // after the column's lines in document order, so the counter's value at
+    // the lines doesn't reflect it yet.
+    if column == 0 && line_config.scope == LineNumberingScope::Page {
+        return Ok(0);
+    }
+
+    // All line numbers of this column come after its lines in document order,
+    // while those of previous columns and pages come before.
+    let counter = Counter::of(ParLineMarker::ELEM);
+    let selector = LocatableSelector(Selector::Location(marker.location().unwrap()));
+    let state = counter.at(engine, Context::none().track(), marker.span(), selector)?;
+    Ok(state.first())
+}
+
+/// Determines the value of the absolute line number counter before the lines of
+/// the current column, given one of the column's line markers.
+fn total_line_count_before(
+    engine: &mut Engine,
+    marker: &Packed<ParLineMarker>,
+) -> SourceResult<u64> {
+    // The absolute counter is never reset, so the updates of this column come
+    // after its lines in document order, just like those of the line counter.
+    let counter = Counter::of(ParLineTotal::ELEM);
+    let selector = LocatableSelector(Selector::Location(marker.location().unwrap()));
+    let state = counter.at(engine, Context::none().track(), marker.span(), selector)?;
+    Ok(state.first())
+}
+
+/// The number of digits to pad the marker's line number to with leading zeros,
+/// if any.
+fn padding(marker: &Packed<ParLineMarker>) -> Option<NonZeroUsize> {
+    marker.numbering_width.filter(|_| marker.number_pad)
+}
+
+/// Measures the width of the given number of zero digits, as they would be
+/// displayed in a line number.
+fn layout_digits_width(
+    engine: &mut Engine,
+    config: &Config,
+    locator: &mut SplitLocator,
+    digits: NonZeroUsize,
+) -> SourceResult<Abs> {
+    let content = TextElem::packed("0".repeat(digits.get()));
+    let frame = crate::layout_frame(
+        engine,
+        &content,
+        locator.next(&()),
+        config.shared,
+        Region::new(Axes::splat(Abs::inf()), Axes::splat(false)),
+    )?;
+    Ok(frame.width())
+}
+
+/// Whether the line numbering is a function that takes the absolute line
+/// number as a second positional argument.
+fn takes_absolute(numbering: &Numbering) -> bool {
+    let Numbering::Func(func) = numbering else { return false };
+    // Only user-defined functions opt into the second argument, such that
+    // native functions like `numbering.with(..)` keep receiving one number.
+    let positional = func
+        .params()
+        .filter(|param| match param {
+            ParamInfo::Closure(info) => matches!(info.v, ClosureParamInfo::Pos { .. }),
+            _ => false,
+        })
+        .count();
+    positional >= 2
+}
+
 /// Creates a frame that resets the line number counter.
 fn layout_line_number_reset(
     engine: &mut Engine,
@@ -938,21 +1919,54 @@ fn layout_line_number_reset(
 /// layer). When we find a solution to this, we should switch to a counter on
 /// `ParLine` instead, thus exposing the counter as `counter(par.line)` to the
 /// user.
+///
+/// Alongside, the counter of absolute line numbers is stepped. If `numbers`
+/// holds the line's number (and possibly its absolute number), the numbering is
+/// applied to them directly instead of displaying the counter. A textual result
+/// is then padded with leading zeros to the given number of digits, if any.
 fn layout_line_number(
     engine: &mut Engine,
     config: &Config,
     locator: &mut SplitLocator,
     numbering: &Numbering,
+    display: bool,
+    numbers: Option<&[u64]>,
+    pad: Option<NonZeroUsize>,
 ) -> SourceResult<Frame> {
     let counter = Counter::of(ParLineMarker::ELEM);
+    let total = Counter::of(ParLineTotal::ELEM);
     let update = CounterUpdate::Step(NonZeroUsize::ONE);
-    let numbering = Smart::Custom(numbering.clone());
 
-    // Combine counter update and display into the content we'll layout.
-    let content = Content::sequence(vec![
-        counter.clone().update(Span::detached(), update),
-        CounterDisplayElem::new(counter, numbering, false).pack(),
-    ]);
+    // Combine counter updates and display into the content we'll layout.
+    let mut seq = vec![
+        counter.clone().update(Span::detached(), update.clone()),
+        total.update(Span::detached(), update),
+    ];
+    if display {
+        seq.push(match numbers {
+            Some(numbers) => {
+                let context = Context::new(None, Some(config.shared));
+                let value = numbering.apply(
+                    engine,
+                    context.track(),
+                    Span::detached(),
+                    numbers,
+                )?;
+                match (value, pad) {
+                    (Value::Str(text), Some(pad)) => {
+                        let zeros = pad.get().saturating_sub(text.chars().count());
+                        TextElem::packed(eco_format!("{}{text}", "0".repeat(zeros)))
+                    }
+                    (value, _) => value.display(),
+                }
+            }
+            None => {
+                let numbering = Smart::Custom(numbering.clone());
+                CounterDisplayElem::new(counter, numbering, false).pack()
+            }
+        });
+    }
+    let content = Content::sequence(seq);
     let content = content.artifact(ArtifactKind::LineNumber);
 
     // Layout the number.
@@ -0,0 +1,162 @@
+//! Structured dumps of flow layout for debugging.
+//!
+//! Only compiled with the `debug-flow` feature. A dump contains the collected
+//! children of a flow and the sequence of control flow events that occurred
+//! while distributing them into regions. The events can be extracted from a
+//! dump again with [`events`], such that tests can check break decisions.
+
+use std::cell::RefCell;
+use std::fmt::{self, Display, Formatter};
+
+use ecow::EcoString;
+use serde_json::{Value, json};
+use typst_library::introspection::Tag;
+use typst_library::layout::{Abs, Frame, PlacementScope, Size};
+
+use super::{Child, Stop};
+
+/// A control flow event that occurred during flow layout.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlowEvent {
+    /// A subregion was finished, either due to a lack of space (`false`) or
+    /// an explicit column break (`true`).
+    Finish(bool),
+    /// The given scope was relayouted.
+    Relayout(PlacementScope),
+    /// Layout failed with the given number of errors.
+    Error(usize),
+    /// A region with the given size was finished.
+    Region(Size),
+}
+
+impl Display for FlowEvent {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Finish(false) => f.write_str("finish"),
+            Self::Finish(true) => f.write_str("finish(forced)"),
+            Self::Relayout(scope) => write!(f, "relayout({})", scope_name(*scope)),
+            Self::Error(count) => write!(f, "error({count})"),
+            Self::Region(_) => f.write_str("region"),
+        }
+    }
+}
+
+/// Records the control flow events of a flow.
+#[derive(Default)]
+pub struct Trace(RefCell<Vec<FlowEvent>>);
+
+impl Trace {
+    /// Record a control flow event.
+    pub fn stop(&self, stop: &Stop) {
+        let event = match stop {
+            Stop::Finish(forced) => FlowEvent::Finish(*forced),
+            Stop::Relayout(scope) => FlowEvent::Relayout(*scope),
+            Stop::Error(errors) => FlowEvent::Error(errors.len()),
+        };
+        self.0.borrow_mut().push(event);
+    }
+
+    /// Record that a region was finished.
+    pub fn region(&self, frame: &Frame) {
+        self.0.borrow_mut().push(FlowEvent::Region(frame.size()));
+    }
+}
+
+/// Serialize the children of a flow and the recorded events to JSON.
+pub fn dump(children: &[Child], trace: &Trace) -> EcoString {
+    let children: Vec<_> = children.iter().map(child).collect();
+    let events: Vec<_> = trace.0.borrow().iter().map(event).collect();
+    json!({ "children": children, "events": events }).to_string().into()
+}
+
+/// Extracts the control flow events from a dump of a flow, in the order in
+/// which they occurred.
+///
+/// Returns `None` if the dump isn't one of a flow.
+pub fn events(dump: &str) -> Option<Vec<FlowEvent>> {
+    let value: Value = serde_json::from_str(dump).ok()?;
+    value.get("events")?.as_array()?.iter().map(parse_event).collect()
+}
+
+/// Serialize a single event.
+fn event(event: &FlowEvent) -> Value {
+    match event {
+        FlowEvent::Finish(forced) => json!({ "kind": "finish", "forced": forced }),
+        FlowEvent::Relayout(scope) => {
+            json!({ "kind": "relayout", "scope": scope_name(*scope) })
+        }
+        FlowEvent::Error(count) => json!({ "kind": "error", "count": count }),
+        FlowEvent::Region(size) => json!({
+            "kind": "region",
+            "width": size.x.to_pt(),
+            "height": size.y.to_pt(),
+        }),
+    }
+}
+
+/// Deserialize a single event.
+fn parse_event(value: &Value) -> Option<FlowEvent> {
+    Some(match value.get("kind")?.as_str()? {
+        "finish" => FlowEvent::Finish(value.get("forced")?.as_bool()?),
+        "relayout" => FlowEvent::Relayout(match value.get("scope")?.as_str()? {
+            "column" => PlacementScope::Column,
+            "parent" => PlacementScope::Parent,
+            _ => return None,
+        }),
+        "error" => FlowEvent::Error(value.get("count")?.as_u64()? as usize),
+        "region" => FlowEvent::Region(Size::new(
+            Abs::pt(value.get("width")?.as_f64()?),
+            Abs::pt(value.get("height")?.as_f64()?),
+        )),
+        _ => return None,
+    })
+}
+
+/// Serialize a single child.
+fn child(child: &Child) -> Value {
+    match child {
+        Child::Tag(tag) => match tag {
+            Tag::Start(elem, _) => json!({ "kind": "tag", "start": elem.func().name() }),
+            Tag::End(..) => json!({ "kind": "tag", "end": true }),
+        },
+        Child::Rel(amount, weakness) => json!({
+            "kind": "rel",
+            "abs": amount.abs.to_pt(),
+            "rel": amount.rel.get(),
+            "weakness": weakness,
+        }),
+        Child::Fr(fr, weakness) => json!({
+            "kind": "fr",
+            "fr": fr.get(),
+            "weakness": weakness,
+        }),
+        Child::Line(line) => json!({
+            "kind": "line",
+            "height": line.frame.height().to_pt(),
+            "need": line.need.to_pt(),
+        }),
+        Child::Single(single) => json!({
+            "kind": "single",
+            "sticky": single.sticky,
+            "fr": single.fr.map(|fr| fr.get()),
+        }),
+        Child::Multi(multi) => json!({ "kind": "multi", "sticky": multi.sticky }),
+        Child::Placed(placed) => json!({
+            "kind": "placed",
+            "float": placed.float,
+            "scope": scope_name(placed.scope),
+        }),
+        Child::Flush => json!({ "kind": "flush" }),
+        Child::Break(weak) => json!({ "kind": "break", "weak": weak }),
+        Child::Balance => json!({ "kind": "balance" }),
+        Child::Keep(start) => json!({ "kind": "keep", "start": start }),
+    }
+}
+
+/// The name of a placement scope, as in Typst code.
+fn scope_name(scope: PlacementScope) -> &'static str {
+    match scope {
+        PlacementScope::Column => "column",
+        PlacementScope::Parent => "parent",
+    }
+}
@@ -1,17 +1,24 @@
-use typst_library::introspection::Tag;
+use comemo::Track;
+use ecow::EcoVec;
+use typst_library::diag::{At, warning};
+use typst_library::foundations::{Context, dict};
+use typst_library::introspection::{Location, Tag};
 use typst_library::layout::{
-    Abs, Axes, FixedAlignment, Fr, Frame, FrameItem, Point, Region, Regions, Rel, Size,
+    Abs, Axes, Dir, FixedAlignment, Fr, Frame, FrameItem, Point, Region, Regions, Rel,
+    Size,
 };
+use typst_syntax::Span;
 use typst_utils::Numeric;
 
 use super::{
-    Child, Composer, FlowResult, LineChild, MultiChild, MultiSpill, PlacedChild,
-    SingleChild, Stop, Work,
+    Child, Composer, FlowMode, FlowResult, LineChild, MultiChild, MultiSpill,
+    PlacedChild, SingleChild, Stop, Work,
 };
 
 /// Distributes as many children as fit from `composer.work` into the first
 /// region and returns the resulting frame and the height actually used
 /// by the inner contents (for column balancing).
+#[typst_macros::time]
 pub fn distribute(
     composer: &mut Composer,
     regions: Regions,
@@ -22,14 +29,21 @@ pub fn distribute(
         regions,
         items: vec![],
         used: Size::zero(),
+        lines: 0,
         target: balancing_target,
         sticky: None,
         stickable: None,
+        keep: None,
+        keep_depth: 0,
+        breaks: vec![],
     };
     let init = distributor.snapshot();
     let forced = match distributor.run() {
         Ok(()) => distributor.composer.work.done(),
-        Err(Stop::Finish(forced)) => forced,
+        Err(stop @ Stop::Finish(forced)) => {
+            distributor.composer.config.trace(&stop);
+            forced
+        }
         Err(err) => return Err(err),
     };
     let region = Region::new(regions.size, regions.expand);
@@ -48,6 +62,8 @@ struct Distributor<'a, 'b, 'x, 'y, 'z> {
     items: Vec<Item<'a, 'b>>,
     /// Size used by laid out items.
     used: Size,
+    /// The number of paragraph lines laid out into the region.
+    lines: usize,
     /// The target height for column balancing.
     target: Option<Abs>,
     /// A snapshot which can be restored to migrate a suffix of sticky blocks to
@@ -73,6 +89,16 @@ struct Distributor<'a, 'b, 'x, 'y, 'z> {
     /// blocks are supposed to always be in the same page as the subsequent
     /// frame, but that is impossible in that case, which is thus pathological.
     stickable: Option<bool>,
+    /// A snapshot at the start of the current group of children that are kept
+    /// together, which is restored to migrate the group to the next region if
+    /// the region ends within it. This is `None` if the group started at the
+    /// top of the region, as migrating it then can't make it fit.
+    keep: Option<DistributionSnapshot<'a, 'b>>,
+    /// How many groups of children that are kept together are currently open.
+    keep_depth: usize,
+    /// Snapshots at the preceding points where the custom break predicate
+    /// accepts a break. Only recorded if there is such a predicate.
+    breaks: Vec<DistributionSnapshot<'a, 'b>>,
 }
 
 /// A snapshot of the distribution state.
@@ -80,6 +106,7 @@ struct DistributionSnapshot<'a, 'b> {
     work: Work<'a, 'b>,
     items: usize,
     used: Size,
+    lines: usize,
 }
 
 /// A laid out item in a distribution.
@@ -117,7 +144,17 @@ impl Item<'_, '_> {
 impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
     /// Distributes content into the region.
     fn run(&mut self) -> FlowResult<()> {
-        // First, handle spill of a breakable block.
+        // Restore weak spacing that was cut off at the end of the previous
+        // region if leading spacing isn't trimmed. Below a spanning float, the
+        // column starts flush against the float's clearance instead.
+        if let Some((amount, weakness)) = self.composer.work.leading.take()
+            && !self.composer.below_spanning_float()
+        {
+            self.use_height(amount);
+            self.items.push(Item::Abs(amount, weakness));
+        }
+
+        // Then, handle spill of a breakable block.
         if let Some(spill) = self.composer.work.spill.take() {
             self.multi_spill(spill)?;
         }
@@ -125,10 +162,65 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         // If spill are taken care of, process children until no space is left
         // or no children are left.
         while let Some(child) = self.composer.work.head() {
+            // To stay in sync with another flow, a region must start with
+            // this child.
+            if self.composer.work.forced_break()
+                && self.items.iter().any(|item| matches!(item, Item::Frame(..)))
+            {
+                return Err(Stop::Finish(true));
+            }
+
+            if self.composer.config.can_break.is_some()
+                && matches!(child, Child::Line(_) | Child::Single(_) | Child::Multi(_))
+                && self.items.iter().any(|item| matches!(item, Item::Frame(..)))
+                && self.break_allowed()?
+            {
+                self.breaks.push(self.snapshot());
+            }
             self.child(child)?;
             self.composer.work.advance();
         }
 
+        // Floats that are still held back and whose anchor isn't in this
+        // region wait for an element that isn't part of this flow. We queue
+        // them so that they aren't lost.
+        if !self.composer.work.held.is_empty() {
+            let (anchored, lost): (EcoVec<_>, Vec<_>) =
+                self.composer.work.held.iter().copied().partition(|placed| {
+                    placed.same_page_as.is_some_and(|loc| self.anchored(loc))
+                });
+            self.composer.work.held = anchored;
+            self.composer.work.floats.extend(lost);
+        }
+
+        // If all children are processed, but there are still queued floats,
+        // give them another chance to end up in this region instead of
+        // spawning a new one just for them.
+        if !self.composer.work.floats.is_empty() {
+            self.trailing_floats()?;
+        }
+
+        Ok(())
+    }
+
+    /// Retries the placement of floats that are still queued once all
+    /// children were processed.
+    ///
+    /// The floats are retried in order, so a float that still doesn't fit
+    /// keeps all following ones queued as well. If one of them fits, the
+    /// composer requests a relayout, after which it is skipped.
+    fn trailing_floats(&mut self) -> FlowResult<()> {
+        // As for in-flow floats, trailing weak spacing would collapse at the
+        // end of the region, so it's available to the floats.
+        let weak_spacing = self.weak_spacing();
+        self.use_height(-weak_spacing);
+        let clearance = self.items.iter().any(|item| matches!(item, Item::Frame(..)));
+        for placed in std::mem::take(&mut self.composer.work.floats) {
+            // There is no origin frame that could migrate, so footnote
+            // migration is forbidden here.
+            self.composer.float(placed, &self.regions, clearance, false)?;
+        }
+        self.use_height(weak_spacing);
         Ok(())
     }
 
@@ -150,6 +242,8 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             Child::Placed(placed) => self.placed(placed)?,
             Child::Flush => self.flush()?,
             Child::Break(weak) => self.break_(*weak)?,
+            Child::Balance => self.balance()?,
+            Child::Keep(start) => self.keep(*start),
         }
         Ok(())
     }
@@ -224,7 +318,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
                 Item::Frame(..) | Item::Fr(.., Some(_)) => return true,
             }
         }
-        false
+        self.keep_leading_spacing()
     }
 
     /// Decides whether to keep weak fractional spacing based on previous items.
@@ -254,17 +348,27 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
                 Item::Frame(..) | Item::Fr(.., Some(_)) => return true,
             }
         }
-        false
+        self.keep_leading_spacing()
+    }
+
+    /// Whether to keep weak spacing at the start of the region, which is only
+    /// the case for continuation regions if trimming is disabled and the
+    /// region doesn't start below a spanning float.
+    fn keep_leading_spacing(&self) -> bool {
+        !self.composer.config.trim_leading
+            && self.composer.continuation()
+            && !self.composer.below_spanning_float()
     }
 
-    /// Trims trailing weak spacing from the items.
-    fn trim_spacing(&mut self) {
+    /// Trims trailing weak spacing from the items. Returns the trimmed
+    /// relative spacing and its weakness, if any.
+    fn trim_spacing(&mut self) -> Option<(Abs, u8)> {
         for (i, item) in self.items.iter().enumerate().rev() {
             match *item {
-                Item::Abs(amount, 1..) => {
+                Item::Abs(amount, weakness @ 1..) => {
                     self.use_height(-amount);
                     self.items.remove(i);
-                    break;
+                    return Some((amount, weakness));
                 }
                 Item::Fr(_, 1.., None) => {
                     self.items.remove(i);
@@ -274,6 +378,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
                 Item::Frame(..) | Item::Fr(..) => break,
             }
         }
+        None
     }
 
     /// The amount of trailing weak spacing.
@@ -301,9 +406,24 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
 
     /// Processes a line of a paragraph.
     fn line(&mut self, line: &'b LineChild) -> FlowResult<()> {
+        // If the region already holds as many lines as allowed, finish it.
+        if let Some(max) = self.composer.config.columns.lines_per_column
+            && self.lines >= max.get()
+            && self.regions.may_progress()
+        {
+            return Err(Stop::Finish(false));
+        }
+
+        // The spacing needed to move the line to the region's first baseline
+        // or onto the baseline grid.
+        let snap = match self.first_baseline(line) {
+            Some(offset) => offset,
+            None => self.snap(line),
+        };
+
         // If the line doesn't fit and a followup region may improve things,
         // finish the region.
-        if !self.fits(line.frame.height()) && self.regions.may_progress() {
+        if !self.fits(snap + line.frame.height()) && self.regions.may_progress() {
             return Err(Stop::Finish(false));
         }
 
@@ -311,7 +431,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         // following lines grouped by widow/orphan prevention, does not fit into
         // the current region, but does fit into the next region, finish the
         // region.
-        if !self.fits(line.need)
+        if !self.fits(snap + line.need)
             && self
                 .regions
                 .iter()
@@ -321,7 +441,94 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             return Err(Stop::Finish(false));
         }
 
-        self.frame(line.frame.clone(), line.align, false, false)
+        // If the region doesn't hold enough lines to precede footnotes yet,
+        // the entries referenced in this line must leave room for the missing
+        // ones. Only lines that follow before the next forced break can make
+        // up for them. We assume them to be as tall as this line and to be
+        // separated by its leading.
+        let missing = self
+            .composer
+            .config
+            .footnote
+            .lines_above
+            .saturating_sub(self.lines + 1);
+        let following = self
+            .composer
+            .work
+            .children
+            .iter()
+            .skip(1)
+            .take_while(|child| !matches!(child, Child::Break(_)))
+            .filter(|child| matches!(child, Child::Line(_)))
+            .take(missing)
+            .count();
+        let lines_need = (line.frame.height() + line.leading) * following as f64;
+
+        if snap.is_zero() {
+            self.frame(line.frame.clone(), line.align, false, false, lines_need)?;
+            self.lines += 1;
+            return Ok(());
+        }
+
+        self.use_height(snap);
+        self.items.push(Item::Abs(snap, 0));
+
+        let result = self.frame(line.frame.clone(), line.align, false, false, lines_need);
+        if result.is_err() {
+            // The line didn't end up in this region, so the spacing that
+            // snapped it onto the grid mustn't either.
+            self.items.pop();
+            self.use_height(-snap);
+        } else {
+            self.lines += 1;
+        }
+        result
+    }
+
+    /// Determines the amount of spacing that is needed in front of the first
+    /// line of the region to move its baseline down to the configured first
+    /// baseline.
+    ///
+    /// Weak spacing in front of the line is absorbed into that amount, such
+    /// that the line ends up at the same position regardless of whether
+    /// leading spacing was trimmed. Returns `None` if no first baseline is
+    /// configured or if the region already holds in-flow content.
+    fn first_baseline(&mut self, line: &LineChild) -> Option<Abs> {
+        let first = self.composer.config.first_baseline?;
+        if self
+            .items
+            .iter()
+            .any(|item| matches!(item, Item::Frame(..) | Item::Fr(..)))
+        {
+            return None;
+        }
+
+        let weak = self.weak_spacing();
+        let offset = first - (self.used.y - weak + line.frame.baseline());
+        if offset <= Abs::zero() || offset.approx_empty() {
+            return Some(Abs::zero());
+        }
+
+        self.trim_spacing();
+        Some(offset)
+    }
+
+    /// Determines the amount of spacing that is needed in front of a line to
+    /// move its baseline down onto the next line of the baseline grid.
+    ///
+    /// This is always zero if baselines aren't synchronized across columns.
+    fn snap(&self, line: &LineChild) -> Abs {
+        let Some(grid) = self.composer.config.columns.baseline_grid else {
+            return Abs::zero();
+        };
+
+        // The grid is relative to the top of the column. We subtract a small
+        // epsilon before rounding up so that a baseline that is already on the
+        // grid (modulo floating point errors) stays where it is.
+        let y = self.used.y + line.frame.baseline();
+        let steps = ((y - grid.origin) / grid.pitch - 1e-6).ceil().max(0.0);
+        let snap = grid.origin + grid.pitch * steps - y;
+        if snap.approx_empty() { Abs::zero() } else { snap.max(Abs::zero()) }
     }
 
     /// Processes an unbreakable block.
@@ -331,6 +538,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             self.composer.engine,
             Region::new(self.regions.base(), self.regions.expand),
         )?;
+        self.check_width(&frame, single.span());
 
         // Handle fractionally sized blocks.
         if let Some(fr) = single.fr {
@@ -347,7 +555,46 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             return Err(Stop::Finish(false));
         }
 
-        self.frame(frame, single.align, single.sticky, false)
+        self.check_height(&frame, single.span());
+        self.frame(frame, single.align, single.sticky, false, Abs::zero())
+    }
+
+    /// Warns if an unbreakable block doesn't fit into the page even though
+    /// the region can't get any larger. Such a block overflows the page
+    /// instead of moving on.
+    fn check_height(&mut self, frame: &Frame, span: Span) {
+        let available = self.regions.size.y;
+        if self.composer.config.mode != FlowMode::Root
+            || self.regions.may_progress()
+            || available.fits(frame.height())
+        {
+            return;
+        }
+
+        self.composer.engine.sink.warn(warning!(
+            span,
+            "block does not fit into any page";
+            hint: "it needs {:.1}pt of height, but only {:.1}pt are available",
+                frame.height().to_pt(), available.to_pt();
+            hint: "try reducing its height or making it breakable with `block(breakable: true)`";
+        ));
+    }
+
+    /// Warns if a block in a multi-column flow is wider than its column. Such
+    /// a block can never fit and overflows into the gutter or the neighbouring
+    /// column.
+    fn check_width(&mut self, frame: &Frame, span: Span) {
+        let overflow = frame.width() - self.regions.size.x;
+        if self.composer.config.columns.count > 1
+            && overflow > Abs::zero()
+            && !overflow.approx_empty()
+        {
+            self.composer.engine.sink.warn(warning!(
+                span,
+                "block is wider than its column";
+                hint: "try using fewer or wider columns, or reduce the block's width";
+            ));
+        }
     }
 
     /// Processes a breakable block.
@@ -368,6 +615,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
 
         // Lay out the block.
         let (frame, spill) = multi.layout(self.composer.engine, pod)?;
+        self.check_width(&frame, multi.span());
         if frame.is_empty()
             && spill.as_ref().is_some_and(|s| s.exist_non_empty_frame)
             && self.regions.may_progress()
@@ -378,7 +626,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             return Err(Stop::Finish(false));
         }
 
-        self.frame(frame, multi.align, multi.sticky, true)?;
+        self.frame(frame, multi.align, multi.sticky, true, Abs::zero())?;
 
         // If the block didn't fully fit into the current region, save it into
         // the `spill` and finish the region.
@@ -410,7 +658,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         // Lay out the spilled remains.
         let align = spill.align();
         let (frame, spill) = spill.layout(self.composer.engine, pod)?;
-        self.frame(frame, align, false, true)?;
+        self.frame(frame, align, false, true, Abs::zero())?;
 
         // If there's still more, save it into the `spill` and finish the
         // region.
@@ -423,12 +671,16 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
     }
 
     /// Processes an in-flow frame, generated from a line or block.
+    ///
+    /// The `lines_need` is the space that footnote entries referenced in the
+    /// frame must leave for lines following it.
     fn frame(
         &mut self,
         frame: Frame,
         align: Axes<FixedAlignment>,
         sticky: bool,
         breakable: bool,
+        lines_need: Abs,
     ) -> FlowResult<()> {
         // If the frame is sticky and we haven't remembered a preceding sticky
         // element, make a checkpoint which we can restore should we end on
@@ -447,6 +699,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         // blocks at the top of a block - but not necessarily of the page - can
         // still be migrated.
         if sticky
+            && self.composer.config.sticky
             && self.sticky.is_none()
             && *self.stickable.get_or_insert_with(|| self.regions.may_progress())
         {
@@ -463,7 +716,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         self.composer.footnotes(
             &self.regions,
             &frame,
-            frame.height(),
+            frame.height() + lines_need,
             breakable,
             true,
         )?;
@@ -484,24 +737,84 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         Ok(())
     }
 
+    /// Places held back floats whose anchor element ended up in the region.
+    ///
+    /// This only runs once it is settled which content stays in the region,
+    /// i.e. after sticky and kept content has possibly been migrated.
+    /// Otherwise, a float could be placed for an anchor that moves on.
+    fn release(&mut self) -> FlowResult<()> {
+        if self.composer.work.held.is_empty() {
+            return Ok(());
+        }
+
+        let (released, kept): (Vec<_>, EcoVec<_>) =
+            self.composer.work.held.iter().copied().partition(|placed| {
+                placed.same_page_as.is_some_and(|loc| self.anchored(loc))
+            });
+        self.composer.work.held = kept;
+
+        for placed in released {
+            self.placed_float(placed)?;
+
+            // If the float wasn't placed, it was queued for the next region.
+            if !self.composer.skipped(placed.location()) {
+                self.composer.engine.sink.warn(warning!(
+                    placed.span(),
+                    "float could not be placed on the same page as its anchor";
+                    hint: "it was moved to the next region instead";
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the element with the given location starts in the region's
+    /// items.
+    fn anchored(&self, loc: Location) -> bool {
+        self.items.iter().any(|item| match item {
+            Item::Tag(Tag::Start(elem, _)) => elem.location() == Some(loc),
+            Item::Frame(frame, _) | Item::Placed(frame, _) => contains_start(frame, loc),
+            _ => false,
+        })
+    }
+
     /// Processes an absolutely or floatingly placed child.
     fn placed(&mut self, placed: &'b PlacedChild<'a>) -> FlowResult<()> {
         if placed.float {
-            // If the element is floatingly placed, let the composer handle it.
-            // It might require relayout because the area available for
-            // distribution shrinks. We make the spacing occupied by weak
-            // spacing temporarily available again because it can collapse if it
-            // ends up at a break due to the float.
-            let weak_spacing = self.weak_spacing();
-            self.use_height(-weak_spacing);
-            self.composer.float(
-                placed,
-                &self.regions,
-                self.items.iter().any(|item| matches!(item, Item::Frame(..))),
-                true,
-            )?;
-            self.use_height(weak_spacing);
+            // If the float should share a page with a later element, hold it
+            // back until that element is laid out.
+            if placed.same_page_as.is_some() && !self.composer.skipped(placed.location())
+            {
+                self.composer.work.held.push(placed);
+                return Ok(());
+            }
+
+            // A float for the next region isn't even tried here, unless there
+            // is no next region.
+            if placed.next
+                && !self.composer.skipped(placed.location())
+                && self.regions.may_progress()
+            {
+                self.composer.work.deferred.push(placed);
+                return Ok(());
+            }
+
+            // An eager float that was already placed in a previous region
+            // ended up before its reference point.
+            if placed.eager && self.composer.work.skips.contains(&placed.location()) {
+                self.composer.engine.sink.warn(warning!(
+                    placed.span(),
+                    "eager float was placed before its reference point";
+                    hint: "it was moved to an earlier region that had room for it";
+                ));
+            }
+
+            self.placed_float(placed)?;
         } else {
+            // An absolutely placed frame doesn't take up space in the flow,
+            // but footnotes within it must still be extracted into this
+            // region, just like those in in-flow frames.
             let frame = placed.layout(self.composer.engine, self.regions.base())?;
             self.composer
                 .footnotes(&self.regions, &frame, Abs::zero(), true, true)?;
@@ -511,11 +824,31 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         Ok(())
     }
 
+    /// Processes a floating placed element.
+    fn placed_float(&mut self, placed: &'b PlacedChild<'a>) -> FlowResult<()> {
+        // Let the composer handle the float. It might require relayout because
+        // the area available for distribution shrinks. We make the spacing
+        // occupied by weak spacing temporarily available again because it can
+        // collapse if it ends up at a break due to the float.
+        let weak_spacing = self.weak_spacing();
+        self.use_height(-weak_spacing);
+        self.composer.float(
+            placed,
+            &self.regions,
+            self.items.iter().any(|item| matches!(item, Item::Frame(..))),
+            true,
+        )?;
+        self.use_height(weak_spacing);
+        Ok(())
+    }
+
     /// Processes a float flush.
     fn flush(&mut self) -> FlowResult<()> {
-        // If there are still pending floats, finish the region instead of
-        // adding more content to it.
-        if !self.composer.work.floats.is_empty() {
+        // If there are still pending floats, including those deferred to the
+        // next region, finish the region instead of adding more content to it.
+        if !self.composer.work.floats.is_empty()
+            || !self.composer.work.deferred.is_empty()
+        {
             return Err(Stop::Finish(false));
         }
         Ok(())
@@ -524,7 +857,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
     /// Processes a column break.
     fn break_(&mut self, weak: bool) -> FlowResult<()> {
         // If there is a region to break into, break into it.
-        if (!weak || !self.items.is_empty())
+        if (!weak || !self.column_empty())
             && (!self.regions.backlog.is_empty() || self.regions.last.is_some())
         {
             self.composer.work.advance();
@@ -533,6 +866,52 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         Ok(())
     }
 
+    /// Processes a balance point. If it determines the height of balanced
+    /// columns, the column ends here.
+    fn balance(&mut self) -> FlowResult<()> {
+        if self.composer.balance_point(self.used.y) && self.regions.may_progress() {
+            self.composer.work.advance();
+            return Err(Stop::Finish(true));
+        }
+        Ok(())
+    }
+
+    /// Processes the start or end of a group of children that are kept
+    /// together. Only the outermost group of nested ones is relevant.
+    fn keep(&mut self, start: bool) {
+        if !start {
+            self.keep_depth = self.keep_depth.saturating_sub(1);
+            if self.keep_depth == 0 {
+                self.keep = None;
+            }
+            return;
+        }
+
+        if self.keep_depth == 0
+            && self.regions.may_progress()
+            && self.items.iter().any(|item| matches!(item, Item::Frame(..)))
+        {
+            // Sticky blocks right before the group migrate with it.
+            self.keep = Some(match self.sticky.take() {
+                Some(snapshot) => snapshot,
+                None => self.snapshot(),
+            });
+        }
+        self.keep_depth += 1;
+    }
+
+    /// Whether the current column is still empty.
+    ///
+    /// A nested flow (e.g. in a breakable block or grid cell) shares its
+    /// column with the content of the enclosing flow that precedes it. If its
+    /// region doesn't span the full column height, that content exists and the
+    /// column isn't empty even if the nested flow hasn't placed anything yet.
+    fn column_empty(&self) -> bool {
+        self.items.is_empty()
+            && (self.composer.config.mode == FlowMode::Root
+                || self.regions.size.y.approx_eq(self.regions.full))
+    }
+
     /// Arranges the produced items into an output frame.
     ///
     /// This performs alignment and resolves fractional spacing and blocks.
@@ -549,15 +928,45 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
             // Restore the initial state of all items are migratable.
             self.restore(init);
         } else {
-            // If we ended on a sticky block, but are not yet at the end of
-            // the flow, restore the saved checkpoint to move the sticky
-            // suffix to the next region.
-            if let Some(snapshot) = self.sticky.take() {
+            // If we ended within a group of children that are kept together,
+            // move the whole group to the next region. Otherwise, if we ended
+            // on a sticky block, but are not yet at the end of the flow,
+            // restore the saved checkpoint to move the sticky suffix to the
+            // next region.
+            if let Some(snapshot) = self.keep.take() {
+                self.restore(snapshot);
+            } else if let Some(snapshot) = self.sticky.take() {
                 self.restore(snapshot);
             }
+
+            // If a custom predicate forbids the break, move it up.
+            self.veto_break()?;
         }
 
-        self.trim_spacing();
+        // Now that the region's content is settled, place held back floats
+        // anchored in it.
+        self.release()?;
+
+        // Trailing weak spacing is removed. If leading spacing isn't trimmed,
+        // it instead moves to the start of the next region.
+        let trimmed = self.trim_spacing();
+        if !forced && !self.composer.config.trim_leading {
+            self.composer.work.leading = trimmed;
+        }
+
+        // Spacing that remains at the end, e.g. from `v`, is whitespace that
+        // footnotes may collapse their clearance with.
+        let trailing: Abs = self
+            .items
+            .iter()
+            .rev()
+            .take_while(|item| !matches!(item, Item::Frame(..) | Item::Fr(..)))
+            .map(|item| match item {
+                Item::Abs(amount, _) => *amount,
+                _ => Abs::zero(),
+            })
+            .sum();
+        self.composer.trailing_spacing(trailing);
 
         let used_height_without_fr = self.used.y;
 
@@ -600,6 +1009,12 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         let size = region.expand.select(region.size, self.used.min(region.size));
         let free = size.y - self.used.y;
 
+        // Content progresses downwards from the top of the region, unless the
+        // columns progress bottom-up, in which case the vertical positions are
+        // mirrored.
+        let btt = self.composer.config.columns.dir.y == Dir::BTT;
+        let progress = |y: Abs, height: Abs| if btt { size.y - y - height } else { y };
+
         let mut output = Frame::soft(size);
         let mut ruler = FixedAlignment::Start;
         let mut offset = Abs::zero();
@@ -610,7 +1025,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         for item in self.items {
             match item {
                 Item::Tag(tag) => {
-                    let y = offset + ruler.position(free);
+                    let y = progress(offset + ruler.position(free), Abs::zero());
                     let pos = Point::with_y(y);
                     output.push(pos, FrameItem::Tag(tag.clone()));
                 }
@@ -622,7 +1037,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
                     if let Some(single) = single {
                         let frame = fr_frames.next().unwrap();
                         let x = single.align.x.position(size.x - frame.width());
-                        let pos = Point::new(x, offset);
+                        let pos = Point::new(x, progress(offset, frame.height()));
                         output.push_frame(pos, frame);
                     }
                     offset += length;
@@ -631,7 +1046,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
                     ruler = ruler.max(align.y);
 
                     let x = align.x.position(size.x - frame.width());
-                    let y = offset + ruler.position(free);
+                    let y = progress(offset + ruler.position(free), frame.height());
                     let pos = Point::new(x, y);
                     offset += frame.height();
 
@@ -653,7 +1068,7 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
                     let x = placed.align_x.position(size.x - frame.width());
                     let y = match placed.align_y.unwrap_or_default() {
                         Some(align) => align.position(size.y - frame.height()),
-                        _ => offset + ruler.position(free),
+                        _ => progress(offset + ruler.position(free), frame.height()),
                     };
 
                     let pos = Point::new(x, y)
@@ -667,12 +1082,74 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         Ok((output, used_height_without_fr))
     }
 
+    /// Moves the break point up to the closest preceding point that is
+    /// accepted by the custom break predicate.
+    ///
+    /// If no point in the region is accepted, the break stays where it is, as
+    /// we would otherwise end up with an empty region.
+    fn veto_break(&mut self) -> FlowResult<()> {
+        if self.composer.config.can_break.is_none() || self.break_allowed()? {
+            return Ok(());
+        }
+
+        // Points that were moved to the next region by a restored checkpoint
+        // aren't in this region anymore.
+        while let Some(snapshot) = self.breaks.pop() {
+            if snapshot.items < self.items.len() {
+                self.restore(snapshot);
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the custom break predicate accepts a break at the current
+    /// point of the distribution.
+    fn break_allowed(&mut self) -> FlowResult<bool> {
+        let config = self.composer.config;
+        let Some(func) = &config.can_break else { return Ok(true) };
+        let work = &*self.composer.work;
+
+        // Breaks within a breakable block aren't subject to the predicate.
+        if work.spill.is_some() {
+            return Ok(true);
+        }
+
+        let start = |tag: &Tag| match tag {
+            Tag::Start(elem, _) => Some(elem.clone()),
+            Tag::End(..) => None,
+        };
+
+        let before = self.items.iter().rev().find_map(|item| match *item {
+            Item::Tag(tag) => start(tag),
+            _ => None,
+        });
+
+        let after = work
+            .tags
+            .iter()
+            .copied()
+            .chain(work.children.iter().map_while(|child| match child {
+                Child::Tag(tag) => Some(*tag),
+                _ => None,
+            }))
+            .find_map(&start);
+
+        let point = dict! { "before" => before, "after" => after };
+        Ok(func
+            .call(self.composer.engine, Context::none().track(), [point])?
+            .cast()
+            .at(func.span())?)
+    }
+
     /// Create a snapshot of the work and items.
     fn snapshot(&self) -> DistributionSnapshot<'a, 'b> {
         DistributionSnapshot {
             work: self.composer.work.clone(),
             items: self.items.len(),
             used: self.used,
+            lines: self.lines,
         }
     }
 
@@ -681,5 +1158,15 @@ impl<'a, 'b> Distributor<'a, 'b, '_, '_, '_> {
         *self.composer.work = snapshot.work;
         self.items.truncate(snapshot.items);
         self.used = snapshot.used;
+        self.lines = snapshot.lines;
     }
 }
+
+/// Whether the frame contains the start of the element with the given location.
+fn contains_start(frame: &Frame, loc: Location) -> bool {
+    frame.items().any(|(_, item)| match item {
+        FrameItem::Group(group) => contains_start(&group.frame, loc),
+        FrameItem::Tag(Tag::Start(elem, _)) => elem.location() == Some(loc),
+        _ => false,
+    })
+}
@@ -3,39 +3,54 @@
 mod block;
 mod collect;
 mod compose;
+#[cfg(feature = "debug-flow")]
+mod debug;
 mod distribute;
 
 pub(crate) use self::block::unbreakable_pod;
+#[cfg(feature = "debug-flow")]
+pub use self::debug::{FlowEvent, events as flow_events};
 
 use std::num::NonZeroUsize;
+use std::ptr;
 use std::rc::Rc;
 
 use bumpalo::Bump;
 use comemo::{Track, Tracked, TrackedMut};
 use ecow::EcoVec;
-use rustc_hash::FxHashSet;
-use typst_library::diag::{At, SourceDiagnostic, SourceResult, bail};
+use rustc_hash::{FxHashMap, FxHashSet};
+use typst_library::diag::{At, SourceDiagnostic, SourceResult, bail, warning};
 use typst_library::engine::{Engine, Route, Sink, Traced};
-use typst_library::foundations::{Content, Packed, Resolve, StyleChain};
+use typst_library::foundations::{
+    Content, Func, NativeElement, Packed, Resolve, StyleChain,
+};
 use typst_library::introspection::{
-    Introspector, Location, Locator, LocatorLink, SplitLocator, Tag,
+    Introspector, Location, Locator, LocatorLink, SplitLocator, Tag, TagFlags,
 };
 use typst_library::layout::{
-    Abs, ColumnsElem, Dir, Em, Fragment, Frame, PageElem, PlacementScope, Region,
-    Regions, Rel, Size,
+    Abs, Axes, BottomOrder, Celled, ColumnGutter, ColumnOverflow, ColumnsElem, Dir, Em,
+    FixedAlignment, Fragment, Frame, FrameItem, GutterFillExtent, PageElem,
+    PageOptimization, PageRegion, PlacementScope, Point, Ratio, Region, Regions, Rel,
+    Sides, Size, Transform,
+};
+use typst_library::model::{
+    DocumentElem, FootnoteContinuation, FootnoteElem, FootnoteEntry, LineNumberingScope,
+    ParElem, ParLine,
 };
-use typst_library::model::{FootnoteElem, FootnoteEntry, LineNumberingScope, ParLine};
 use typst_library::pdf::ArtifactKind;
 use typst_library::routines::{Arenas, FragmentKind, Pair, RealizationKind};
 use typst_library::text::TextElem;
+use typst_library::visualize::{Curve, Paint};
 use typst_library::{Library, World};
+use typst_syntax::Span;
 use typst_utils::{LazyHash, NonZeroExt, Numeric, Protected};
 
 use self::block::{layout_multi_block, layout_single_block};
 use self::collect::{
-    Child, LineChild, MultiChild, MultiSpill, PlacedChild, SingleChild, collect,
+    Child, DetachedSpill, LineChild, MultiChild, MultiSpill, PlacedChild, SingleChild,
+    collect,
 };
-use self::compose::{Composer, compose};
+use self::compose::{Composer, compose, validate_footnotes};
 use self::distribute::distribute;
 
 /// Lays out content into a single region, producing a single frame.
@@ -50,6 +65,185 @@ pub fn layout_frame(
         .map(Fragment::into_frame)
 }
 
+/// Lays out content into an explicit list of regions.
+///
+/// In contrast to [`Regions`], which consist of a first region, a backlog, and
+/// a repeating last region, each region's size is given individually here. This
+/// is useful for approximating irregular shapes with a sequence of rectangles.
+/// The content fills the regions one after another. Content that doesn't fit
+/// into all of them continues in further regions with the size of the last
+/// one. Since the flow's lines are broken only once, all regions must have the
+/// same width.
+///
+/// If `expand.y` is `true`, every region is filled up to its full height and a
+/// frame is produced for each region, even if the content ends earlier.
+/// Otherwise, each frame is only as tall as its content and layout stops once
+/// the content is exhausted.
+pub fn layout_flow_into(
+    engine: &mut Engine,
+    content: &Content,
+    locator: Locator,
+    styles: StyleChain,
+    sizes: &[Size],
+    expand: Axes<bool>,
+) -> SourceResult<Fragment> {
+    let Some((first, rest)) = sizes.split_first() else {
+        bail!(content.span(), "at least one region is required");
+    };
+    if rest.iter().any(|size| size.x != first.x) {
+        bail!(content.span(), "all regions of a flow must have the same width");
+    }
+
+    // The last region repeats for content that doesn't fit into the given
+    // ones.
+    let last = rest.last().unwrap_or(first);
+    let backlog: Vec<Abs> = rest.iter().map(|size| size.y).collect();
+    let regions = Regions {
+        size: *first,
+        full: first.y,
+        backlog: &backlog,
+        last: Some(last.y),
+        expand,
+    };
+
+    layout_fragment(engine, content, locator, styles, regions)
+}
+
+/// Lays out content through several rectangular sub-regions per page, for
+/// instance to approximate a non-rectangular shape.
+///
+/// Each page has the given `size` and contains the given rectangles, each
+/// described by the position of its top-left corner within the page and its
+/// size. The content fills the rectangles of a page in the given order before
+/// moving on to the next page, which has the same rectangles again. Like for
+/// [`layout_flow_into`], all rectangles must have the same width. One frame is
+/// produced per page. On the last page, only the rectangles that received
+/// content are filled.
+///
+/// Each rectangle is a region of its own, so a float is placed within the
+/// rectangle it appears in or one of the following ones. Footnotes are not laid
+/// out by this function. Their entries are left to an enclosing page flow, as
+/// for other nested flows.
+pub fn layout_flow_shaped(
+    engine: &mut Engine,
+    content: &Content,
+    locator: Locator,
+    styles: StyleChain,
+    size: Size,
+    rects: &[(Point, Size)],
+) -> SourceResult<Fragment> {
+    if rects.is_empty() {
+        bail!(content.span(), "at least one rectangle is required per page");
+    }
+
+    let expand = Axes::new(true, false);
+    let sizes = |pages: usize| -> Vec<Size> {
+        rects
+            .iter()
+            .map(|&(_, size)| size)
+            .cycle()
+            .take(rects.len() * pages)
+            .collect()
+    };
+
+    // Find out how many pages are needed. Content that doesn't fit into the
+    // given rectangles spills into further regions, so we double the number
+    // of pages until it doesn't.
+    let mut pages = 1;
+    loop {
+        let sizes = sizes(pages);
+        let fragment = layout_flow_into(
+            engine,
+            content,
+            locator.relayout(),
+            styles,
+            &sizes,
+            expand,
+        )?;
+        if fragment.len() <= sizes.len() {
+            break;
+        }
+        pages *= 2;
+    }
+
+    let frames =
+        layout_flow_into(engine, content, locator, styles, &sizes(pages), expand)?
+            .into_frames();
+
+    // Assemble the rectangles' frames into pages.
+    let mut output = vec![];
+    let mut frames = frames.into_iter().peekable();
+    while frames.peek().is_some() {
+        let mut page = Frame::hard(size);
+        for (&(pos, _), frame) in rects.iter().zip(frames.by_ref()) {
+            page.push_frame(pos, frame);
+        }
+        output.push(page);
+    }
+
+    Ok(Fragment::frames(output))
+}
+
+/// Lays out content into regions whose breaks are synchronized with those of
+/// another flow.
+///
+/// This is meant for content that is laid out side by side with a
+/// counterpart, like the two languages of a bilingual edition, where both
+/// sides must break into the next region at the same points.
+///
+/// The flows are matched through their _sync points:_ the top-level
+/// paragraphs and blocks of each flow, numbered from zero in order of
+/// appearance. The n-th sync point of one flow is assumed to correspond to
+/// the n-th sync point of the other. Nested content, spacing, and placed
+/// elements don't count.
+///
+/// Alongside the laid out fragment, this returns the flow's breaks: For each
+/// region boundary, the number of the first sync point that starts after it.
+/// A paragraph or block that is split by the boundary thus belongs to the
+/// region before it. Boundaries that aren't followed by a new sync point are
+/// left out.
+///
+/// The `breaks` of another flow are accepted as constraints: A region break
+/// is forced before each of the given sync points, unless the current region
+/// is still empty. Sync points that don't exist in this flow are ignored.
+/// Content that doesn't fit still breaks into further regions as usual, so the
+/// returned breaks can contain more entries than the given ones. Typically,
+/// the first flow is laid out without constraints and its breaks are then
+/// passed to the second one. If the second one needed additional breaks,
+/// both flows can be laid out again with the returned breaks.
+pub fn layout_flow_synced(
+    engine: &mut Engine,
+    content: &Content,
+    locator: Locator,
+    styles: StyleChain,
+    regions: Regions,
+    breaks: &[usize],
+) -> SourceResult<(Fragment, Vec<usize>)> {
+    layout_fragment_impl(
+        engine.world,
+        engine.library,
+        engine.introspector.into_raw(),
+        engine.traced,
+        TrackedMut::reborrow_mut(&mut engine.sink),
+        engine.route.track(),
+        content,
+        locator.track(),
+        styles,
+        regions,
+        ColumnOptions::single(),
+        Some(breaks),
+    )
+}
+
+/// The region breaks of a flow that is synchronized with another one. See
+/// [`layout_flow_synced`].
+struct SyncPoints<'s> {
+    /// The sync points before which a region must start.
+    breaks: &'s [usize],
+    /// The first sync point after each region boundary.
+    recorded: Vec<usize>,
+}
+
 /// Lays out content into multiple regions.
 ///
 /// When laying out into just one region, prefer [`layout_frame`].
@@ -71,12 +265,279 @@ pub fn layout_fragment(
         locator.track(),
         styles,
         regions,
-        ColumnOptions {
-            count: NonZeroUsize::ONE,
-            balanced: false,
-            gutter: Rel::zero(),
-        },
+        ColumnOptions::single(),
+        None,
     )
+    .map(|(fragment, _)| fragment)
+}
+
+/// Measures the intrinsic minimum and maximum width of content.
+///
+/// The content is collected like for flow layout, once at zero width and once
+/// at the given `base` width, and the widest of the collected lines and blocks
+/// determines each width. At zero width, lines are broken at every
+/// opportunity, so the minimum is the width of the widest unbreakable piece of
+/// content. At the base width, lines are only broken where explicitly
+/// requested or where they would exceed the base. Relative widths within the
+/// content resolve against the width of the collection, so they don't
+/// contribute to the minimum and are relative to the base for the maximum. The
+/// base must thus be finite. Typically, it is the width that is available to
+/// the content at most.
+///
+/// Both measurements are performed with a copy of the locator, so that the
+/// content can afterwards still be laid out for real, just like other
+/// measurements.
+pub fn measure_intrinsic(
+    engine: &mut Engine,
+    content: &Content,
+    locator: Locator,
+    styles: StyleChain,
+    base: Abs,
+) -> SourceResult<(Abs, Abs)> {
+    if !base.is_finite() {
+        bail!(content.span(), "cannot measure content against an infinite width");
+    }
+
+    engine.route.check_layout_depth().at(content.span())?;
+
+    let mut locator = locator.relayout().split();
+    let mut kind = FragmentKind::Block;
+    let arenas = Arenas::default();
+    let children = (engine.library.routines.realize)(
+        RealizationKind::Fragment { kind: &mut kind },
+        engine,
+        &mut locator,
+        &arenas,
+        content,
+        styles,
+    )?;
+
+    let locator = locator.next(&());
+    let mut measure = |width| -> SourceResult<Abs> {
+        let region = Region::new(Size::new(width, Abs::inf()), Axes::splat(false));
+        let bump = Bump::new();
+        let collected = collect(
+            engine,
+            &bump,
+            &children,
+            locator.relayout(),
+            region.size,
+            false,
+            kind.into(),
+            None,
+        )?;
+
+        let mut max = Abs::zero();
+        for child in &collected {
+            let width = match child {
+                Child::Line(line) => line.frame.width(),
+                Child::Single(single) => single.layout(engine, region)?.width(),
+                Child::Multi(multi) => multi.layout(engine, region.into())?.0.width(),
+                _ => continue,
+            };
+            max.set_max(width);
+        }
+        Ok(max)
+    };
+
+    let min = measure(Abs::zero())?;
+    let max = measure(base)?;
+    Ok((min, max.max(min)))
+}
+
+/// How content fits into a region, as determined by [`measure_flow`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FlowMeasurement {
+    /// The number of regions of the given size the content occupies.
+    pub regions: usize,
+    /// The height of the content that doesn't fit into the first region, i.e.
+    /// the summed height of the content in all further regions. Zero if the
+    /// content fits.
+    pub overflow: Abs,
+}
+
+impl FlowMeasurement {
+    /// Whether the content fits into the first region.
+    pub fn fits(&self) -> bool {
+        self.regions <= 1
+    }
+}
+
+/// Measures how content fits into a region.
+///
+/// The content is laid out with a copy of the locator into regions of the
+/// region's size. The regions don't expand vertically, such that the content
+/// of the further regions only counts with its own height.
+pub fn measure_flow(
+    engine: &mut Engine,
+    content: &Content,
+    locator: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<FlowMeasurement> {
+    let regions = Regions::repeat(region.size, Axes::new(region.expand.x, false));
+    let fragment = layout_fragment(engine, content, locator.relayout(), styles, regions)?;
+    Ok(FlowMeasurement {
+        regions: fragment.len(),
+        overflow: fragment.iter().skip(1).map(Frame::height).sum(),
+    })
+}
+
+/// The smallest factor by which [`fit_to_region`] shrinks content.
+const MIN_FIT_SCALE: f64 = 0.25;
+
+/// The number of bisection steps [`fit_to_region`] performs at most.
+const FIT_ITERATIONS: usize = 8;
+
+/// Lays out content into a single region, uniformly shrinking it such that it
+/// fits without breaking into further regions.
+///
+/// The content is laid out into a region enlarged by the inverse of a scale
+/// factor and the resulting frame is scaled down by that factor. The largest
+/// factor at which the content fits is searched with [`measure_flow`]: The
+/// overflow at full scale yields a first estimate, assuming that the area of
+/// the content stays the same as it reflows into the enlarged region. A
+/// bisection then refines it with a bounded number of further measurements.
+/// If the content doesn't fit even at the minimum scale, a warning is emitted
+/// and it is laid out at the minimum scale regardless.
+pub fn fit_to_region(
+    engine: &mut Engine,
+    content: &Content,
+    locator: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let size_at =
+        |scale: f64| region.size.map(|v| if v.is_finite() { v / scale } else { v });
+
+    let measure = |engine: &mut Engine, scale: f64| {
+        let pod = Region::new(size_at(scale), region.expand);
+        measure_flow(engine, content, locator.relayout(), styles, pod)
+    };
+
+    let full = measure(engine, 1.0)?;
+    if full.fits() {
+        return layout_frame(engine, content, locator, styles, region);
+    }
+
+    let scale = if !measure(engine, MIN_FIT_SCALE)?.fits() {
+        engine.sink.warn(warning!(
+            content.span(),
+            "content does not fit into the region even when scaled down to {}%",
+            MIN_FIT_SCALE * 100.0;
+            hint: "try reducing the amount of content or enlarging the region";
+        ));
+        MIN_FIT_SCALE
+    } else {
+        // Invariant: The content fits at `lo`, but not at `hi`.
+        let (mut lo, mut hi) = (MIN_FIT_SCALE, 1.0);
+
+        // Enlarging the region in both dimensions by the inverse of the scale
+        // grows its area by the inverse of the squared scale.
+        let height = region.size.y;
+        if height.is_finite() && height > Abs::zero() {
+            let estimate = (height / (height + full.overflow)).sqrt();
+            if estimate > lo && estimate < hi {
+                if measure(engine, estimate)?.fits() {
+                    lo = estimate;
+                } else {
+                    hi = estimate;
+                }
+            }
+        }
+
+        for _ in 0..FIT_ITERATIONS {
+            let mid = (lo + hi) / 2.0;
+            if measure(engine, mid)?.fits() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    };
+
+    let pod = Region::new(size_at(scale), region.expand);
+    let mut frame = layout_frame(engine, content, locator, styles, pod)?;
+    let size = frame.size() * scale;
+    let baseline = frame.has_baseline().then(|| frame.baseline() * scale);
+    frame.transform(Transform::scale(Ratio::new(scale), Ratio::new(scale)));
+    frame.set_size(size);
+    if let Some(baseline) = baseline {
+        frame.set_baseline(baseline);
+    }
+
+    Ok(frame)
+}
+
+/// Lays out content into a grid of equally sized cells, producing one frame
+/// per filled grid.
+///
+/// The content flows through the cells row by row, like it would through the
+/// columns of a page, and moves on to a fresh grid once all cells of the
+/// current one are filled. This is useful for contact or sticker sheets.
+///
+/// The gutter is only inserted between adjacent cells and not around the
+/// outer edges of the grid. It is subtracted from the region's size before the
+/// remainder is divided evenly among the cells. Along an axis where the region
+/// is unbounded, the cells instead take the size of the largest laid out cell.
+pub fn layout_cell_grid(
+    engine: &mut Engine,
+    content: &Content,
+    locator: Locator,
+    styles: StyleChain,
+    region: Region,
+    cells: Axes<NonZeroUsize>,
+    gutter: Axes<Abs>,
+) -> SourceResult<Fragment> {
+    let count = cells.map(|n| n.get() as f64);
+    let cell = Size::new(
+        cell_extent(region.size.x, count.x, gutter.x),
+        cell_extent(region.size.y, count.y, gutter.y),
+    );
+
+    let regions = Regions::repeat(cell, cell.map(Abs::is_finite));
+    let frames =
+        layout_fragment(engine, content, locator, styles, regions)?.into_frames();
+
+    // Determine the cell size along unbounded axes.
+    let largest = frames.iter().fold(Size::zero(), |max, frame| max.max(frame.size()));
+    let cell = Size::new(
+        if cell.x.is_finite() { cell.x } else { largest.x },
+        if cell.y.is_finite() { cell.y } else { largest.y },
+    );
+
+    let size = Size::new(
+        cell.x * count.x + gutter.x * (count.x - 1.0),
+        cell.y * count.y + gutter.y * (count.y - 1.0),
+    );
+
+    let per_grid = cells.x.get() * cells.y.get();
+    let mut grids = vec![];
+    let mut frames = frames.into_iter().peekable();
+    while frames.peek().is_some() {
+        let mut output = Frame::hard(size);
+        for (i, frame) in frames.by_ref().take(per_grid).enumerate() {
+            let (x, y) = (i % cells.x.get(), i / cells.x.get());
+            let pos = Point::new(
+                (cell.x + gutter.x) * x as f64,
+                (cell.y + gutter.y) * y as f64,
+            );
+            output.push_frame(pos, frame);
+        }
+        grids.push(output);
+    }
+
+    Ok(Fragment::frames(grids))
+}
+
+/// Determines the extent of one of `count` cells sharing the given space,
+/// with the gutter between them.
+fn cell_extent(space: Abs, count: f64, gutter: Abs) -> Abs {
+    if !space.is_finite() {
+        return space;
+    }
+    ((space - gutter * (count - 1.0)) / count).max(Abs::zero())
 }
 
 /// Layout the columns.
@@ -91,7 +552,80 @@ pub fn layout_columns(
     styles: StyleChain,
     regions: Regions,
 ) -> SourceResult<Fragment> {
-    layout_fragment_impl(
+    let options = |count| ColumnOptions {
+        count,
+        balanced: elem.balanced.get(styles),
+        gutter: elem.gutter.resolve(styles),
+        sync_baselines: elem.sync_baselines.get(styles),
+        lines_per_column: elem.lines_per_column.get(styles),
+        first_inset: Abs::zero(),
+        fill: elem.fill.get_cloned(styles),
+        gutter_fill: elem.gutter_fill.get_cloned(styles),
+        gutter_fill_extent: elem.gutter_fill_extent.get(styles),
+        progression: elem.progression.get(styles).0,
+        reserve_bottom: elem.reserve_bottom.resolve(styles),
+        reserved: elem.reserved.get_cloned(styles),
+    };
+
+    // Cap the height of all regions at the maximum height, if any. Clipped
+    // columns only get a single region.
+    let max_height = elem.max_height.resolve(styles);
+    let clip = max_height.is_some() && elem.overflow.get(styles) == ColumnOverflow::Clip;
+    let mut backlog = vec![];
+    let regions = match max_height {
+        Some(max) if clip => Region::new(
+            Size::new(regions.size.x, regions.size.y.min(max)),
+            regions.expand,
+        )
+        .into(),
+        Some(max) => regions.map(&mut backlog, |size| Size::new(size.x, size.y.min(max))),
+        None => regions,
+    };
+
+    // Content that doesn't fit into the single region of clipped columns is
+    // cut off. The introspection tags of the cut off content are kept at the
+    // bottom edge, so that it can still be located and counted.
+    let finish = |fragment: Fragment| {
+        if !clip {
+            return fragment;
+        }
+        let mut frames = fragment.into_frames().into_iter();
+        let mut frame = frames.next().unwrap();
+        frame.clip(Curve::rect(frame.size()));
+        for mut rest in frames {
+            rest.hide();
+            frame.push_frame(Point::with_y(frame.height()), rest);
+        }
+        Fragment::frame(frame)
+    };
+
+    // If the content fits into few enough regions in a single column, there
+    // is no need to split it into columns.
+    let count = elem.count.get(styles);
+    if let Some(limit) = elem.collapse_if_fits.get(styles).0
+        && count.get() > 1
+    {
+        let fragment = layout_fragment_impl(
+            engine.world,
+            engine.library,
+            engine.introspector.into_raw(),
+            engine.traced,
+            TrackedMut::reborrow_mut(&mut engine.sink),
+            engine.route.track(),
+            &elem.body,
+            locator.relayout().track(),
+            styles,
+            regions,
+            options(NonZeroUsize::ONE),
+            None,
+        )?
+        .0;
+        if fragment.len() <= limit.get() {
+            return Ok(finish(fragment));
+        }
+    }
+
+    let fragment = layout_fragment_impl(
         engine.world,
         engine.library,
         engine.introspector.into_raw(),
@@ -102,15 +636,19 @@ pub fn layout_columns(
         locator.track(),
         styles,
         regions,
-        ColumnOptions {
-            count: elem.count.get(styles),
-            balanced: elem.balanced.get(styles),
-            gutter: elem.gutter.resolve(styles),
-        },
-    )
+        options(count),
+        None,
+    )?
+    .0;
+
+    Ok(finish(fragment))
 }
 
-/// The cached, internal implementation of [`layout_fragment`].
+/// The cached, internal implementation of [`layout_fragment`] and
+/// [`layout_flow_synced`].
+///
+/// If `breaks` are given, the flow is kept in sync with another one and its
+/// own breaks are returned alongside the fragment. Otherwise, they are empty.
 #[comemo::memoize]
 #[expect(clippy::too_many_arguments)]
 fn layout_fragment_impl(
@@ -125,7 +663,8 @@ fn layout_fragment_impl(
     styles: StyleChain,
     regions: Regions,
     column: ColumnOptions,
-) -> SourceResult<Fragment> {
+    breaks: Option<&[usize]>,
+) -> SourceResult<(Fragment, Vec<usize>)> {
     if !regions.size.x.is_finite() && regions.expand.x {
         bail!(content.span(), "cannot expand into infinite width");
     }
@@ -158,7 +697,8 @@ fn layout_fragment_impl(
         styles,
     )?;
 
-    layout_flow(
+    let mut sync = breaks.map(|breaks| SyncPoints { breaks, recorded: vec![] });
+    let fragment = layout_flow_impl(
         &mut engine,
         &children,
         &mut locator,
@@ -166,7 +706,10 @@ fn layout_fragment_impl(
         regions,
         column,
         kind.into(),
-    )
+        sync.as_mut(),
+    )?;
+
+    Ok((fragment, sync.map(|sync| sync.recorded).unwrap_or_default()))
 }
 
 /// The mode a flow can be laid out in.
@@ -196,80 +739,796 @@ pub fn layout_flow<'a>(
     children: &[Pair<'a>],
     locator: &mut SplitLocator<'a>,
     shared: StyleChain<'a>,
+    regions: Regions,
+    column: ColumnOptions,
+    mode: FlowMode,
+) -> SourceResult<Fragment> {
+    layout_flow_impl(engine, children, locator, shared, regions, column, &[], mode, None)
+}
+
+/// Lays out realized content into regions with individual column layouts.
+///
+/// The `column` options apply to the first region and the `backlog` to the
+/// following ones, parallel to the backlog of the `regions`. The last options
+/// repeat for all further regions. This way, a document can for instance start
+/// with a single-column title page and continue in two columns. With an empty
+/// backlog, this is the same as [`layout_flow`].
+///
+/// Since the column width determines where paragraphs break into lines, the
+/// content is collected once for each distinct column width. A paragraph or
+/// block that is split by a region break at which the width changes is
+/// finished at its previous width. The content after it continues at the new
+/// width. Space to keep free at the top of the first region is only taken from
+/// the `column` options.
+#[expect(clippy::too_many_arguments)]
+pub fn layout_flow_columns<'a>(
+    engine: &mut Engine,
+    children: &[Pair<'a>],
+    locator: &mut SplitLocator<'a>,
+    shared: StyleChain<'a>,
+    regions: Regions,
+    column: ColumnOptions,
+    backlog: &[ColumnOptions],
+    mode: FlowMode,
+) -> SourceResult<Fragment> {
+    layout_flow_impl(
+        engine, children, locator, shared, regions, column, backlog, mode, None,
+    )
+}
+
+/// The internal implementation of [`layout_flow`] and
+/// [`layout_flow_columns`], which can additionally keep the flow's region
+/// breaks in sync with another flow.
+#[expect(clippy::too_many_arguments)]
+fn layout_flow_impl<'a>(
+    engine: &mut Engine,
+    pairs: &[Pair<'a>],
+    locator: &mut SplitLocator<'a>,
+    shared: StyleChain<'a>,
     mut regions: Regions,
     column: ColumnOptions,
+    backlog: &[ColumnOptions],
     mode: FlowMode,
+    mut sync: Option<&mut SyncPoints>,
 ) -> SourceResult<Fragment> {
     // Prepare configuration that is shared across the whole flow.
-    let config = configuration(shared, regions, column, mode);
+    let mut config = configuration(shared, regions, &column, mode);
+    let breaks = sync.as_ref().map(|sync| sync.breaks).unwrap_or_default();
 
     // Collect the elements into pre-processed children. These are much easier
     // to handle than the raw elements.
+    //
+    // Collection, composition, and distribution are timed separately. The
+    // timings are recorded by `typst-timing` rather than the sink because
+    // memoized layouts replay their sink calls on a cache hit, which would
+    // report the durations of the original layout over and over again.
+    //
+    // Content is collected once per distinct column width. All collections
+    // use the same locator, so that their elements have the same locations.
     let bump = Bump::new();
-    let children = collect(
-        engine,
-        &bump,
-        children,
-        locator.next(&()),
-        Size::new(config.columns.width, regions.full),
-        regions.expand.x,
-        mode,
-    )?;
+    let collect_locator = locator.next(&());
+    let mut collections: Vec<Collection> = vec![];
+    for options in std::iter::once(&column).chain(backlog) {
+        let width = column_config(shared, regions, options).width;
+        if collections.iter().any(|collection| collection.width == width) {
+            continue;
+        }
 
-    let mut work = Work::new(&children);
+        let mut starts = vec![];
+        let children = collect(
+            engine,
+            &bump,
+            pairs,
+            collect_locator.relayout(),
+            Size::new(width, regions.full),
+            regions.expand.x,
+            mode,
+            (sync.is_some() || !backlog.is_empty()).then_some(&mut starts),
+        )?;
+
+        let forced =
+            forced_breaks(children.len(), &starts, breaks, config.breaks.as_deref());
+
+        collections.push(Collection { width, children, starts, forced });
+    }
+
+    let mut active = &collections[0];
+    let children = &active.children;
+
+    // The baseline grid depends on the metrics of the laid out lines, so it
+    // can only be determined after collection.
+    if column.sync_baselines {
+        config.columns.baseline_grid = baseline_grid(children, shared);
+    }
+
+    let mut work = Work::new(children);
+    work.forced = &active.forced;
     let mut finished = vec![];
+    let mut offset = 0;
+
+    // Compact pagination may relax soft break constraints in some regions to
+    // save regions.
+    let can_break = config.can_break.clone();
+    let plan = if config.compact {
+        compact_plan(
+            engine,
+            &work,
+            &mut config,
+            locator.next(&"compact"),
+            regions,
+            column.first_inset,
+        )?
+    } else {
+        EcoVec::new()
+    };
 
     // This loop runs once per region produced by the flow layout.
     loop {
-        let frame = compose(engine, &mut work, &config, locator.next(&()), regions)?;
+        if !plan.is_empty() {
+            let relaxation = plan.get(work.region).copied().unwrap_or_default();
+            relaxation.apply(&mut config, &can_break);
+        }
+
+        // Switch to the column layout of this region.
+        if work.region > 0
+            && let Some(options) = backlog.get(work.region - 1).or(backlog.last())
+        {
+            config.columns = column_config(shared, regions, options);
+            if config.columns.width != active.width {
+                let target = collections
+                    .iter()
+                    .find(|collection| collection.width == config.columns.width)
+                    .unwrap();
+                if work.switch(active, target) {
+                    active = target;
+                }
+            }
+            if options.sync_baselines {
+                config.columns.baseline_grid = baseline_grid(&active.children, shared);
+            }
+        }
+
+        let mut frame = compose_region(
+            engine,
+            &mut work,
+            &config,
+            locator.next(&()),
+            regions,
+            column.first_inset,
+        )?;
+
+        // Everything is processed, though the backlog may need draining.
+        let last = work.done() && (!regions.expand.y || regions.backlog.is_empty());
+        if mode == FlowMode::Root {
+            mark_region(engine, locator, &mut frame, work.region, offset, last);
+        }
+
+        // With fixed page breaks, the next region must start exactly at the
+        // next given break and there mustn't be any regions beyond them.
+        offset = active.consumed(&work);
+        if let Some(breaks) = &config.breaks {
+            check_break(breaks, work.region, offset, last, shared)?;
+        }
+
+        #[cfg(feature = "debug-flow")]
+        config.trace.region(&frame);
         finished.push(frame);
+        work.region += 1;
 
-        // Terminate the loop when everything is processed, though draining the
-        // backlog if necessary.
-        if work.done() && (!regions.expand.y || regions.backlog.is_empty()) {
+        // Terminate the loop when this was the last region.
+        if last {
             break;
         }
 
+        // Record the first sync point that starts after this region.
+        if let Some(sync) = &mut sync {
+            let consumed = active.consumed(&work);
+            let point = active.starts.partition_point(|&start| start < consumed);
+            if point < active.starts.len() && sync.recorded.last() != Some(&point) {
+                sync.recorded.push(point);
+            }
+        }
+
         regions.next();
     }
 
+    // Make sure that the footnote markers and entries match up. Until
+    // introspection has converged, the markers may not resolve yet, so
+    // mismatches are only reported if they remain in the last iteration.
+    if mode == FlowMode::Root && config.footnote.validate && !config.footnote.draft {
+        let result = validate_footnotes(engine, &finished);
+        engine.delay(result);
+    }
+
+    #[cfg(feature = "debug-flow")]
+    engine.sink.dump(debug::dump(children, &config.trace));
+
     Ok(Fragment::frames(finished))
 }
 
+/// The children of a flow, collected at a specific column width.
+struct Collection<'a> {
+    /// The column width at which the children were collected.
+    width: Abs,
+    /// The collected children.
+    children: Vec<Child<'a>>,
+    /// The index of the first child of each sync point. Only determined if
+    /// needed.
+    starts: Vec<usize>,
+    /// The numbers of remaining children at which a region must start.
+    forced: Vec<usize>,
+}
+
+impl Collection<'_> {
+    /// The number of children of this collection that the work already
+    /// processed. Children of another collection whose paragraph or block is
+    /// still being finished count as processed.
+    fn consumed(&self, work: &Work) -> usize {
+        self.children.len() - work.ahead()
+    }
+}
+
+/// A flow whose children were collected once, such that its regions can be
+/// laid out one at a time.
+///
+/// Each region resumes from a cursor, which describes the work that is left
+/// after laying out the regions preceding it. Start with
+/// [`FlowCursor::default`] for the first region and then pass the cursor
+/// returned alongside each frame to the call for the next region. A region can
+/// thus be relayouted on its own, for instance after an edit that only affected
+/// the region itself, without collecting the flow's children again.
+///
+/// The regions passed for a region must be those the flow would see at this
+/// point, i.e. advanced once per preceding region. If the locator is the one
+/// that [`layout_flow`]'s split locator is created from, laying out all regions
+/// one after another yields the same frames as [`layout_flow`].
+pub struct FlowRegions<'a> {
+    /// The collected children.
+    children: Vec<Child<'a>>,
+    /// The index of the first child of each sync point.
+    starts: Vec<usize>,
+    /// The sync points of another flow before which a region must start.
+    breaks: Vec<usize>,
+    /// The locator the flow's locators are derived from.
+    locator: Locator<'a>,
+    /// The flow's configuration.
+    config: Config<'a>,
+    /// The break predicate of the flow before compact pagination relaxes it.
+    can_break: Option<Func>,
+    /// Space to keep free at the top of the first region.
+    first_inset: Abs,
+}
+
+impl<'a> FlowRegions<'a> {
+    /// Collects the children of a flow.
+    ///
+    /// The `breaks` are those of another flow that this one is kept in sync
+    /// with, as for [`layout_flow_synced`]. Pass an empty slice if there is no
+    /// such flow.
+    #[expect(clippy::too_many_arguments)]
+    pub fn new(
+        engine: &mut Engine,
+        arenas: &'a Arenas,
+        children: &[Pair<'a>],
+        locator: Locator<'a>,
+        shared: StyleChain<'a>,
+        regions: Regions,
+        column: ColumnOptions,
+        mode: FlowMode,
+        breaks: &[usize],
+    ) -> SourceResult<Self> {
+        let mut config = configuration(shared, regions, &column, mode);
+
+        // Collection must see the same locator as in `layout_flow` for the
+        // children to be identical.
+        let mut starts = vec![];
+        let children = collect(
+            engine,
+            &arenas.bump,
+            children,
+            locator.relayout().split().next(&()),
+            Size::new(config.columns.width, regions.full),
+            regions.expand.x,
+            mode,
+            Some(&mut starts),
+        )?;
+
+        if column.sync_baselines {
+            config.columns.baseline_grid = baseline_grid(&children, shared);
+        }
+
+        Ok(Self {
+            children,
+            starts,
+            breaks: breaks.to_vec(),
+            locator,
+            can_break: config.can_break.clone(),
+            config,
+            first_inset: column.first_inset,
+        })
+    }
+
+    /// Lays out the region the cursor points to and returns its frame along
+    /// with the cursor for the next region.
+    pub fn layout(
+        &mut self,
+        engine: &mut Engine,
+        regions: Regions,
+        cursor: &FlowCursor,
+    ) -> SourceResult<(Frame, FlowCursor)> {
+        let children = &self.children;
+        let config = &mut self.config;
+
+        // Derive the same locators as `layout_flow`, where collection and each
+        // preceding region drew one before.
+        let mut locator = self.locator.relayout().split();
+        for _ in 0..=cursor.region {
+            locator.next(&());
+        }
+
+        // The forced breaks are determined once, when laying out the first
+        // region, and then carried along by the cursor.
+        let forced = if cursor.region == 0 {
+            forced_breaks(
+                children.len(),
+                &self.starts,
+                &self.breaks,
+                config.breaks.as_deref(),
+            )
+            .into()
+        } else {
+            cursor.forced.clone()
+        };
+
+        let mut work = Work::resume(children, cursor);
+        work.forced = &forced;
+
+        // The same goes for the plan for compact pagination.
+        let mut plan = cursor.plan.clone();
+        if config.compact {
+            if cursor.region == 0 {
+                plan = compact_plan(
+                    engine,
+                    &work,
+                    config,
+                    locator.next(&"compact"),
+                    regions,
+                    self.first_inset,
+                )?;
+            }
+            let relaxation = plan.get(cursor.region).copied().unwrap_or_default();
+            relaxation.apply(config, &self.can_break);
+        }
+
+        let offset = children.len() - work.children.len();
+        let mut frame = compose_region(
+            engine,
+            &mut work,
+            config,
+            locator.next(&()),
+            regions,
+            self.first_inset,
+        )?;
+
+        let last = work.done() && (!regions.expand.y || regions.backlog.is_empty());
+        if config.mode == FlowMode::Root {
+            mark_region(engine, &mut locator, &mut frame, cursor.region, offset, last);
+        }
+
+        let consumed = children.len() - work.children.len();
+        if let Some(breaks) = &config.breaks {
+            check_break(breaks, cursor.region, consumed, last, config.shared)?;
+        }
+
+        let mut next = work.cursor(children, cursor.region + 1);
+        next.plan = plan;
+        let point = self.starts.partition_point(|&start| start < consumed);
+        next.sync_point = (point < self.starts.len()).then_some(point);
+        Ok((frame, next))
+    }
+}
+
+/// Determines the numbers of remaining children at which a region must start,
+/// in ascending order.
+///
+/// These result from the sync points of another flow before which a region
+/// must start, given the index of the first child of each of this flow's sync
+/// points, and from fixed page breaks, given as the number of children before
+/// them.
+fn forced_breaks(
+    len: usize,
+    starts: &[usize],
+    breaks: &[usize],
+    fixed: Option<&[usize]>,
+) -> Vec<usize> {
+    let mut forced: Vec<usize> = breaks
+        .iter()
+        .filter_map(|&point| starts.get(point))
+        .map(|&start| len - start)
+        .collect();
+    forced.extend(
+        fixed
+            .unwrap_or_default()
+            .iter()
+            .filter(|&&offset| offset <= len)
+            .map(|&offset| len - offset),
+    );
+    forced.sort_unstable();
+    forced
+}
+
+/// Composes a single region, keeping the first inset free if this is the
+/// first region of the flow, filling the space reserved at the bottom, and
+/// mirroring the result if configured.
+fn compose_region(
+    engine: &mut Engine,
+    work: &mut Work,
+    config: &Config,
+    locator: Locator,
+    regions: Regions,
+    first_inset: Abs,
+) -> SourceResult<Frame> {
+    // The reserved content is laid out anew for each region, such that the
+    // elements within are located once per region. The reserved space is at
+    // least as tall as the content.
+    let mut reserve = config.reserve_bottom;
+    let mut backlog = vec![];
+    let (locator, regions, reserved) = if reserve > Abs::zero()
+        || config.reserved.is_some()
+    {
+        let mut split = locator.split();
+        let reserved = match &config.reserved {
+            Some(content) => {
+                let pod = Region::new(regions.size, Axes::new(regions.expand.x, false));
+                let locator = split.next(&content.span());
+                let frame = layout_frame(engine, content, locator, config.shared, pod)?;
+                reserve.set_max(frame.height());
+                Some(frame)
+            }
+            None => None,
+        };
+        reserve = reserve.min(regions.size.y);
+        let regions = regions.map(&mut backlog, |size| {
+            Size::new(size.x, (size.y - reserve).max(Abs::zero()))
+        });
+        (split.next(&()), regions, reserved)
+    } else {
+        (locator, regions, None)
+    };
+
+    let mut frame = compose_inset(engine, work, config, locator, regions, first_inset)?;
+
+    // Extend the region by the reserved space and fill it.
+    if reserve > Abs::zero() {
+        let y = frame.height();
+        frame.size_mut().y += reserve;
+        if let Some(reserved) = reserved {
+            frame.push_frame(Point::with_y(y), reserved);
+        }
+    }
+
+    // Flip the finished region along its vertical center axis. The transform
+    // applies to the region as a whole, so everything within keeps its
+    // position relative to the rest of the content.
+    if config.mirrored {
+        let center = frame.width() / 2.0;
+        frame.transform(Transform::scale_at(
+            Ratio::new(-1.0),
+            Ratio::one(),
+            center,
+            Abs::zero(),
+        ));
+    }
+
+    Ok(frame)
+}
+
+/// Composes a single region, keeping the first inset free if this is the
+/// first region of the flow.
+fn compose_inset(
+    engine: &mut Engine,
+    work: &mut Work,
+    config: &Config,
+    locator: Locator,
+    mut regions: Regions,
+    first_inset: Abs,
+) -> SourceResult<Frame> {
+    if work.region > 0 || first_inset <= Abs::zero() {
+        return compose(engine, work, config, locator, regions);
+    }
+
+    // Only the first region shrinks. The frame is grown back afterwards, so
+    // that it still covers the full region.
+    let inset = first_inset.min(regions.size.y);
+    regions.size.y -= inset;
+    let mut frame = compose(engine, work, config, locator, regions)?;
+    frame.size_mut().y += inset;
+    frame.translate(Point::with_y(inset));
+    Ok(frame)
+}
+
+/// Records at the start of a region of a root flow whether it is the first or
+/// the last region of the flow, along with the region's height.
+fn mark_region(
+    engine: &mut Engine,
+    locator: &mut SplitLocator,
+    frame: &mut Frame,
+    region: usize,
+    offset: usize,
+    last: bool,
+) {
+    // The key must not depend on whether the region is the last one, as the
+    // location would otherwise change between layout iterations.
+    let marker =
+        Packed::new(PageRegion::new(region == 0, last, offset, frame.height().into()));
+    let key = typst_utils::hash128(&("region", region));
+    let loc = locator.next_location(engine, key, Span::detached());
+    frame.prepend_multiple(marker_tags(marker, loc, key, Point::zero()));
+}
+
+/// Creates the start and end tags at the given position for a marker element
+/// through which layout information is recorded for introspection.
+fn marker_tags<T: NativeElement>(
+    mut marker: Packed<T>,
+    loc: Location,
+    key: u128,
+    pos: Point,
+) -> [(Point, FrameItem); 2] {
+    marker.set_location(loc);
+    let flags = TagFlags { introspectable: true, tagged: false };
+    [
+        (pos, FrameItem::Tag(Tag::Start(marker.pack(), flags))),
+        (pos, FrameItem::Tag(Tag::End(loc, key, flags))),
+    ]
+}
+
+/// Checks that a region of a flow with fixed page breaks ended where it should.
+///
+/// The `offset` is the number of children laid out up to the end of the
+/// region with the given index.
+fn check_break(
+    breaks: &[usize],
+    region: usize,
+    offset: usize,
+    last: bool,
+    shared: StyleChain,
+) -> SourceResult<()> {
+    let expected = breaks.get(region).copied();
+    if expected == Some(offset) || (last && expected.is_none()) {
+        return Ok(());
+    }
+
+    // Point to the set rule that established the breaks.
+    let span = shared
+        .entries()
+        .find(|style| {
+            style.property().is_some_and(|property| {
+                property.is(PageElem::ELEM, PageElem::breaks.index())
+            })
+        })
+        .map_or(Span::detached(), |style| style.span());
+
+    match expected {
+        Some(expected) if !last => bail!(
+            span,
+            "page {} starts at position {offset} instead of {expected}",
+            region + 2;
+            hint: "the content up to the break does not fit onto the page";
+            hint: "try adjusting the breaks or the content";
+        ),
+        Some(expected) => bail!(
+            span,
+            "content ends at position {offset}, before the break at {expected}";
+            hint: "try removing the breaks after the end of the content";
+        ),
+        None => bail!(
+            span,
+            "content needs more pages than the breaks allow";
+            hint: "try adding a break at position {offset}";
+        ),
+    }
+}
+
+/// Plans how far the soft break constraints of the flow are relaxed in each of
+/// its regions to minimize the number of regions it occupies.
+///
+/// This is a total-fit search in the spirit of the Knuth-Plass line breaking
+/// algorithm, but over region breaks: Each region has one candidate break per
+/// relaxation level, namely where the region ends if it is laid out with the
+/// level's constraints. A break that relaxes constraints incurs demerits. The
+/// search advances all paths of candidate breaks region by region and merges
+/// paths that arrive at the same state, keeping the one with the fewest
+/// demerits. The first region count at which a path completes the flow is the
+/// minimum and among the paths that complete it there, the one with the fewest
+/// demerits wins. If no path saves a region compared to upholding all
+/// constraints, the returned plan is empty.
+///
+/// To keep this tractable, only a limited number of the most advanced paths
+/// are explored further after each region and the search stops once it can't
+/// save a region anymore. The trial layouts run on a throw-away sink, so that
+/// their warnings don't end up in the output.
+fn compact_plan(
+    engine: &mut Engine,
+    work: &Work,
+    config: &mut Config,
+    locator: Locator,
+    regions: Regions,
+    first_inset: Abs,
+) -> SourceResult<EcoVec<Relaxation>> {
+    /// How many paths are explored further after each region.
+    const WIDTH: usize = 4;
+
+    /// A path of candidate breaks through the flow.
+    struct Path<'a, 'b> {
+        work: Work<'a, 'b>,
+        plan: EcoVec<Relaxation>,
+        demerits: usize,
+    }
+
+    let can_break = config.can_break.clone();
+    let result = engine.trial(|engine| {
+        let mut locator = locator.split();
+
+        // The number of regions needed when upholding all constraints. Only a
+        // path that needs fewer regions is of interest.
+        let mut strict = work.clone();
+        let mut pod = regions;
+        loop {
+            compose_region(
+                engine,
+                &mut strict,
+                config,
+                locator.next(&()),
+                pod,
+                first_inset,
+            )?;
+            strict.region += 1;
+            if strict.done() && (!pod.expand.y || pod.backlog.is_empty()) {
+                break;
+            }
+            pod.next();
+        }
+
+        let mut locator = locator.next(&"paths").split();
+        let mut pod = regions;
+        let mut paths = vec![Path {
+            work: work.clone(),
+            plan: EcoVec::new(),
+            demerits: 0,
+        }];
+        for _ in work.region + 1..strict.region {
+            let locator = locator.next(&());
+            let mut next: Vec<Path> = vec![];
+            let mut complete: Option<Path> = None;
+            for path in &paths {
+                for relaxation in
+                    [Relaxation::None, Relaxation::Breaks, Relaxation::Sticky]
+                {
+                    relaxation.apply(config, &can_break);
+                    let mut work = path.work.clone();
+                    compose_region(
+                        engine,
+                        &mut work,
+                        config,
+                        locator.relayout(),
+                        pod,
+                        first_inset,
+                    )?;
+                    work.region += 1;
+
+                    let mut plan = path.plan.clone();
+                    plan.push(relaxation);
+                    let demerits = path.demerits + relaxation as usize;
+                    let candidate = Path { work, plan, demerits };
+
+                    if candidate.work.done() && (!pod.expand.y || pod.backlog.is_empty())
+                    {
+                        if complete.as_ref().is_none_or(|c| demerits < c.demerits) {
+                            complete = Some(candidate);
+                        }
+                    } else if let Some(existing) =
+                        next.iter_mut().find(|p| p.work.equivalent(&candidate.work))
+                    {
+                        if demerits < existing.demerits {
+                            *existing = candidate;
+                        }
+                    } else {
+                        next.push(candidate);
+                    }
+                }
+            }
+
+            if let Some(complete) = complete {
+                return Ok(complete.plan);
+            }
+
+            next.sort_by_key(|path| (path.work.remaining(), path.demerits));
+            next.truncate(WIDTH);
+            paths = next;
+            pod.next();
+        }
+
+        Ok(EcoVec::new())
+    });
+
+    Relaxation::None.apply(config, &can_break);
+    result
+}
+
+/// How far the soft break constraints of a flow are relaxed in a region in
+/// compact pagination. Each level includes the relaxations of the previous
+/// ones. The discriminant is the level's demerits.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+enum Relaxation {
+    /// All constraints are upheld.
+    #[default]
+    None = 0,
+    /// The custom break predicate of the page is ignored.
+    Breaks = 1,
+    /// Additionally, sticky blocks may be separated from what follows them.
+    Sticky = 2,
+}
+
+impl Relaxation {
+    /// Configures the constraints for this level, given the flow's original
+    /// break predicate.
+    fn apply(self, config: &mut Config, can_break: &Option<Func>) {
+        config.can_break = if self >= Self::Breaks { None } else { can_break.clone() };
+        config.sticky = self < Self::Sticky;
+    }
+}
+
 /// Determine the flow's configuration.
 fn configuration<'x>(
     shared: StyleChain<'x>,
     regions: Regions,
-    column: ColumnOptions,
+    column: &ColumnOptions,
     mode: FlowMode,
 ) -> Config<'x> {
+    let area_align = shared
+        .get(FootnoteElem::area_align)
+        .map(|align| align.resolve(shared))
+        .custom();
+
     Config {
         mode,
         shared,
-        columns: {
-            let mut count = column.count.get();
-            if !regions.size.x.is_finite() {
-                count = 1;
-            }
-
-            let gutter = column.gutter.relative_to(regions.base().x);
-            let width = (regions.size.x - gutter * (count - 1) as f64) / count as f64;
-            let dir = shared.resolve(TextElem::dir);
-            ColumnConfig {
-                count,
-                width,
-                gutter,
-                dir,
-                balanced: column.balanced,
-            }
-        },
+        columns: column_config(shared, regions, column),
         footnote: FootnoteConfig {
             separator: shared
                 .get_cloned(FootnoteEntry::separator)
                 .artifact(ArtifactKind::Other),
+            separator_inset: shared
+                .resolve(FootnoteEntry::separator_inset)
+                .unwrap_or_default(),
+            rule: shared
+                .get_cloned(FootnoteEntry::rule)
+                .map(|rule| rule.artifact(ArtifactKind::Other)),
             clearance: shared.resolve(FootnoteEntry::clearance),
+            // The footnotes only directly follow the flow if the region doesn't
+            // expand. Otherwise, they are at the bottom anyway.
+            collapse_clearance: shared.get(FootnoteEntry::collapse_clearance)
+                && !regions.expand.y,
             gap: shared.resolve(FootnoteEntry::gap),
-            expand: regions.expand.x,
+            // An aligned footnote area takes up only as much width as it needs.
+            expand: regions.expand.x && area_align.is_none(),
+            area_align,
+            bottom_order: shared.get(PageElem::bottom_order),
+            draft: shared.get(DocumentElem::draft),
+            nested_indent: shared.resolve(FootnoteEntry::nested_indent),
+            breakable: shared.get(FootnoteEntry::breakable),
+            columns: if regions.size.x.is_finite() {
+                shared.get(FootnoteElem::columns).get()
+            } else {
+                1
+            },
+            column_gutter: shared
+                .resolve(FootnoteElem::column_gutter)
+                .relative_to(regions.base().x),
+            lines_above: shared.get(FootnoteElem::lines_above).get(),
+            same_column: shared.get(FootnoteElem::continuation)
+                == FootnoteContinuation::SameColumn,
+            validate: shared.get(FootnoteEntry::validate),
         },
         line_numbers: (mode == FlowMode::Root).then(|| LineNumberConfig {
             scope: shared.get(ParLine::numbering_scope),
@@ -289,9 +1548,75 @@ fn configuration<'x>(
                 )
             },
         }),
+        trim_leading: mode != FlowMode::Root || shared.get(PageElem::trim_leading),
+        first_baseline: if mode == FlowMode::Root {
+            shared.resolve(PageElem::first_baseline)
+        } else {
+            None
+        },
+        breaks: if mode == FlowMode::Root {
+            shared.get_cloned(PageElem::breaks)
+        } else {
+            None
+        },
+        mirrored: mode == FlowMode::Root && shared.get(PageElem::mirrored),
+        reserve_bottom: column.reserve_bottom,
+        reserved: column.reserved.clone(),
+        can_break: if mode == FlowMode::Root {
+            shared.get_cloned(PageElem::can_break)
+        } else {
+            None
+        },
+        sticky: true,
+        compact: mode == FlowMode::Root
+            && shared.get(PageElem::optimize) == Some(PageOptimization::Compact),
+        #[cfg(feature = "debug-flow")]
+        trace: debug::Trace::default(),
     }
 }
 
+/// Determine the configuration of a flow's columns.
+fn column_config(
+    shared: StyleChain,
+    regions: Regions,
+    column: &ColumnOptions,
+) -> ColumnConfig {
+    let mut count = column.count.get();
+    if !regions.size.x.is_finite() {
+        count = 1;
+    }
+
+    let gutter = column.gutter.map(|v| v.relative_to(regions.base().x));
+    let width = (regions.size.x - gutter.total(count)) / count as f64;
+    let dir = Axes::new(shared.resolve(TextElem::dir), column.progression);
+    ColumnConfig {
+        count,
+        width,
+        gutter,
+        dir,
+        balanced: column.balanced,
+        lines_per_column: column.lines_per_column,
+        fill: column.fill.clone(),
+        gutter_fill: column.gutter_fill.clone(),
+        gutter_fill_extent: column.gutter_fill_extent,
+        baseline_grid: None,
+    }
+}
+
+/// Determine the baseline grid for columns with synchronized baselines.
+///
+/// The distance between baselines is that of two consecutive lines of the
+/// first paragraph in the flow.
+fn baseline_grid(children: &[Child], shared: StyleChain) -> Option<BaselineGrid> {
+    let line = children.iter().find_map(|child| match child {
+        Child::Line(line) => Some(line),
+        _ => None,
+    })?;
+
+    let pitch = line.frame.height() + shared.resolve(ParElem::leading);
+    (pitch > Abs::zero()).then(|| BaselineGrid { origin: line.frame.baseline(), pitch })
+}
+
 /// The work that is left to do by flow layout.
 ///
 /// The lifetimes 'a and 'b are used across flow layout:
@@ -305,16 +1630,39 @@ struct Work<'a, 'b> {
     spill: Option<MultiSpill<'a, 'b>>,
     /// Queued floats that didn't fit in previous regions.
     floats: EcoVec<&'b PlacedChild<'a>>,
-    /// Queued footnotes that didn't fit in previous regions.
-    footnotes: EcoVec<Packed<FootnoteElem>>,
+    /// Floats that are held back until the element they should share a page
+    /// with is laid out.
+    held: EcoVec<&'b PlacedChild<'a>>,
+    /// Floats that are deferred to the top of the next region.
+    deferred: EcoVec<&'b PlacedChild<'a>>,
+    /// Queued footnotes that didn't fit in previous regions, along with their
+    /// nesting depth.
+    footnotes: EcoVec<(Packed<FootnoteElem>, usize)>,
     /// Spilled frames of a footnote that didn't fully fit. Similar to `spill`.
     footnote_spill: Option<std::vec::IntoIter<Frame>>,
+    /// Queued footnotes and footnote spill that continue in a specific column
+    /// of the next region.
+    parked: EcoVec<ParkedFootnotes>,
     /// Queued tags that will be attached to the next frame.
     tags: EcoVec<&'a Tag>,
     /// Identifies floats and footnotes that can be skipped if visited because
     /// they were already handled and incorporated as column or page level
     /// insertions.
     skips: Rc<FxHashSet<Location>>,
+    /// How many regions each queued float was deferred across so far.
+    deferrals: Rc<FxHashMap<Location, usize>>,
+    /// The number of regions of the flow that were already finished.
+    region: usize,
+    /// Weak spacing that was cut off at the end of the previous region and is
+    /// to be kept at the start of the next one.
+    leading: Option<(Abs, u8)>,
+    /// The numbers of remaining children at which a region must start, in
+    /// ascending order. Used to keep the flow in sync with another one.
+    forced: &'b [usize],
+    /// Children of another collection of the flow that follow once `children`
+    /// is exhausted. This is only non-empty while the rest of a paragraph or
+    /// block is finished after switching to a different column width.
+    rest: &'b [Child<'a>],
 }
 
 impl<'a, 'b> Work<'a, 'b> {
@@ -324,13 +1672,137 @@ impl<'a, 'b> Work<'a, 'b> {
             children,
             spill: None,
             floats: EcoVec::new(),
+            held: EcoVec::new(),
+            deferred: EcoVec::new(),
             footnotes: EcoVec::new(),
             footnote_spill: None,
+            parked: EcoVec::new(),
             tags: EcoVec::new(),
             skips: Rc::new(FxHashSet::default()),
+            deferrals: Rc::new(FxHashMap::default()),
+            region: 0,
+            leading: None,
+            forced: &[],
+            rest: &[],
+        }
+    }
+
+    /// Reconstruct the work state from a cursor into a list of children.
+    fn resume(children: &'b [Child<'a>], cursor: &'b FlowCursor) -> Self {
+        let child = |i: usize| children.get(i);
+        let placed = |i: usize| match child(i)? {
+            Child::Placed(placed) => Some(&**placed),
+            _ => None,
+        };
+        Self {
+            children: &children[cursor.child.min(children.len())..],
+            spill: cursor.spill.as_ref().and_then(|(i, spill)| match child(*i)? {
+                Child::Multi(multi) => Some(spill.attach(multi)),
+                _ => None,
+            }),
+            floats: cursor.floats.iter().filter_map(|&i| placed(i)).collect(),
+            held: cursor.held.iter().filter_map(|&i| placed(i)).collect(),
+            deferred: cursor.deferred.iter().filter_map(|&i| placed(i)).collect(),
+            footnotes: cursor.footnotes.clone(),
+            footnote_spill: cursor.footnote_spill.clone().map(Vec::into_iter),
+            parked: cursor
+                .parked
+                .iter()
+                .map(|parked| ParkedFootnotes {
+                    column: parked.column,
+                    footnotes: parked.footnotes.clone(),
+                    spill: parked.spill.clone().map(Vec::into_iter),
+                })
+                .collect(),
+            tags: cursor
+                .tags
+                .iter()
+                .filter_map(|&i| match child(i)? {
+                    Child::Tag(tag) => Some(*tag),
+                    _ => None,
+                })
+                .collect(),
+            skips: Rc::new(cursor.skips.clone()),
+            deferrals: Rc::new(cursor.deferrals.clone()),
+            region: cursor.region,
+            leading: cursor.leading,
+            forced: &cursor.forced,
+            rest: &[],
         }
     }
 
+    /// Capture the work state as a cursor that doesn't borrow from the
+    /// children. The children must be those the work was created from.
+    fn cursor(&self, children: &'b [Child<'a>], region: usize) -> FlowCursor {
+        let find = |pred: &dyn Fn(&Child) -> bool| {
+            children.iter().position(pred).expect("child to be part of the flow")
+        };
+
+        let spill = self.spill.as_ref().map(|spill| {
+            let multi = spill.multi();
+            let i =
+                find(&|child| matches!(child, Child::Multi(m) if ptr::eq(&**m, multi)));
+            (i, spill.detach())
+        });
+
+        let index = |placed: &PlacedChild| {
+            find(&|child| matches!(child, Child::Placed(p) if ptr::eq(&**p, placed)))
+        };
+
+        let floats = self.floats.iter().map(|&placed| index(placed)).collect();
+        let held = self.held.iter().map(|&placed| index(placed)).collect();
+        let deferred = self.deferred.iter().map(|&placed| index(placed)).collect();
+
+        let tags = self
+            .tags
+            .iter()
+            .map(|&tag| find(&|child| matches!(child, Child::Tag(t) if ptr::eq(*t, tag))))
+            .collect();
+
+        FlowCursor {
+            region,
+            child: children.len() - self.children.len(),
+            spill,
+            floats,
+            held,
+            deferred,
+            footnotes: self.footnotes.clone(),
+            footnote_spill: self.footnote_spill.as_ref().map(|s| s.as_slice().to_vec()),
+            parked: self
+                .parked
+                .iter()
+                .map(|parked| ParkedFootnotes {
+                    column: parked.column,
+                    footnotes: parked.footnotes.clone(),
+                    spill: parked.spill.as_ref().map(|s| s.as_slice().to_vec()),
+                })
+                .collect(),
+            tags,
+            skips: (*self.skips).clone(),
+            deferrals: (*self.deferrals).clone(),
+            leading: self.leading,
+            forced: self.forced.iter().copied().collect(),
+            plan: EcoVec::new(),
+            sync_point: None,
+            done: self.done(),
+        }
+    }
+
+    /// Whether two work states are interchangeable for the layout of the
+    /// following regions. This is an approximation that only compares how much
+    /// work is left of each kind.
+    fn equivalent(&self, other: &Self) -> bool {
+        self.remaining() == other.remaining()
+            && self.spill.is_some() == other.spill.is_some()
+            && self.floats.len() == other.floats.len()
+            && self.held.len() == other.held.len()
+            && self.deferred.len() == other.deferred.len()
+            && self.footnotes.len() == other.footnotes.len()
+            && self.footnote_spill.is_some() == other.footnote_spill.is_some()
+            && self.parked.len() == other.parked.len()
+            && self.skips.len() == other.skips.len()
+    }
+
     /// Get the first unprocessed child, from the start of the slice.
     fn head(&self) -> Option<&'b Child<'a>> {
         self.children.first()
@@ -339,6 +1811,58 @@ impl<'a, 'b> Work<'a, 'b> {
     /// Mark the `head()` child as processed, advancing the slice by one.
     fn advance(&mut self) {
         self.children = &self.children[1..];
+        if self.children.is_empty() {
+            self.children = std::mem::take(&mut self.rest);
+        }
+    }
+
+    /// The number of children that are left, including those of another
+    /// collection that follow.
+    fn remaining(&self) -> usize {
+        self.children.len() + self.rest.len()
+    }
+
+    /// The number of children that are left in the collection the work
+    /// continues with.
+    fn ahead(&self) -> usize {
+        if self.rest.is_empty() { self.children.len() } else { self.rest.len() }
+    }
+
+    /// Whether a region must start with the `head()` child to keep the flow
+    /// in sync with another one.
+    fn forced_break(&self) -> bool {
+        self.rest.is_empty() && self.forced.binary_search(&self.children.len()).is_ok()
+    }
+
+    /// Continues with another collection of the flow from the first sync
+    /// point that is still ahead. The rest of a paragraph or block that was
+    /// already partially processed is kept from the current collection.
+    ///
+    /// Returns whether there was such a sync point. If not, the work stays
+    /// with the current collection.
+    fn switch(
+        &mut self,
+        current: &'b Collection<'a>,
+        target: &'b Collection<'a>,
+    ) -> bool {
+        let consumed = current.consumed(self);
+        let point = current.starts.partition_point(|&start| start < consumed);
+        let Some(&start) = target.starts.get(point) else { return false };
+
+        // If the work is already finishing a paragraph or block of another
+        // collection, only the children after it are replaced.
+        if self.rest.is_empty() {
+            let end = current.starts[point] - consumed;
+            self.children = &self.children[..end];
+        }
+
+        self.rest = &target.children[start..];
+        self.forced = &target.forced;
+        if self.children.is_empty() {
+            self.children = std::mem::take(&mut self.rest);
+        }
+
+        true
     }
 
     /// Whether all work is done. This means we can terminate flow layout.
@@ -346,8 +1870,35 @@ impl<'a, 'b> Work<'a, 'b> {
         self.children.is_empty()
             && self.spill.is_none()
             && self.floats.is_empty()
+            && self.held.is_empty()
+            && self.deferred.is_empty()
             && self.footnote_spill.is_none()
             && self.footnotes.is_empty()
+            && self.parked.is_empty()
+    }
+
+    /// Moves the queued footnotes and footnote spill left by the given column
+    /// aside, such that they continue in the same column of the next region.
+    fn park_footnotes(&mut self, column: usize) {
+        let footnotes = std::mem::take(&mut self.footnotes);
+        let spill = self.footnote_spill.take();
+        if !footnotes.is_empty() || spill.is_some() {
+            self.parked.push(ParkedFootnotes { column, footnotes, spill });
+        }
+    }
+
+    /// Restores the footnotes that were parked for the given column.
+    fn unpark_footnotes(&mut self, column: usize) {
+        if let Some(i) = self.parked.iter().position(|parked| parked.column == column) {
+            let parked = self.parked.remove(i);
+            self.footnotes.extend(parked.footnotes);
+            self.footnote_spill = parked.spill;
+        }
+    }
+
+    /// Counts that a queued float is tried again in a new region.
+    fn defer(&mut self, location: Location) {
+        *Rc::make_mut(&mut self.deferrals).entry(location).or_default() += 1;
     }
 
     /// Add skipped floats and footnotes from the insertion areas to the skip
@@ -359,7 +1910,86 @@ impl<'a, 'b> Work<'a, 'b> {
     }
 }
 
+/// Footnotes that continue in a specific column of the next region.
+#[derive(Debug, Clone)]
+struct ParkedFootnotes<S = std::vec::IntoIter<Frame>> {
+    /// The column in which they continue.
+    column: usize,
+    /// Queued footnotes and their nesting depth.
+    footnotes: EcoVec<(Packed<FootnoteElem>, usize)>,
+    /// Spilled frames of a footnote that didn't fully fit.
+    spill: Option<S>,
+}
+
+/// A snapshot of the work that is left to do by flow layout at the start of a
+/// region.
+///
+/// In contrast to the internal work state, the cursor doesn't borrow from the
+/// flow's children, but refers to them by index. It can thus be stored and
+/// later be used to resume layout with [`FlowRegions::layout`].
+#[derive(Debug, Clone, Default)]
+pub struct FlowCursor {
+    /// The number of regions laid out before.
+    region: usize,
+    /// The index of the first unprocessed child.
+    child: usize,
+    /// The index and state of a breakable block that spilled into this region.
+    spill: Option<(usize, DetachedSpill)>,
+    /// The indices of queued floats.
+    floats: EcoVec<usize>,
+    /// The indices of floats that are held back.
+    held: EcoVec<usize>,
+    /// The indices of floats deferred to the next region.
+    deferred: EcoVec<usize>,
+    /// Queued footnotes and their nesting depth.
+    footnotes: EcoVec<(Packed<FootnoteElem>, usize)>,
+    /// Spilled frames of a footnote that didn't fully fit.
+    footnote_spill: Option<Vec<Frame>>,
+    /// Queued footnotes and footnote spill per column they continue in.
+    parked: EcoVec<ParkedFootnotes<Vec<Frame>>>,
+    /// The indices of queued tags.
+    tags: EcoVec<usize>,
+    /// Floats and footnotes that were already handled.
+    skips: FxHashSet<Location>,
+    /// How many regions each queued float was deferred across so far.
+    deferrals: FxHashMap<Location, usize>,
+    /// Weak spacing to be kept at the start of the region.
+    leading: Option<(Abs, u8)>,
+    /// The numbers of remaining children at which a region must start.
+    forced: EcoVec<usize>,
+    /// How far soft break constraints are relaxed in each region for compact
+    /// pagination. Empty if they aren't relaxed at all.
+    plan: EcoVec<Relaxation>,
+    /// The first sync point that starts at or after the cursor, if any.
+    sync_point: Option<usize>,
+    /// Whether all work is done.
+    done: bool,
+}
+
+impl FlowCursor {
+    /// The number of regions laid out before this cursor.
+    pub fn region(&self) -> usize {
+        self.region
+    }
+
+    /// Whether all work is done, i.e. the flow needs no further region (unless
+    /// it must drain a region backlog).
+    pub fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// The number of the first sync point that starts at or after the cursor,
+    /// if any. Collecting these for all region boundaries yields the breaks of
+    /// the flow, as returned by [`layout_flow_synced`].
+    pub fn sync_point(&self) -> Option<usize> {
+        self.sync_point
+    }
+}
+
 /// Options defining the column layout.
+///
+/// The options apply uniformly to all regions of a flow, unless it is laid out
+/// with [`layout_flow_columns`].
 #[derive(Hash)]
 pub struct ColumnOptions {
     /// The number of columns.
@@ -367,7 +1997,47 @@ pub struct ColumnOptions {
     /// Whether column heights are to be equalized.
     pub balanced: bool,
     /// The spacing between columns.
-    pub gutter: Rel<Abs>,
+    pub gutter: ColumnGutter<Rel<Abs>>,
+    /// Whether to snap lines onto a baseline grid shared by all columns.
+    pub sync_baselines: bool,
+    /// The maximum number of paragraph lines in each column, if any.
+    pub lines_per_column: Option<NonZeroUsize>,
+    /// Space to keep free at the top of the first region. Later regions are
+    /// unaffected.
+    pub first_inset: Abs,
+    /// How to fill the columns.
+    pub fill: Celled<Option<Paint>>,
+    /// Content that is tiled vertically into each gutter.
+    pub gutter_fill: Option<Content>,
+    /// How far the gutter fill extends vertically.
+    pub gutter_fill_extent: GutterFillExtent,
+    /// The vertical direction in which content progresses within a column.
+    pub progression: Dir,
+    /// Space to reserve at the bottom of every region. The content does not
+    /// flow into it.
+    pub reserve_bottom: Abs,
+    /// Content to place into the reserved space of every region.
+    pub reserved: Option<Content>,
+}
+
+impl ColumnOptions {
+    /// Options for a flow with a single column.
+    pub fn single() -> Self {
+        Self {
+            count: NonZeroUsize::ONE,
+            balanced: false,
+            gutter: ColumnGutter::splat(Rel::zero()),
+            sync_baselines: false,
+            lines_per_column: None,
+            first_inset: Abs::zero(),
+            fill: Celled::default(),
+            gutter_fill: None,
+            gutter_fill_extent: GutterFillExtent::Full,
+            progression: Dir::TTB,
+            reserve_bottom: Abs::zero(),
+            reserved: None,
+        }
+    }
 }
 
 /// Shared configuration for the whole flow.
@@ -384,18 +2054,88 @@ struct Config<'x> {
     footnote: FootnoteConfig,
     /// Settings for line numbers.
     line_numbers: Option<LineNumberConfig>,
+    /// Whether weak spacing at the start of continuation regions is removed.
+    trim_leading: bool,
+    /// The distance from the top of each region to the baseline of its first
+    /// line.
+    first_baseline: Option<Abs>,
+    /// The fixed positions at which regions must start, as the number of
+    /// children before them.
+    breaks: Option<Vec<usize>>,
+    /// Whether finished regions are mirrored horizontally.
+    mirrored: bool,
+    /// Space reserved at the bottom of every region.
+    reserve_bottom: Abs,
+    /// Content placed into the reserved space of every region.
+    reserved: Option<Content>,
+    /// Decides whether the flow may break at a given point.
+    can_break: Option<Func>,
+    /// Whether sticky blocks stick to the content that follows them.
+    sticky: bool,
+    /// Whether to minimize the number of regions by relaxing soft break
+    /// constraints.
+    compact: bool,
+    /// Control flow events recorded for debugging.
+    #[cfg(feature = "debug-flow")]
+    trace: debug::Trace,
+}
+
+impl Config<'_> {
+    /// Records a control flow event if flow debugging is enabled.
+    fn trace(&self, _stop: &Stop) {
+        #[cfg(feature = "debug-flow")]
+        self.trace.stop(_stop);
+    }
 }
 
 /// Configuration of footnotes.
 struct FootnoteConfig {
     /// The separator between flow content and footnotes. Typically a line.
     separator: Content,
+    /// The space around the separator.
+    separator_inset: Sides<Abs>,
+    /// The rule preceding each footnote entry, if any.
+    rule: Option<Content>,
     /// The amount of space left above the separator.
     clearance: Abs,
+    /// Whether the clearance collapses with trailing spacing of the flow.
+    collapse_clearance: bool,
     /// The gap between footnote entries.
     gap: Abs,
     /// Whether horizontal expansion is enabled for footnotes.
     expand: bool,
+    /// How to align the footnote area within the column, if it shouldn't
+    /// span the column's full width.
+    area_align: Option<FixedAlignment>,
+    /// The order of bottom floats and footnotes.
+    bottom_order: BottomOrder,
+    /// Whether footnote entries are left out because the document is a draft.
+    draft: bool,
+    /// How far entries are indented per level of footnote nesting.
+    nested_indent: Abs,
+    /// Whether entries may break across regions instead of moving to the next
+    /// one together with their origin frame.
+    breakable: bool,
+    /// The number of columns in the footnote area.
+    columns: usize,
+    /// The space between the footnote area's columns.
+    column_gutter: Abs,
+    /// The minimum number of lines that precede the footnote area.
+    lines_above: usize,
+    /// Whether footnotes that don't fit continue in the same column of the
+    /// next region instead of the next column.
+    same_column: bool,
+    /// Whether to cross-check footnote markers and entries after layout.
+    validate: bool,
+}
+
+impl FootnoteConfig {
+    /// The width of a column of the footnote area, given the area's full
+    /// width.
+    fn column_width(&self, width: Abs) -> Abs {
+        let gutters = self.column_gutter * (self.columns - 1) as f64;
+        ((width - gutters) / self.columns as f64).max(Abs::zero())
+    }
 }
 
 /// Configuration of columns.
@@ -405,12 +2145,34 @@ struct ColumnConfig {
     /// The width of each column.
     width: Abs,
     /// The amount of space between columns.
-    gutter: Abs,
-    /// The horizontal direction in which columns progress. Defined by
-    /// `text.dir`.
-    dir: Dir,
+    gutter: ColumnGutter<Abs>,
+    /// The directions in which content progresses. The horizontal direction
+    /// orders the columns and is defined by `text.dir`. The vertical direction
+    /// orders the content within a column and is defined by
+    /// `columns.progression`.
+    dir: Axes<Dir>,
     /// Whether to equalize the height of columns by breaking columns early.
     balanced: bool,
+    /// The maximum number of paragraph lines in each column, if any.
+    lines_per_column: Option<NonZeroUsize>,
+    /// How to fill the columns.
+    fill: Celled<Option<Paint>>,
+    /// Content that is tiled vertically into each gutter.
+    gutter_fill: Option<Content>,
+    /// How far the gutter fill extends vertically.
+    gutter_fill_extent: GutterFillExtent,
+    /// The grid onto which the baselines of lines are snapped, if baselines
+    /// are synchronized across columns.
+    baseline_grid: Option<BaselineGrid>,
+}
+
+/// A grid of evenly spaced baselines, shared by all columns of a flow.
+#[derive(Debug, Copy, Clone)]
+struct BaselineGrid {
+    /// The position of the first baseline, relative to the top of a column.
+    origin: Abs,
+    /// The distance between two consecutive baselines.
+    pitch: Abs,
 }
 
 /// Configuration of line numbers.
@@ -1,4 +1,6 @@
+use typst_library::diag::warning;
 use typst_library::introspection::SplitLocator;
+use typst_library::layout::{Frame, Point};
 use typst_utils::Numeric;
 
 use super::*;
@@ -11,6 +13,7 @@ pub fn finalize(
     lines: &[Line],
     region: Size,
     expand: bool,
+    wrap: Option<ParWrap>,
     locator: &mut SplitLocator<'_>,
 ) -> SourceResult<Fragment> {
     // Determine the resulting width: Full width of the region if we should
@@ -26,10 +29,88 @@ pub fn finalize(
         region.x
     };
 
+    // In a draft, warn about lines that are wider than the region.
+    if p.config.draft && region.x.is_finite() {
+        for (i, line) in lines.iter().enumerate() {
+            let available = match wrap {
+                Some(wrap) if wrap.narrows(i) => region.x - wrap.width,
+                _ => region.x,
+            };
+            check_overflow(engine, p, line, available);
+        }
+    }
+
     // Stack the lines into one frame per region.
     lines
         .iter()
-        .map(|line| commit(engine, p, line, width, region.y, locator))
+        .enumerate()
+        .map(|(i, line)| match wrap {
+            Some(wrap) if wrap.narrows(i) => {
+                commit_wrapped(engine, p, line, width, region, wrap, locator)
+            }
+            _ => commit(engine, p, line, width, region.y, locator),
+        })
         .collect::<SourceResult<_>>()
         .map(Fragment::frames)
 }
+
+/// Warns if a line is wider than the available width even after shrinking it.
+/// This happens when inline content that can't be broken, like a long URL,
+/// doesn't fit into a line of its own.
+fn check_overflow(engine: &mut Engine, p: &Preparation, line: &Line, available: Abs) {
+    let overflow =
+        line.width + p.config.hanging_indent - line.shrinkability() - available;
+    if overflow <= Abs::zero() || overflow.approx_empty() {
+        return;
+    }
+
+    // Report the overflow at the widest item, which is most likely the one
+    // that couldn't be broken.
+    let Some(span) = line
+        .items
+        .indexed_iter()
+        .filter_map(|(idx, item)| Some((idx.item_index()?, item.natural_width())))
+        .max_by_key(|&(_, width)| width)
+        .map(|(i, _)| p.spans.span_at(p.items[i].0.start).0)
+        .filter(|span| !span.is_detached())
+    else {
+        return;
+    };
+
+    engine.sink.warn(warning!(
+        span,
+        "content overflows the line by {:.1}pt",
+        overflow.to_pt();
+        hint: "try wrapping it in a `box` with a fixed width or enabling hyphenation";
+    ));
+}
+
+/// Commits a line that runs alongside a wrapping float. The line is laid out
+/// at the narrowed width and moved away from the float's side.
+fn commit_wrapped(
+    engine: &mut Engine,
+    p: &Preparation,
+    line: &Line,
+    width: Abs,
+    region: Size,
+    wrap: ParWrap,
+    locator: &mut SplitLocator<'_>,
+) -> SourceResult<Frame> {
+    // The line was broken against the region's width minus the hanging indent
+    // and the float, not against the paragraph's final width, which is only
+    // the widest line's width in ragged text. The indent is applied by
+    // `commit` within the narrowed width.
+    let available = if region.x.is_finite() { region.x } else { width };
+    let narrow = (available - p.config.hanging_indent - wrap.width).max(Abs::zero());
+    let inner =
+        commit(engine, p, line, narrow + p.config.hanging_indent, region.y, locator)?;
+    let x = match wrap.side {
+        FixedAlignment::Start => wrap.width,
+        _ => Abs::zero(),
+    };
+
+    let mut frame = Frame::soft(Size::new(width, inner.height()));
+    frame.set_baseline(inner.baseline());
+    frame.push_frame(Point::with_x(x), inner);
+    Ok(frame)
+}
@@ -4,7 +4,7 @@ use std::ops::{Deref, DerefMut};
 use typst_library::engine::Engine;
 use typst_library::introspection::{SplitLocator, Tag, TagFlags};
 use typst_library::layout::{Abs, Dir, Em, Fr, Frame, FrameItem, Point};
-use typst_library::model::ParLineMarker;
+use typst_library::model::{LineNumberFilter, ParLineMarker};
 use typst_library::text::{Lang, TextElem, families, variant};
 use typst_utils::Numeric;
 
@@ -616,7 +616,7 @@ pub fn commit(
     output.set_baseline(top);
 
     if let Some(marker) = &p.config.numbering_marker {
-        add_par_line_marker(&mut output, marker, engine, locator, top);
+        add_par_line_marker(&mut output, marker, line, width, engine, locator, top);
     }
 
     // Ensure that the final frame's items are in logical order rather than in
@@ -641,9 +641,15 @@ pub fn commit(
 ///
 /// The `top` parameter is used to ensure the marker, and thus the line's
 /// number in the margin, is aligned to the line's baseline.
+///
+/// The marker also records the extent of the line (and, if a function decides
+/// which lines are numbered, its text), so that the root flow can decide
+/// whether to number it.
 fn add_par_line_marker(
     output: &mut Frame,
     marker: &Packed<ParLineMarker>,
+    line: &Line,
+    full: Abs,
     engine: &mut Engine,
     locator: &mut SplitLocator,
     top: Abs,
@@ -655,6 +661,20 @@ fn add_par_line_marker(
     // where line numbers can be displayed), so we just need it to be in a tag
     // and to be valid (to have a location).
     let mut marker = marker.clone();
+    marker.width = Some(line.width);
+    marker.full = Some(full);
+    if matches!(marker.number_lines, Smart::Custom(LineNumberFilter::Func(_))) {
+        marker.text = Some(
+            line.items
+                .iter()
+                .filter_map(|item| match item {
+                    Item::Text(shaped) => Some(shaped.text),
+                    _ => None,
+                })
+                .collect(),
+        );
+    }
+
     let key = typst_utils::hash128(&marker);
     let loc = locator.next_location(engine, key, marker.span());
     marker.set_location(loc);
@@ -793,6 +813,12 @@ impl LogicalIndex {
         // limited to `isize::MAX` elements.
         Self(i + 1)
     }
+
+    /// The index of the item in [`p.items`](Preparation::items), if it is a
+    /// normal item.
+    pub fn item_index(self) -> Option<usize> {
+        (self != Self::START_HYPHEN && self != Self::END_HYPHEN).then(|| self.0 - 1)
+    }
 }
 
 /// A reference to or a boxed item.
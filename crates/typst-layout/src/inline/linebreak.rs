@@ -148,18 +148,62 @@ impl Trim {
     }
 }
 
+/// The widths available to the lines of a paragraph.
+#[derive(Debug, Copy, Clone)]
+pub struct LineWidths {
+    /// The width available to most lines.
+    pub full: Abs,
+    /// The width available to the `narrowed` lines after the first `skip` ones.
+    pub narrow: Abs,
+    /// The number of lines at the start that have the full width before the
+    /// narrowed ones.
+    pub skip: usize,
+    /// The number of lines that only have the `narrow` width.
+    pub narrowed: usize,
+}
+
+impl LineWidths {
+    /// The same width for all lines.
+    pub fn uniform(width: Abs) -> Self {
+        Self { full: width, narrow: width, skip: 0, narrowed: 0 }
+    }
+
+    /// The width available to the line with the given index.
+    fn get(&self, line: usize) -> Abs {
+        if (self.skip..self.skip + self.narrowed).contains(&line) {
+            self.narrow
+        } else {
+            self.full
+        }
+    }
+
+    /// Whether all lines have the same width.
+    fn is_uniform(&self) -> bool {
+        self.narrowed == 0 || self.narrow == self.full
+    }
+}
+
 /// Breaks the text into lines.
 pub fn linebreak<'a>(
     engine: &Engine,
     p: &'a Preparation<'a>,
-    width: Abs,
+    widths: LineWidths,
 ) -> Vec<Line<'a>> {
-    match p.config.linebreaks {
-        Linebreaks::Simple => linebreak_simple(engine, p, width),
-        Linebreaks::Optimized => linebreak_optimized(engine, p, width),
+    let mut lines = match p.config.linebreaks {
+        Linebreaks::Simple => linebreak_simple(engine, p, widths),
+        Linebreaks::Optimized => linebreak_optimized(engine, p, widths),
+    };
+
+    if p.config.balance_last_line {
+        balance_last_line(engine, p, widths, &mut lines);
     }
+
+    lines.into_iter().map(|(line, _)| line).collect()
 }
 
+/// Lines together with the text offsets at which they end.
+type BrokenLines<'a> = Vec<(Line<'a>, usize)>;
+
 /// Performs line breaking in simple first-fit style. This means that we build
 /// lines greedily, always taking the longest possible line. This may lead to
 /// very unbalanced line, but is fast and simple.
@@ -167,15 +211,16 @@ pub fn linebreak<'a>(
 fn linebreak_simple<'a>(
     engine: &Engine,
     p: &'a Preparation<'a>,
-    width: Abs,
-) -> Vec<Line<'a>> {
+    widths: LineWidths,
+) -> BrokenLines<'a> {
     let mut lines = Vec::with_capacity(16);
     let mut start = 0;
     let mut last = None;
 
     breakpoints(p, |end, breakpoint| {
         // Compute the line and its size.
-        let mut attempt = line(engine, p, start..end, breakpoint, lines.last());
+        let mut attempt = line(engine, p, start..end, breakpoint, pred(&lines));
+        let mut width = widths.get(lines.len());
 
         // If the line doesn't fit anymore, we push the last fitting attempt
         // into the stack and rebuild the line from the attempt's end. The
@@ -183,16 +228,17 @@ fn linebreak_simple<'a>(
         if !width.fits(attempt.width)
             && let Some((last_attempt, last_end)) = last.take()
         {
-            lines.push(last_attempt);
+            lines.push((last_attempt, last_end));
             start = last_end;
-            attempt = line(engine, p, start..end, breakpoint, lines.last());
+            attempt = line(engine, p, start..end, breakpoint, pred(&lines));
+            width = widths.get(lines.len());
         }
 
         // Finish the current line if there is a mandatory line break (i.e. due
         // to "\n") or if the line doesn't fit horizontally already since then
         // no shorter line will be possible.
         if breakpoint == Breakpoint::Mandatory || !width.fits(attempt.width) {
-            lines.push(attempt);
+            lines.push((attempt, end));
             start = end;
             last = None;
         } else {
@@ -200,13 +246,15 @@ fn linebreak_simple<'a>(
         }
     });
 
-    if let Some((line, _)) = last {
-        lines.push(line);
-    }
-
+    lines.extend(last);
     lines
 }
 
+/// The line preceding the next line.
+fn pred<'a, 'b>(lines: &'b BrokenLines<'a>) -> Option<&'b Line<'a>> {
+    lines.last().map(|(line, _)| line)
+}
+
 /// Performs line breaking in optimized Knuth-Plass style. Here, we use more
 /// context to determine the line breaks than in the simple first-fit style. For
 /// example, we might choose to cut a line short even though there is still a
@@ -227,17 +275,26 @@ fn linebreak_simple<'a>(
 fn linebreak_optimized<'a>(
     engine: &Engine,
     p: &'a Preparation<'a>,
-    width: Abs,
-) -> Vec<Line<'a>> {
+    widths: LineWidths,
+) -> BrokenLines<'a> {
     let metrics = CostMetrics::compute(p);
 
     // Determines the exact costs of a likely good layout through Knuth-Plass
     // with approximate metrics. We can use this cost as an upper bound to prune
     // the search space in our proper optimization pass below.
-    let upper_bound = linebreak_optimized_approximate(engine, p, width, &metrics);
+    //
+    // If the lines have different widths, the optimal layout up to a
+    // breakpoint also determines the widths of the following lines. Then, the
+    // approximate layout is not guaranteed to be reachable in the exact pass,
+    // so we can't use its cost as a bound.
+    let upper_bound = if widths.is_uniform() {
+        linebreak_optimized_approximate(engine, p, widths.full, &metrics)
+    } else {
+        Cost::INFINITY
+    };
 
     // Using the upper bound, perform exact optimized linebreaking.
-    linebreak_optimized_bounded(engine, p, width, &metrics, upper_bound)
+    linebreak_optimized_bounded(engine, p, widths, &metrics, upper_bound)
 }
 
 /// Performs line breaking in optimized Knuth-Plass style, but with an upper
@@ -246,20 +303,27 @@ fn linebreak_optimized<'a>(
 fn linebreak_optimized_bounded<'a>(
     engine: &Engine,
     p: &'a Preparation<'a>,
-    width: Abs,
+    widths: LineWidths,
     metrics: &CostMetrics,
     upper_bound: Cost,
-) -> Vec<Line<'a>> {
+) -> BrokenLines<'a> {
     /// An entry in the dynamic programming table for inline layout optimization.
     struct Entry<'a> {
         pred: usize,
         total: Cost,
         line: Line<'a>,
         end: usize,
+        count: usize,
     }
 
     // Dynamic programming table.
-    let mut table = vec![Entry { pred: 0, total: 0.0, line: Line::empty(), end: 0 }];
+    let mut table = vec![Entry {
+        pred: 0,
+        total: 0.0,
+        line: Line::empty(),
+        end: 0,
+        count: 0,
+    }];
 
     let mut active = 0;
     let mut prev_end = 0;
@@ -286,6 +350,10 @@ fn linebreak_optimized_bounded<'a>(
             // Build the line.
             let attempt = line(engine, p, start..end, breakpoint, Some(&pred.line));
 
+            // The line's width depends on how many lines precede it in the
+            // optimal layout up to its start.
+            let width = widths.get(pred.count);
+
             // Determine the cost of the line and its stretch ratio.
             let (line_ratio, line_cost) = ratio_and_cost(
                 p,
@@ -335,7 +403,13 @@ fn linebreak_optimized_bounded<'a>(
 
             // If this attempt is better than what we had before, take it!
             if best.as_ref().is_none_or(|best| best.total >= total) {
-                best = Some(Entry { pred: pred_index, total, line: attempt, end });
+                best = Some(Entry {
+                    pred: pred_index,
+                    total,
+                    line: attempt,
+                    end,
+                    count: pred.count + 1,
+                });
             }
         }
 
@@ -359,13 +433,13 @@ fn linebreak_optimized_bounded<'a>(
         panic!("bounded inline layout is incomplete");
 
         #[cfg(not(debug_assertions))]
-        return linebreak_optimized_bounded(engine, p, width, metrics, Cost::INFINITY);
+        return linebreak_optimized_bounded(engine, p, widths, metrics, Cost::INFINITY);
     }
 
     while idx != 0 {
         table.truncate(idx + 1);
         let entry = table.pop().unwrap();
-        lines.push(entry.line);
+        lines.push((entry.line, entry.end));
         idx = entry.pred;
     }
 
@@ -529,6 +603,67 @@ fn linebreak_optimized_approximate(
     exact
 }
 
+/// Moves the last word of the second-to-last line down if the last line would
+/// otherwise consist of just a single word.
+///
+/// The number of lines stays the same, so this doesn't affect widow and orphan
+/// prevention in the flow. If the longer last line doesn't fit, the lines are
+/// kept as they are.
+fn balance_last_line<'a>(
+    engine: &Engine,
+    p: &'a Preparation<'a>,
+    widths: LineWidths,
+    lines: &mut BrokenLines<'a>,
+) {
+    let n = lines.len();
+    if n < 2 {
+        return;
+    }
+
+    let start = if n > 2 { lines[n - 3].1 } else { 0 };
+    let mid = lines[n - 2].1;
+    let end = lines[n - 1].1;
+
+    // Find the last word boundary in the second-to-last line and check that
+    // the last line has no word boundary of its own. Hyphenation opportunities
+    // don't count as word boundaries.
+    let mut split = None;
+    let mut mid_breakpoint = Breakpoint::Normal;
+    let mut end_breakpoint = Breakpoint::Mandatory;
+    let mut runt = true;
+    breakpoints(p, |i, breakpoint| {
+        if i == mid {
+            mid_breakpoint = breakpoint;
+        } else if i == end {
+            end_breakpoint = breakpoint;
+        } else if breakpoint == Breakpoint::Normal {
+            if i > start && i < mid {
+                split = Some(i);
+            } else if i > mid && i < end {
+                runt = false;
+            }
+        }
+    });
+
+    // A last line after a forced break doesn't continue the preceding line, so
+    // there is nothing to balance.
+    let Some(split) = split else { return };
+    if !runt || mid_breakpoint == Breakpoint::Mandatory {
+        return;
+    }
+
+    let pred = if n > 2 { Some(&lines[n - 3].0) } else { None };
+    let first = line(engine, p, start..split, Breakpoint::Normal, pred);
+    let last = line(engine, p, split..end, end_breakpoint, Some(&first));
+    if !widths.get(n - 1).fits(last.width) {
+        return;
+    }
+
+    lines.truncate(n - 2);
+    lines.push((first, split));
+    lines.push((last, end));
+}
+
 /// Compute the stretch ratio and cost of a line.
 fn ratio_and_cost(
     p: &Preparation,
@@ -18,8 +18,8 @@ use typst_library::foundations::{Packed, Smart, StyleChain};
 use typst_library::introspection::{Introspector, Locator, LocatorLink, SplitLocator};
 use typst_library::layout::{Abs, AlignElem, Dir, FixedAlignment, Fragment, Size};
 use typst_library::model::{
-    EnumElem, FirstLineIndent, JustificationLimits, Linebreaks, ListElem, ParElem,
-    ParLine, ParLineMarker, TermsElem,
+    DocumentElem, EnumElem, FirstLineIndent, JustificationLimits, Linebreaks, ListElem,
+    ParElem, ParLine, ParLineMarker, TermsElem,
 };
 use typst_library::routines::{Arenas, Pair, RealizationKind};
 use typst_library::text::{Costs, Lang, TextElem};
@@ -30,7 +30,7 @@ use self::collect::{Item, Segment, SpanMapper, collect};
 use self::deco::decorate;
 use self::finalize::finalize;
 use self::line::{Line, apply_shift, commit, line};
-use self::linebreak::{Breakpoint, linebreak};
+use self::linebreak::{Breakpoint, LineWidths, linebreak};
 use self::prepare::{Preparation, prepare};
 use self::shaping::{
     BEGIN_PUNCT_PAT, END_PUNCT_PAT, ShapedGlyph, ShapedText, cjk_punct_style,
@@ -49,6 +49,7 @@ pub fn layout_par(
     region: Size,
     expand: bool,
     situation: ParSituation,
+    wrap: Option<ParWrap>,
 ) -> SourceResult<Fragment> {
     layout_par_impl(
         elem,
@@ -63,6 +64,7 @@ pub fn layout_par(
         region,
         expand,
         situation,
+        wrap,
     )
 }
 
@@ -82,6 +84,7 @@ fn layout_par_impl(
     region: Size,
     expand: bool,
     situation: ParSituation,
+    wrap: Option<ParWrap>,
 ) -> SourceResult<Fragment> {
     let introspector = Protected::from_raw(introspector);
     let link = LocatorLink::new(locator);
@@ -113,9 +116,11 @@ fn layout_par_impl(
         region,
         expand,
         Some(situation),
+        wrap,
         &ConfigBase {
             justify: elem.justify.get(styles),
             linebreaks: elem.linebreaks.get(styles),
+            balance_last_line: elem.balance_last_line.get(styles),
             first_line_indent: elem.first_line_indent.get(styles),
             hanging_indent: elem.hanging_indent.resolve(styles),
         },
@@ -139,9 +144,11 @@ pub fn layout_inline<'a>(
         region,
         expand,
         None,
+        None,
         &ConfigBase {
             justify: shared.get(ParElem::justify),
             linebreaks: shared.get(ParElem::linebreaks),
+            balance_last_line: shared.get(ParElem::balance_last_line),
             first_line_indent: shared.get(ParElem::first_line_indent),
             hanging_indent: shared.resolve(ParElem::hanging_indent),
         },
@@ -158,6 +165,7 @@ fn layout_inline_impl<'a>(
     region: Size,
     expand: bool,
     par: Option<ParSituation>,
+    wrap: Option<ParWrap>,
     base: &ConfigBase,
 ) -> SourceResult<Fragment> {
     // Prepare configuration that is shared across the whole inline layout.
@@ -170,11 +178,22 @@ fn layout_inline_impl<'a>(
     // proceed to line breaking.
     let p = prepare(engine, &config, &text, segments, spans)?;
 
-    // Break the text into lines.
-    let lines = linebreak(engine, &p, region.x - config.hanging_indent);
+    // Break the text into lines. Lines that run alongside a wrapping float or
+    // an excluded area are narrowed.
+    let width = region.x - config.hanging_indent;
+    let widths = match wrap {
+        Some(wrap) => LineWidths {
+            full: width,
+            narrow: width - wrap.width,
+            skip: wrap.skip,
+            narrowed: wrap.lines,
+        },
+        None => LineWidths::uniform(width),
+    };
+    let lines = linebreak(engine, &p, widths);
 
     // Turn the selected lines into frames.
-    finalize(engine, &p, &lines, region, expand, locator)
+    finalize(engine, &p, &lines, region, expand, wrap, locator)
 }
 
 /// Determine the inline layout's configuration.
@@ -194,6 +213,7 @@ fn configuration(
         linebreaks: base.linebreaks.unwrap_or_else(|| {
             if justify { Linebreaks::Optimized } else { Linebreaks::Simple }
         }),
+        balance_last_line: base.balance_last_line,
         first_line_indent: {
             let amount = base.first_line_indent.amount();
             let all = base.first_line_indent.all();
@@ -227,6 +247,10 @@ fn configuration(
                 // laid out to avoid inconsistent spacing depending on varying
                 // font size.
                 shared.get(ParLine::number_clearance),
+                shared.get_cloned(ParLine::number_lines),
+                shared.get(ParLine::number_step),
+                shared.get(ParLine::numbering_width),
+                shared.get(ParLine::number_pad),
             ))
         }),
         align: shared.get(AlignElem::alignment).fix(dir).x,
@@ -238,6 +262,7 @@ fn configuration(
         fallback: shared.get(TextElem::fallback),
         cjk_latin_spacing: shared.get(TextElem::cjk_latin_spacing).is_auto(),
         costs: shared.get(TextElem::costs),
+        draft: shared.get(DocumentElem::draft),
     }
 }
 
@@ -257,10 +282,34 @@ pub enum ParSituation {
     Other,
 }
 
+/// Describes how lines of a paragraph make room for a float that the paragraph
+/// wraps around or for an excluded area of the region.
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct ParWrap {
+    /// The number of lines at the start of the paragraph that keep the full
+    /// width before the narrowed ones.
+    pub skip: usize,
+    /// The number of lines that are narrowed.
+    pub lines: usize,
+    /// The amount by which these lines are narrowed.
+    pub width: Abs,
+    /// The side of the paragraph at which the float is, either `Start` (left)
+    /// or `End` (right).
+    pub side: FixedAlignment,
+}
+
+impl ParWrap {
+    /// Whether the line with the given index is narrowed.
+    pub fn narrows(&self, line: usize) -> bool {
+        (self.skip..self.skip + self.lines).contains(&line)
+    }
+}
+
 /// Raw values from a `ParElem` or style chain. Used to initialize a [`Config`].
 struct ConfigBase {
     justify: bool,
     linebreaks: Smart<Linebreaks>,
+    balance_last_line: bool,
     first_line_indent: FirstLineIndent,
     hanging_indent: Abs,
 }
@@ -273,6 +322,8 @@ struct Config {
     justification_limits: JustificationLimits,
     /// How to determine line breaks.
     linebreaks: Linebreaks,
+    /// Whether to avoid a last line with just a single word.
+    balance_last_line: bool,
     /// The indent the first line of a paragraph should have.
     first_line_indent: Abs,
     /// The indent that all but the first line of a paragraph should have.
@@ -297,6 +348,9 @@ struct Config {
     cjk_latin_spacing: bool,
     /// Costs for various layout decisions.
     costs: Costs,
+    /// Whether the document is compiled as a draft, in which case lines that
+    /// overflow the region are reported.
+    draft: bool,
 }
 
 /// Get a style property, but only if it is the same for all of the children.
@@ -3,7 +3,7 @@ use std::num::NonZeroUsize;
 
 use ecow::{EcoString, EcoVec};
 
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use typst_library::diag::StrResult;
 use typst_library::foundations::{Content, Label, Selector};
 use typst_library::introspection::{
@@ -24,6 +24,11 @@ pub struct PagedIntrospector {
     elements: ElementIntrospector<PagedPosition>,
     /// Locations that are linked to via `FrameItem::Link`.
     frame_link_targets: FxHashSet<Location>,
+    /// The outermost column that contains each location, for locations in a
+    /// multi-column flow.
+    columns: FxHashMap<Location, usize>,
+    /// The positions at which floating elements were finally placed.
+    placements: FxHashMap<Location, PagedPosition>,
     /// The number of pages in the document.
     pages: NonZeroUsize,
     /// The page numberings, indexed by page number minus 1.
@@ -45,9 +50,12 @@ impl PagedIntrospector {
             let nr = NonZeroUsize::new(1 + i).unwrap();
             page_numberings.push(page.numbering.clone());
             page_supplements.push(page.supplement.clone());
-            builder.discover_frame(&page.frame, Transform::identity(), &mut |point| {
-                PagedPosition { page: nr, point }
-            });
+            builder.discover_frame(
+                &page.frame,
+                Transform::identity(),
+                None,
+                &mut |point| PagedPosition { page: nr, point },
+            );
         }
 
         builder.finish(
@@ -119,6 +127,14 @@ impl Introspector for PagedIntrospector {
         self.elements.position(location).copied().map(DocumentPosition::Paged)
     }
 
+    fn column(&self, location: Location) -> Option<usize> {
+        self.columns.get(&location).copied()
+    }
+
+    fn placement(&self, location: Location) -> Option<DocumentPosition> {
+        self.placements.get(&location).copied().map(DocumentPosition::Paged)
+    }
+
     fn page_numbering(&self, location: Location) -> Option<&Numbering> {
         let page = self.page(location)?;
         self.page_numberings.get(page.get() - 1)?.as_ref()
@@ -153,6 +169,8 @@ impl Debug for PagedIntrospector {
 struct PagedIntrospectorBuilder {
     elements: ElementIntrospectorBuilder<PagedPosition>,
     frame_link_targets: FxHashSet<Location>,
+    columns: FxHashMap<Location, usize>,
+    placements: FxHashMap<Location, PagedPosition>,
 }
 
 impl PagedIntrospectorBuilder {
@@ -167,6 +185,8 @@ impl PagedIntrospectorBuilder {
         PagedIntrospector {
             elements: self.elements.finalize(),
             frame_link_targets: self.frame_link_targets,
+            columns: self.columns,
+            placements: self.placements,
             pages,
             page_numberings,
             page_supplements,
@@ -174,26 +194,42 @@ impl PagedIntrospectorBuilder {
     }
 
     /// Discovers introspectibles in a frame.
-    fn discover_frame<F>(&mut self, frame: &Frame, ts: Transform, to_pos: &mut F)
-    where
+    ///
+    /// The `column` is the outermost column of a multi-column flow that
+    /// contains the frame, if any.
+    fn discover_frame<F>(
+        &mut self,
+        frame: &Frame,
+        ts: Transform,
+        column: Option<usize>,
+        to_pos: &mut F,
+    ) where
         F: FnMut(Point) -> PagedPosition,
     {
         for (pos, item) in frame.items() {
             match item {
                 FrameItem::Tag(tag) => {
+                    if let Some(column) = column {
+                        self.columns.entry(tag.location()).or_insert(column);
+                    }
                     self.elements.discover_tag(tag, to_pos(pos.transform(ts)));
                 }
                 FrameItem::Group(group) => {
                     let ts = ts
                         .pre_concat(Transform::translate(pos.x, pos.y))
                         .pre_concat(group.transform);
+                    let column = column.or(group.column);
+
+                    if let Some(loc) = group.placement {
+                        self.placements.insert(loc, to_pos(Point::zero().transform(ts)));
+                    }
 
                     if let Some(parent) = group.parent {
                         self.elements.start_insertion();
-                        self.discover_frame(&group.frame, ts, to_pos);
+                        self.discover_frame(&group.frame, ts, column, to_pos);
                         self.elements.end_insertion(parent.location);
                     } else {
-                        self.discover_frame(&group.frame, ts, to_pos);
+                        self.discover_frame(&group.frame, ts, column, to_pos);
                     }
                 }
                 FrameItem::Link(dest, _) => {
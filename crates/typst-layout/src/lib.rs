@@ -22,3 +22,17 @@ pub use self::flow::{layout_fragment, layout_frame};
 pub use self::introspect::PagedIntrospector;
 pub use self::pages::{layout_document, layout_document_for_bundle};
 pub use self::rules::register;
+
+/// Internals of flow layout that the test suite exercises directly.
+///
+/// These are not part of the crate's public API and may change at any time.
+#[doc(hidden)]
+pub mod testing {
+    pub use crate::flow::{
+        ColumnOptions, FlowCursor, FlowMeasurement, FlowMode, FlowRegions, fit_to_region,
+        layout_cell_grid, layout_flow, layout_flow_columns, layout_flow_into,
+        layout_flow_shaped, layout_flow_synced, measure_flow, measure_intrinsic,
+    };
+    #[cfg(feature = "debug-flow")]
+    pub use crate::flow::{FlowEvent, flow_events};
+}
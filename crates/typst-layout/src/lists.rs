@@ -67,6 +67,7 @@ pub fn layout_list(
         body_indent,
         baseline_align,
         is_rtl,
+        elem.keep_items_together.get(styles),
         styles,
     );
 
@@ -162,6 +163,7 @@ pub fn layout_enum(
         body_indent,
         baseline_align,
         is_rtl,
+        false,
         styles,
     );
 
@@ -191,6 +193,9 @@ struct ListLayouter {
     baseline_align: bool,
     /// Whether RTL was the chosen text direction.
     is_rtl: bool,
+    /// Whether items should be moved to the next region as a whole instead of
+    /// being split, if they fit there.
+    keep_together: bool,
     /// Maximum measured width of a marker, so they may align horizontally
     /// relative to each other.
     marker_width: Abs,
@@ -210,6 +215,7 @@ impl ListLayouter {
         body_indent: Length,
         baseline_align: bool,
         is_rtl: bool,
+        keep_together: bool,
         styles: StyleChain,
     ) -> Self {
         let indent = indent.resolve(styles);
@@ -222,6 +228,7 @@ impl ListLayouter {
             body_indent,
             baseline_align,
             is_rtl,
+            keep_together,
 
             // These will be calculated later.
             marker_width: Abs::zero(),
@@ -295,6 +302,7 @@ fn layout_items(
         layouter.span,
         Some(layouter.gutter.into()),
         Dir::TTB,
+        layouter.keep_together,
         engine,
         // This locator should not be used by cells.
         locator.next(&()),
@@ -40,24 +40,6 @@ pub fn shrink(size: Size, inset: &Sides<Rel<Abs>>) -> Size {
     size - inset.sum_by_axis().relative_to(size)
 }
 
-/// Shrink the components of possibly multiple `Regions` by an inset relative to
-/// the regions themselves.
-pub fn shrink_multiple(
-    size: &mut Size,
-    full: &mut Abs,
-    backlog: &mut [Abs],
-    last: &mut Option<Abs>,
-    inset: &Sides<Rel<Abs>>,
-) {
-    let summed = inset.sum_by_axis();
-    *size -= summed.relative_to(*size);
-    *full -= summed.y.relative_to(*full);
-    for item in backlog {
-        *item -= summed.y.relative_to(*item);
-    }
-    *last = last.map(|v| v - summed.y.relative_to(v));
-}
-
 /// Grow a frame's size by an inset relative to the grown size.
 /// This is the inverse operation to `shrink()`.
 ///
@@ -4,18 +4,21 @@ mod collect;
 mod finalize;
 mod run;
 
+use std::num::NonZeroUsize;
+
 use comemo::{Track, Tracked, TrackedMut};
 use ecow::EcoVec;
-use typst_library::diag::SourceResult;
+use typst_library::diag::{SourceResult, bail};
 use typst_library::engine::{Engine, Route, Sink, Traced};
-use typst_library::foundations::{Content, StyleChain};
+use typst_library::foundations::{Content, NativeElement, StyleChain, Styles};
 use typst_library::introspection::{
     Introspector, Locator, LocatorLink, ManualPageCounter, SplitLocator, TagElem,
 };
-use typst_library::layout::{FrameItem, Point};
+use typst_library::layout::{FrameItem, PageElem, PageImposition, Point};
 use typst_library::model::DocumentInfo;
 use typst_library::routines::{Arenas, Pair, RealizationKind};
 use typst_library::{Library, World};
+use typst_syntax::Span;
 use typst_utils::{LazyHash, Protected};
 
 use self::collect::{Item, collect};
@@ -197,12 +200,14 @@ fn layout_pages<'a>(
     let mut pages = EcoVec::new();
     let mut tags = vec![];
     let mut counter = ManualPageCounter::new();
+    let mut last = None;
 
     // Collect and finalize the runs, handling things like page parity and tags
     // between pages.
     for item in &items {
         match item {
-            Item::Run(..) => {
+            Item::Run(children, initial, _) => {
+                last = Some((*children, *initial));
                 let layouted = runs.next().unwrap()?;
                 for layouted in layouted {
                     let page = finalize(engine, &mut counter, &mut tags, layouted)?;
@@ -229,6 +234,52 @@ fn layout_pages<'a>(
         }
     }
 
+    // Document-wide page properties are taken from the last page run.
+    let mut imposition = None;
+    if let Some((children, initial)) = last {
+        let root = Styles::root(children, initial);
+        let styles = StyleChain::new(&root);
+
+        // Pad the document with blank pages if requested. Saddle stitching
+        // needs a multiple of four pages. If both apply, the page count must
+        // be a multiple of both.
+        imposition = styles.get(PageElem::imposition);
+        let multiple = [
+            styles.get(PageElem::pad_to_multiple).map(NonZeroUsize::get),
+            imposition.map(|PageImposition::SaddleStitch| 4),
+        ]
+        .into_iter()
+        .flatten()
+        .fold(1, lcm);
+        while pages.len() % multiple != 0 {
+            let layouted = layout_blank_page(engine, locator.next(&()), styles)?;
+            let page = finalize(engine, &mut counter, &mut tags, layouted)?;
+            pages.push(page);
+        }
+
+        // Ensure that the document stays within its page budget.
+        if let Some(max) = styles.get(PageElem::max_pages)
+            && pages.len() > max.get()
+        {
+            // Point to the set rule that established the budget.
+            let span = styles
+                .entries()
+                .find(|style| {
+                    style.property().is_some_and(|property| {
+                        property.is(PageElem::ELEM, PageElem::max_pages.index())
+                    })
+                })
+                .map_or(Span::detached(), |style| style.span());
+            bail!(
+                span,
+                "document has {} pages, but at most {} are allowed",
+                pages.len(),
+                max;
+                hint: "try shortening the content or raising `max-pages`";
+            );
+        }
+    }
+
     // Add the remaining tags to the very end of the last page.
     if !tags.is_empty() {
         let last = pages.make_mut().last_mut().unwrap();
@@ -237,5 +288,39 @@ fn layout_pages<'a>(
             .push_multiple(tags.into_iter().map(|tag| (pos, FrameItem::Tag(tag))));
     }
 
+    // Reorder the finished pages for printing. This happens last such that
+    // everything above sees the pages in reading order.
+    if let Some(imposition) = imposition {
+        pages = impose(&pages, imposition);
+    }
+
     Ok(pages)
 }
+
+/// Reorders finished pages according to an imposition scheme.
+fn impose(pages: &[Page], imposition: PageImposition) -> EcoVec<Page> {
+    match imposition {
+        PageImposition::SaddleStitch => {
+            // Each sheet holds two pages from the back and two from the front
+            // of the document. The page count is a multiple of four thanks to
+            // padding.
+            let n = pages.len();
+            (0..n / 4)
+                .flat_map(|sheet| {
+                    let (front, back) = (2 * sheet, n - 1 - 2 * sheet);
+                    [back, front, front + 1, back - 1]
+                })
+                .map(|i| pages[i].clone())
+                .collect()
+        }
+    }
+}
+
+/// The least common multiple of two positive numbers.
+fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
@@ -1,3 +1,5 @@
+use std::num::NonZeroUsize;
+
 use comemo::{Track, Tracked, TrackedMut};
 use typst_library::diag::SourceResult;
 use typst_library::engine::{Engine, Route, Sink, Traced};
@@ -18,7 +20,7 @@ use typst_library::routines::Pair;
 use typst_library::text::{LocalName, TextElem};
 use typst_library::visualize::Paint;
 use typst_library::{Library, World};
-use typst_utils::{LazyHash, Numeric, Protected};
+use typst_utils::{LazyHash, NonZeroExt, Numeric, Protected};
 
 use crate::flow::{ColumnOptions, FlowMode, layout_flow};
 
@@ -112,6 +114,12 @@ fn layout_page_run_impl(
         std::mem::swap(&mut size.x, &mut size.y);
     }
 
+    // A continuous page grows with its content.
+    let continuous = styles.get(PageElem::continuous);
+    if continuous {
+        size.y = Abs::inf();
+    }
+
     let mut min = width.min(height);
     if !min.is_finite() {
         min = Paper::A4.width();
@@ -194,9 +202,22 @@ fn layout_page_run_impl(
         styles,
         Regions::repeat(area, area.map(Abs::is_finite)),
         ColumnOptions {
-            count: styles.get(PageElem::columns),
+            count: if continuous {
+                NonZeroUsize::ONE
+            } else {
+                styles.get(PageElem::columns)
+            },
             balanced: styles.get(ColumnsElem::balanced),
             gutter: styles.get(ColumnsElem::gutter).resolve(styles),
+            sync_baselines: styles.get(ColumnsElem::sync_baselines),
+            lines_per_column: styles.get(ColumnsElem::lines_per_column),
+            first_inset: styles.resolve(PageElem::first_inset),
+            fill: styles.get_cloned(ColumnsElem::fill),
+            gutter_fill: styles.get_cloned(ColumnsElem::gutter_fill),
+            gutter_fill_extent: styles.get(ColumnsElem::gutter_fill_extent),
+            progression: styles.get(ColumnsElem::progression).0,
+            reserve_bottom: Abs::zero(),
+            reserved: None,
         },
         FlowMode::Root,
     )?;
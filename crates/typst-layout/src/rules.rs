@@ -1,18 +1,20 @@
 use comemo::Track;
 use ecow::{EcoVec, eco_format};
 use smallvec::smallvec;
-use typst_library::diag::{At, SourceResult, bail};
+use typst_library::diag::{At, SourceResult, bail, warning};
+use typst_library::engine::Engine;
 use typst_library::foundations::{
     Content, Context, NativeElement, NativeRuleMap, Packed, Resolve, ShowFn, Smart,
     StyleChain, Synthesize, Target, dict,
 };
 use typst_library::introspection::{Counter, Locator, LocatorLink};
 use typst_library::layout::{
-    Abs, AlignElem, Alignment, Axes, BlockBody, BlockElem, ColumnsElem, Em,
-    FixedAlignment, GridCell, GridChild, GridElem, GridItem, HAlignment, HElem, HideElem,
-    InlineElem, LayoutElem, Length, MoveElem, OuterVAlignment, PadElem, PageElem,
-    PlaceElem, PlacementScope, Region, Rel, RepeatElem, RotateElem, ScaleElem, Sides,
-    Size, Sizing, SkewElem, Spacing, StackChild, StackElem, TrackSizings, VElem,
+    Abs, AlignElem, Alignment, Axes, BlockBody, BlockElem, BoxElem, ColumnsElem, Em,
+    FixedAlignment, Frame, GridCell, GridChild, GridElem, GridItem, HAlignment, HElem,
+    HideElem, InlineElem, KeepBoundary, KeepElem, LayoutElem, Length, MoveElem,
+    OuterVAlignment, PadElem, PageElem, PlaceElem, PlacementScope, Region, Rel,
+    RepeatElem, RotateElem, ScaleElem, Sides, Size, Sizing, SkewElem, Spacing,
+    StackChild, StackElem, TrackSizings, VElem,
 };
 use typst_library::math::EquationElem;
 use typst_library::model::{
@@ -90,6 +92,7 @@ pub fn register(rules: &mut NativeRuleMap) {
     rules.register(Paged, SKEW_RULE);
     rules.register(Paged, REPEAT_RULE);
     rules.register(Paged, HIDE_RULE);
+    rules.register(Paged, KEEP_RULE);
     rules.register(Paged, LAYOUT_RULE);
 
     // Visualize.
@@ -300,29 +303,15 @@ const HEADING_RULE: ShowFn<HeadingElem> = |elem, engine, styles| {
 
 const FIGURE_RULE: ShowFn<FigureElem> = |elem, _, styles| {
     let span = elem.span();
-    let mut realized = elem.body.clone();
 
-    // Build the caption, if any.
-    if let Some(caption) = elem.caption.get_cloned(styles) {
-        let (first, second) = match caption.position.get(styles) {
-            OuterVAlignment::Top => (caption.pack(), realized),
-            OuterVAlignment::Bottom => (realized, caption.pack()),
-        };
-        realized = Content::sequence(vec![
-            first,
-            VElem::new(elem.gap.get(styles).into())
-                .with_weak(true)
-                .pack()
-                .spanned(span),
-            second,
-        ]);
+    // Wrap the contents in a block. A figure that is kept together is laid
+    // out as a single unit, even if the block would be breakable.
+    let mut realized = if elem.keep_together.get(styles) {
+        BlockElem::single_layouter(elem.clone(), layout_kept_figure).pack()
+    } else {
+        BlockElem::packed(figure_contents(elem, styles))
     }
-
-    // Ensure that the body is considered a paragraph.
-    realized += ParbreakElem::shared().clone().spanned(span);
-
-    // Wrap the contents in a block.
-    realized = BlockElem::packed(realized).spanned(span);
+    .spanned(span);
 
     // Wrap in a float.
     if let Some(align) = elem.placement.get(styles) {
@@ -343,6 +332,51 @@ const FIGURE_RULE: ShowFn<FigureElem> = |elem, _, styles| {
     Ok(realized)
 };
 
+/// Assembles the body and caption of a figure.
+fn figure_contents(elem: &Packed<FigureElem>, styles: StyleChain) -> Content {
+    let span = elem.span();
+    let mut realized = elem.body.clone();
+
+    // Build the caption, if any.
+    if let Some(caption) = elem.caption.get_cloned(styles) {
+        let (first, second) = match caption.position.get(styles) {
+            OuterVAlignment::Top => (caption.pack(), realized),
+            OuterVAlignment::Bottom => (realized, caption.pack()),
+        };
+        realized = Content::sequence(vec![
+            first,
+            VElem::new(elem.gap.get(styles).into())
+                .with_weak(true)
+                .pack()
+                .spanned(span),
+            second,
+        ]);
+    }
+
+    // Ensure that the body is considered a paragraph.
+    realized + ParbreakElem::shared().clone().spanned(span)
+}
+
+/// Lays out a figure whose body and caption are kept together.
+fn layout_kept_figure(
+    elem: &Packed<FigureElem>,
+    engine: &mut Engine,
+    locator: Locator,
+    styles: StyleChain,
+    region: Region,
+) -> SourceResult<Frame> {
+    let contents = figure_contents(elem, styles);
+    let frame = crate::layout_frame(engine, &contents, locator, styles, region)?;
+    if region.size.y.is_finite() && frame.height() > region.size.y {
+        engine.sink.warn(warning!(
+            elem.span(),
+            "figure does not fit into a region even though it is kept together";
+            hint: "try making the figure smaller or disabling `keep-together`";
+        ));
+    }
+    Ok(frame)
+}
+
 const FIGURE_CAPTION_RULE: ShowFn<FigureCaption> =
     |elem, engine, styles| Ok(BlockElem::packed(elem.realize(engine, styles)?));
 
@@ -383,6 +417,11 @@ const QUOTE_RULE: ShowFn<QuoteElem> = |elem, _, styles| {
 };
 
 const FOOTNOTE_RULE: ShowFn<FootnoteElem> = |elem, engine, styles| {
+    // An unnumbered footnote has no marker, only its entry.
+    if !elem.is_ref() && !elem.numbered.get(styles) {
+        return Ok(Content::empty());
+    }
+
     // The footnote number that links to the footnote entry.
     let link = elem.realize(engine, styles)?;
     let sup = SuperElem::new(link).pack().spanned(elem.span());
@@ -391,12 +430,23 @@ const FOOTNOTE_RULE: ShowFn<FootnoteElem> = |elem, engine, styles| {
 
 const FOOTNOTE_ENTRY_RULE: ShowFn<FootnoteEntry> = |elem, engine, styles| {
     let number_gap = Em::new(0.05);
-    let (sup, body) = elem.realize(engine, styles)?;
+    let (mut sup, body) = elem.realize(engine, styles)?;
+    if let Some(width) = elem.number_width.get(styles) {
+        let align = Alignment::H(elem.number_align.get(styles));
+        sup = BoxElem::new()
+            .with_width(Sizing::Rel(width.into()))
+            .with_body(Some(sup.aligned(align)))
+            .pack();
+    }
     let prefix = PdfMarkerTag::Label(sup);
+    let separator = elem
+        .separator_between_number_and_text
+        .get_cloned(styles)
+        .unwrap_or_else(|| HElem::new(number_gap.into()).with_weak(true).pack());
     Ok(Content::sequence([
         HElem::new(elem.indent.get(styles).into()).pack(),
         prefix,
-        HElem::new(number_gap.into()).with_weak(true).pack(),
+        separator,
         body,
     ]))
 };
@@ -736,6 +786,15 @@ const REPEAT_RULE: ShowFn<RepeatElem> = |elem, _, _| {
 const HIDE_RULE: ShowFn<HideElem> =
     |elem, _, _| Ok(elem.body.clone().set(HideElem::hidden, true));
 
+const KEEP_RULE: ShowFn<KeepElem> = |elem, _, _| {
+    let span = elem.span();
+    Ok(Content::sequence([
+        KeepBoundary::new(true).pack().spanned(span),
+        elem.body.clone(),
+        KeepBoundary::new(false).pack().spanned(span),
+    ]))
+};
+
 const LAYOUT_RULE: ShowFn<LayoutElem> = |elem, _, _| {
     Ok(BlockElem::multi_layouter(
         elem.clone(),
@@ -23,6 +23,7 @@ pub fn layout_stack(
         elem.span(),
         elem.spacing.get(styles),
         elem.dir.get(styles),
+        false,
         engine,
         locator,
         styles,
@@ -64,12 +65,16 @@ where
 /// more deeply, such as for lists, which need a custom layout function that
 /// might borrow data from the environment for each list item (a stack child).
 /// Each child receives relevant layout data from the stack as well.
+///
+/// If `keep_together` is set, children with a custom layouter are moved to the
+/// next region as a whole instead of being split if they fit there.
 #[expect(clippy::too_many_arguments)]
 pub fn layout_stack_internal<'a, F>(
     children: impl IntoIterator<Item = StackLayoutChild<'a, F>>,
     span: Span,
     spacing: Option<Spacing>,
     dir: Dir,
+    keep_together: bool,
     engine: &mut Engine,
     locator: Locator,
     styles: StyleChain,
@@ -115,7 +120,12 @@ where
                     layouter.layout_spacing(kind);
                 }
 
-                layouter.layout_custom_layouter(engine, custom_layouter, styles)?;
+                layouter.layout_custom_layouter(
+                    engine,
+                    custom_layouter,
+                    keep_together,
+                    styles,
+                )?;
                 deferred = spacing;
             }
         }
@@ -254,6 +264,7 @@ impl<'a> StackLayouter<'a> {
         &mut self,
         engine: &mut Engine,
         layouter: impl Fn(&mut Engine, StyleChain, Regions) -> SourceResult<Fragment>,
+        keep_together: bool,
         styles: StyleChain,
     ) -> SourceResult<()> {
         if self.regions.is_full() {
@@ -262,7 +273,25 @@ impl<'a> StackLayouter<'a> {
 
         let align = styles.get(AlignElem::alignment).resolve(styles);
 
-        let fragment = layouter(engine, styles, self.regions)?;
+        let mut fragment = layouter(engine, styles, self.regions)?;
+
+        // If the child would be split, try to move it to the next region as a
+        // whole. This only helps if there is already something in the current
+        // region. If the child doesn't fit into the next region either, we
+        // keep it split where it is.
+        if keep_together
+            && fragment.len() > 1
+            && self.regions.may_progress()
+            && self.items.iter().any(|item| matches!(item, StackItem::Frame(..)))
+        {
+            let mut next = self.regions;
+            next.next();
+            let moved = layouter(engine, styles, next)?;
+            if moved.len() == 1 {
+                self.finish_region()?;
+                fragment = moved;
+            }
+        }
 
         self.layout_fragment(align, fragment)
     }
@@ -3,7 +3,7 @@
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 use comemo::{Track, Tracked, TrackedMut};
-use ecow::EcoVec;
+use ecow::{EcoString, EcoVec};
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 use rustc_hash::FxHashSet;
 use typst_syntax::{FileId, Span};
@@ -95,12 +95,45 @@ impl Engine<'_> {
                 sink.delayed,
                 sink.warnings,
                 sink.values,
+                sink.dumps,
             );
         }
 
         pairs.into_iter().map(|(output, _)| output)
     }
 
+    /// Runs a throw-away computation on the engine, e.g. a trial layout whose
+    /// result only informs a decision and doesn't end up in the document.
+    ///
+    /// Warnings, delayed errors, and traced values of the computation are
+    /// discarded. Introspections are still recorded because the decision
+    /// depends on them.
+    pub fn trial<T>(&mut self, f: impl FnOnce(&mut Engine) -> T) -> T {
+        let Engine {
+            world, introspector, traced, ref route, library, ..
+        } = *self;
+
+        let mut sink = Sink::new();
+        let output = f(&mut Engine {
+            world,
+            introspector,
+            traced,
+            sink: sink.track_mut(),
+            route: route.clone(),
+            library,
+        });
+
+        self.sink.extend(
+            sink.introspections,
+            EcoVec::new(),
+            EcoVec::new(),
+            EcoVec::new(),
+            EcoVec::new(),
+        );
+
+        output
+    }
+
     /// Performs an introspection on the introspector and returns its result.
     ///
     /// As a side effect, the introspection is stored in the sink. If the
@@ -164,6 +197,10 @@ pub struct Sink {
     warnings_set: FxHashSet<u128>,
     /// A sequence of traced values for a span.
     values: EcoVec<(Value, Option<Styles>)>,
+    /// Structured dumps of internal layout state, serialized as JSON. These
+    /// are only produced if the layout engine was built with debugging
+    /// support.
+    dumps: EcoVec<EcoString>,
 }
 
 impl Sink {
@@ -195,9 +232,20 @@ impl Sink {
         self.values
     }
 
+    /// Get the recorded layout dumps.
+    pub fn dumps(&self) -> &[EcoString] {
+        &self.dumps
+    }
+
     /// Extend from another sink.
     pub fn extend_from_sink(&mut self, other: Sink) {
-        self.extend(other.introspections, other.delayed, other.warnings, other.values);
+        self.extend(
+            other.introspections,
+            other.delayed,
+            other.warnings,
+            other.values,
+            other.dumps,
+        );
     }
 }
 
@@ -234,6 +282,11 @@ impl Sink {
         }
     }
 
+    /// Record a structured dump of internal layout state.
+    pub fn dump(&mut self, dump: EcoString) {
+        self.dumps.push(dump);
+    }
+
     /// Extend from parts of another sink.
     fn extend(
         &mut self,
@@ -241,6 +294,7 @@ impl Sink {
         delayed: EcoVec<SourceDiagnostic>,
         warnings: EcoVec<SourceDiagnostic>,
         values: EcoVec<(Value, Option<Styles>)>,
+        dumps: EcoVec<EcoString>,
     ) {
         self.introspections.extend(introspections);
         self.delayed.extend(delayed);
@@ -250,6 +304,7 @@ impl Sink {
         if let Some(remaining) = Self::MAX_VALUES.checked_sub(self.values.len()) {
             self.values.extend(values.into_iter().take(remaining));
         }
+        self.dumps.extend(dumps);
     }
 }
 
@@ -21,7 +21,10 @@ use crate::introspection::{
 };
 use crate::layout::{Frame, FrameItem, PageElem};
 use crate::math::EquationElem;
-use crate::model::{FigureElem, FootnoteElem, HeadingElem, Numbering, NumberingPattern};
+use crate::model::{
+    FigureElem, FootnoteElem, HeadingElem, Numbering, NumberingPattern,
+    display_in_section, section_state,
+};
 use crate::{Library, World};
 
 /// Counts through pages, elements, and more.
@@ -283,6 +286,29 @@ impl Counter {
             .display())
     }
 
+    /// Whether this is the footnote counter and footnotes are numbered per
+    /// section at the given location.
+    fn per_section(
+        &self,
+        engine: &mut Engine,
+        context: Tracked<Context>,
+        loc: Location,
+        span: Span,
+    ) -> bool {
+        let CounterKey::Selector(Selector::Elem(func, _)) = self.0 else { return false };
+        if func != FootnoteElem::ELEM {
+            return false;
+        }
+
+        engine
+            .introspect(QueryFirstIntrospection(Selector::Location(loc), span))
+            .and_then(|content| {
+                *content.to_packed::<FootnoteElem>()?.per_section.as_option()
+            })
+            .or_else(|| Some(context.styles().ok()?.get(FootnoteElem::per_section)))
+            .unwrap_or(false)
+    }
+
     /// Resolves the numbering for this counter track.
     ///
     /// This coupling between the counter type and the remaining standard
@@ -431,6 +457,7 @@ impl Counter {
             engine.introspect(CounterAtIntrospection(self.clone(), location, span))?
         };
 
+        let custom = numbering.is_custom();
         let numbering = numbering
             .custom()
             .or_else(|| {
@@ -438,12 +465,29 @@ impl Counter {
             })
             .unwrap_or_else(|| NumberingPattern::from_str("1.1").unwrap().into());
 
-        if at.is_custom() {
-            let context = Context::new(Some(location), context.styles().ok());
-            state.display(engine, context.track(), span, &numbering)
-        } else {
-            state.display(engine, context, span, &numbering)
+        let located = at
+            .is_custom()
+            .then(|| Context::new(Some(location), context.styles().ok()));
+        let context = match &located {
+            Some(located) => located.track(),
+            None => context,
+        };
+
+        // Per-section footnote numbers are counted from the start of the
+        // section. An explicit numbering receives the section's number along
+        // with them.
+        if !both && self.per_section(engine, context, location, span) {
+            let (section, number) = section_state(engine, location, span, &state)?;
+            return if custom {
+                let numbers =
+                    if section == 0 { vec![number] } else { vec![section, number] };
+                numbering.apply(engine, context, span, &numbers)
+            } else {
+                display_in_section(engine, context, span, &numbering, section, number)
+            };
         }
+
+        state.display(engine, context, span, &numbering)
     }
 
     /// Retrieves the value of the counter at the given location. Always returns
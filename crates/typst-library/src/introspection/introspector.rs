@@ -67,6 +67,14 @@ pub trait Introspector: Send + Sync {
     /// Returns the position for the given location.
     fn position(&self, location: Location) -> Option<DocumentPosition>;
 
+    /// Returns the index of the outermost column of a multi-column flow that
+    /// contains the given location, if any.
+    fn column(&self, location: Location) -> Option<usize>;
+
+    /// Returns the position at which the floating element with the given
+    /// location was finally placed, if it is a float.
+    fn placement(&self, location: Location) -> Option<DocumentPosition>;
+
     /// Returns the page numbering for the given location, if any.
     fn page_numbering(&self, location: Location) -> Option<&Numbering>;
 
@@ -142,6 +150,14 @@ impl Introspector for EmptyIntrospector {
         None
     }
 
+    fn column(&self, _: Location) -> Option<usize> {
+        None
+    }
+
+    fn placement(&self, _: Location) -> Option<DocumentPosition> {
+        None
+    }
+
     fn page_numbering(&self, _: Location) -> Option<&Numbering> {
         None
     }
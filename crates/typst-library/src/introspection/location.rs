@@ -109,6 +109,34 @@ impl Location {
         engine.introspect(PositionIntrospection(self, span))
     }
 
+    /// Returns a dictionary with the page number and the x, y position at
+    /// which a floating element was finally placed, in the same format as
+    /// `position()`.
+    ///
+    /// The position of a float itself is where it appears in the flow, which
+    /// may be on an earlier page than the float if it was deferred for lack of
+    /// space. This method instead returns the top-left corner of the float's
+    /// placed content. For any other element, including floats that wrap text
+    /// and thus stay in the flow, it is the same as `position()`.
+    ///
+    /// Together with a query for floats, this can be used to build a list of
+    /// figures or other floats with accurate page numbers.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #context for float in query(place.where(float: true)) {
+    ///   let pos = float.location().placement()
+    ///   [Float on page #pos.page \ ]
+    /// }
+    ///
+    /// #lorem(10)
+    /// #place(top, float: true, rect(height: 90pt))
+    /// ```
+    #[func(since = "0.16.0")]
+    pub fn placement(self, engine: &mut Engine, span: Span) -> PagedPosition {
+        engine.introspect(PlacementIntrospection(self, span))
+    }
+
     /// Returns the page numbering pattern of the page at this location. This
     /// can be used when displaying the page counter in order to obtain the
     /// local numbering. This is useful if you are building custom indices or
@@ -203,6 +231,47 @@ impl Introspect for PositionIntrospection {
     }
 }
 
+/// Retrieves the position at which a floating element was finally placed.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct PlacementIntrospection(pub Location, pub Span);
+
+impl Introspect for PlacementIntrospection {
+    type Output = PagedPosition;
+
+    fn introspect(
+        &self,
+        _: &mut Engine,
+        introspector: Tracked<dyn Introspector + '_>,
+    ) -> Self::Output {
+        match introspector
+            .placement(self.0)
+            .or_else(|| introspector.position(self.0))
+        {
+            Some(DocumentPosition::Paged(pos)) => pos,
+            Some(DocumentPosition::Html(_)) | None => PagedPosition::ORIGIN,
+        }
+    }
+
+    fn diagnose(&self, history: &History<Self::Output>) -> SourceDiagnostic {
+        format_convergence_warning(
+            self.0,
+            self.1,
+            history,
+            "placements",
+            |element| eco_format!("{element} placement"),
+            |pos| {
+                let coord = |v: Abs| repr::format_float(v.to_pt(), Some(0), false, "pt");
+                eco_format!(
+                    "page {} at ({}, {})",
+                    pos.page,
+                    coord(pos.point.x),
+                    coord(pos.point.y)
+                )
+            },
+        )
+    }
+}
+
 /// Retrieves the number of the page where an element is located.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub struct PageIntrospection(pub Location, pub Span);
@@ -2,11 +2,11 @@ use comemo::Tracked;
 use ecow::{EcoString, EcoVec, eco_format};
 use typst_syntax::Span;
 
-use super::{History, Introspect};
+use super::{DocumentPosition, History, Introspect};
 use crate::diag::{HintedStrResult, SourceDiagnostic, StrResult, warning};
 use crate::engine::Engine;
 use crate::foundations::{
-    Array, Content, Context, Label, LocatableSelector, Repr, Selector, Value, func,
+    Array, Cast, Content, Context, Label, LocatableSelector, Repr, Selector, Value, func,
 };
 use crate::introspection::Introspector;
 
@@ -70,6 +70,41 @@ use crate::introspection::Introspector;
 /// @counter:page-counter[page counter] at that location and apply the numbering
 /// to the counter.
 ///
+/// = Result order <result-order>
+/// By default, `query` returns the matching elements in document order, i.e.
+/// the order in which they appear in your markup. This order is stable and
+/// does not depend on layout.
+///
+/// When laid out, the elements may be read in a different order. For example,
+/// a floating figure is read at the top or bottom of its page, and with
+/// multiple columns, everything in the first column is read before anything
+/// in the second one. By passing `{order: "reading"}`, the results are instead
+/// returned in this reading order: by page first, then by the outermost
+/// column, and finally from top to bottom and left to right within the column.
+/// Elements that aren't inside of a column, like parent-scoped floats or page
+/// headers, are ordered as if they were in the first column. Elements at the
+/// exact same position keep their document order. In HTML export, there is no
+/// layout, so the reading order is the same as the document order.
+///
+/// #example(
+///   ```
+///   #set page(columns: 2, height: 120pt)
+///   = Introduction
+///   #place(
+///     top,
+///     float: true,
+///     scope: "parent",
+///     heading(outlined: false)[Title],
+///   )
+///   #colbreak()
+///   = Methods
+///
+///   #context query(heading, order: "reading")
+///     .map(it => it.body)
+///     .join[, ]
+///   ```
+/// )
+///
 /// = #short-or-long[Caution][A word of caution] <caution>
 /// To resolve all your queries, Typst evaluates and layouts parts of the
 /// document multiple times. However, there is no guarantee that your queries
@@ -169,12 +204,32 @@ pub fn query(
     ///
     /// Only @location:locatable[locatable] element functions are supported.
     target: LocatableSelector,
+    /// The order in which to return the matching elements. See the
+    /// @query:result-order[section on result order] for details.
+    #[named]
+    #[default]
+    order: QueryOrder,
 ) -> HintedStrResult<Array> {
     context.introspect()?;
-    let vec = engine.introspect(QueryIntrospection(target.0, span));
+    let vec = match order {
+        QueryOrder::Document => engine.introspect(QueryIntrospection(target.0, span)),
+        QueryOrder::Reading => {
+            engine.introspect(QueryReadingIntrospection(target.0, span))
+        }
+    };
     Ok(vec.into_iter().map(Value::Content).collect())
 }
 
+/// The order in which `query` returns its results.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum QueryOrder {
+    /// The order in which the elements appear in the document.
+    #[default]
+    Document,
+    /// The order in which the elements are read in the laid-out document.
+    Reading,
+}
+
 /// Retrieves all matches of a selector in the document.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub struct QueryIntrospection(pub Selector, pub Span);
@@ -202,6 +257,39 @@ impl Introspect for QueryIntrospection {
     }
 }
 
+/// Retrieves all matches of a selector in the document, in reading order.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct QueryReadingIntrospection(pub Selector, pub Span);
+
+impl Introspect for QueryReadingIntrospection {
+    type Output = EcoVec<Content>;
+
+    fn introspect(
+        &self,
+        _: &mut Engine,
+        introspector: Tracked<dyn Introspector + '_>,
+    ) -> Self::Output {
+        let mut vec = introspector.query(&self.0);
+        vec.make_mut().sort_by_cached_key(|elem| {
+            // Elements without a paged position keep their document order
+            // at the end.
+            let loc = elem.location().unwrap();
+            introspector
+                .position(loc)
+                .and_then(DocumentPosition::as_paged)
+                .map_or((true, None), |pos| {
+                    let column = introspector.column(loc).unwrap_or(0);
+                    (false, Some((pos.page, column, pos.point.y, pos.point.x)))
+                })
+        });
+        vec
+    }
+
+    fn diagnose(&self, history: &History<Self::Output>) -> SourceDiagnostic {
+        QueryIntrospection(self.0.clone(), self.1).diagnose(history)
+    }
+}
+
 /// Retrieves the first match of a selector in the document.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub struct QueryFirstIntrospection(pub Selector, pub Span);
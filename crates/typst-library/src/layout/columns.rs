@@ -1,7 +1,18 @@
 use std::num::NonZeroUsize;
 
-use crate::foundations::{Content, elem};
-use crate::layout::{Length, Ratio, Rel};
+use comemo::Tracked;
+use typst_syntax::Span;
+use typst_utils::NonZeroExt;
+
+use crate::diag::{At, SourceResult, bail};
+use crate::engine::Engine;
+use crate::foundations::{
+    Args, Cast, Construct, Content, Context, Dict, IntoValue, NativeElement, Resolve,
+    StyleChain, cast, dict, elem, func, scope,
+};
+use crate::introspection::{PositionIntrospection, QueryIntrospection};
+use crate::layout::{Abs, Axis, Celled, Dir, Length, Ratio, Rel};
+use crate::visualize::Paint;
 
 /// Separates a region into multiple equally sized columns.
 ///
@@ -55,7 +66,7 @@ use crate::layout::{Length, Ratio, Rel};
 ///   #lorem(40)
 ///   ```
 /// )
-#[elem(since = "forever")]
+#[elem(scope, since = "forever")]
 pub struct ColumnsElem {
     /// The number of columns.
     #[positional]
@@ -72,11 +83,100 @@ pub struct ColumnsElem {
     /// #lorem(30)
     /// ```
     /// )
-    #[default(Ratio::new(0.04).into())]
-    pub gutter: Rel<Length>,
+    ///
+    /// For symmetric layouts, the gaps next to the first and last column can
+    /// be sized differently than the ones in between by passing a dictionary
+    /// with the keys `outer` and `inner`. The gaps after the first and before
+    /// the last column use `outer`, all others use `inner`. With two or three
+    /// columns, all gaps are thus outer gaps.
+    ///
+    /// #example(
+    /// ```
+    /// #set page(columns: 4, height: 4cm)
+    /// #set columns(
+    ///   gutter: (outer: 12pt, inner: 4pt),
+    ///   fill: aqua.lighten(60%),
+    /// )
+    /// #lorem(30)
+    /// ```
+    /// )
+    #[default(ColumnGutter::splat(Ratio::new(0.04).into()))]
+    pub gutter: ColumnGutter,
+
+    /// How to fill the background of the columns.
+    ///
+    /// This can be:
+    /// - a single paint for all columns
+    /// - an array of paints corresponding to each column, which is cycled
+    ///   through if there are more columns than paints
+    /// - a function that maps the index of a column and the index of the
+    ///   region (e.g. the page) the column is in to a paint
+    ///
+    /// The fill spans the full width and height of each column, but not the
+    /// gutter. Like other column settings, this also applies to page-level
+    /// columns.
+    ///
+    /// #example(
+    /// ```
+    /// #set page(height: 4cm)
+    /// #columns(2, fill: (luma(230), none))[
+    ///   #lorem(20)
+    /// ]
+    /// ```
+    /// )
+    pub fill: Celled<Option<Paint>>,
+
+    /// Content that is repeated vertically to fill each gutter between the
+    /// columns, like a dotted line or a row of ornaments.
+    ///
+    /// The content is laid out with the width of the gutter and then tiled
+    /// from the top of the columns to their bottom. The last tile is clipped
+    /// at the bottom of the columns if it doesn't fit fully. Like the
+    /// @columns.fill[`fill`], this also applies to page-level columns.
+    ///
+    /// #example(
+    /// ```
+    /// #set page(height: 4cm)
+    /// #columns(
+    ///   2,
+    ///   gutter-fill: align(center, text(gray)[·]),
+    /// )[
+    ///   #lorem(40)
+    /// ]
+    /// ```
+    /// )
+    pub gutter_fill: Option<Content>,
+
+    /// How far the @columns.gutter-fill[gutter fill] extends vertically.
+    ///
+    /// - `{"full"}`: The fill spans the full height of the columns.
+    /// - `{"content-overlap"}`: The fill only spans the height that both
+    ///   columns next to the gutter fill with content. This omits it next to
+    ///   a short last column or next to an empty column.
+    ///
+    /// #example(
+    /// ```
+    /// #set page(height: 4cm)
+    /// #columns(
+    ///   2,
+    ///   gutter-fill: align(center, line(angle: 90deg, length: 1em)),
+    ///   gutter-fill-extent: "content-overlap",
+    /// )[
+    ///   #lorem(30)
+    ///   #colbreak()
+    ///   #lorem(8)
+    /// ]
+    /// ```
+    /// )
+    pub gutter_fill_extent: GutterFillExtent,
 
     /// Whether to equalize the height of columns by breaking columns early.
     ///
+    /// Only the columns of the last region, such as the last page of the
+    /// columns, are balanced. Columns in all preceding regions are filled up
+    /// naturally, as they would be without balancing. Only the last region is
+    /// thus laid out a second time to find the balanced height.
+    ///
     /// #example(
     /// ```
     /// #set page(columns: 2, height: 5cm)
@@ -89,11 +189,211 @@ pub struct ColumnsElem {
     #[default(false)]
     pub balanced: bool,
 
+    /// Whether to align the baselines of text lines across columns.
+    ///
+    /// When enabled, the lines of paragraphs are snapped onto a baseline grid
+    /// that is shared by all columns. The distance between two lines of the
+    /// grid is derived from the first line of text in the columns and the
+    /// paragraph @par.leading[leading].
+    ///
+    /// Elements that don't fit the grid, such as figures, headings, or
+    /// block-level equations, are not snapped themselves. Instead, the line
+    /// following them is moved down onto the next line of the grid. Only the
+    /// lines of top-level paragraphs are snapped; lines within lists, tables,
+    /// or other blocks keep their natural position. Fractional spacing and
+    /// bottom-aligned content shift lines after snapping and thus also break
+    /// the grid.
+    ///
+    /// #example(
+    /// ```
+    /// #set page(columns: 2, height: 5cm)
+    /// #set columns(sync-baselines: true)
+    /// #lorem(10)
+    ///
+    /// #text(1.4em)[Interlude]
+    ///
+    /// #lorem(20)
+    /// ```
+    /// )
+    #[default(false)]
+    pub sync_baselines: bool,
+
+    /// The maximum number of paragraph lines in each column.
+    ///
+    /// When set, a column is ended once it holds this many lines, even if
+    /// there would be space for more. This is useful for setting verse by line
+    /// count. Columns still end earlier if they run out of space. Only the
+    /// lines of top-level paragraphs are counted; other content like headings
+    /// or blocks is placed as usual, but doesn't count towards the limit.
+    ///
+    /// #example(
+    /// ```
+    /// #set page(columns: 2, height: 5cm)
+    /// #set columns(lines-per-column: 4)
+    /// #for i in range(1, 11) [
+    ///   Verse line #i \
+    /// ]
+    /// ```
+    /// )
+    pub lines_per_column: Option<NonZeroUsize>,
+
+    /// The direction in which content progresses within each column.
+    ///
+    /// By default, content flows from the top to the bottom of a column. Some
+    /// vertical writing systems instead fill columns from the bottom up, which
+    /// can be achieved by setting this to `{btt}`. The order of the columns
+    /// themselves still follows the @text.dir[text direction].
+    ///
+    /// Vertical alignment follows the progression, i.e. content aligned to
+    /// the `{bottom}` ends up at the top of a bottom-up column.
+    ///
+    /// #example(
+    /// ```
+    /// #set page(columns: 2, height: 3cm)
+    /// #set columns(progression: btt)
+    /// #lorem(12)
+    /// ```
+    /// )
+    pub progression: ColumnProgression,
+
+    /// Whether to lay the content out in a single column if it is short
+    /// enough.
+    ///
+    /// When `{true}`, the content is first measured in a single column. If it
+    /// fits into the first region, e.g. the rest of the page, the columns are
+    /// dropped and the content stays in that single column. This keeps short
+    /// content from being split into columns. A positive integer instead
+    /// allows the single-column content to span up to that many regions.
+    ///
+    /// #example(
+    /// ```
+    /// #set page(height: 5cm)
+    /// #columns(2, collapse-if-fits: true)[
+    ///   This text is short enough to
+    ///   fit into a single column.
+    /// ]
+    /// ```
+    /// )
+    #[default]
+    pub collapse_if_fits: ColumnCollapse,
+
+    /// The maximum height of the columns.
+    ///
+    /// When set, the columns are at most this tall, even if their container
+    /// or the page offers more space. What happens with content that doesn't
+    /// fit is determined by the @columns.overflow[`overflow`] parameter.
+    ///
+    /// #example(
+    /// ```
+    /// #set page(height: 5cm)
+    /// #columns(2, max-height: 1.5cm)[
+    ///   #lorem(30)
+    /// ]
+    /// ```
+    /// )
+    pub max_height: Option<Length>,
+
+    /// What happens with content that exceeds the
+    /// @columns.max-height[maximum height].
+    ///
+    /// #example(
+    /// ```
+    /// #set page(height: 5cm)
+    /// #columns(2, max-height: 1.5cm, overflow: "clip")[
+    ///   #lorem(30)
+    /// ]
+    /// ```
+    /// )
+    pub overflow: ColumnOverflow,
+
+    /// Space to reserve at the bottom of every region of the columns.
+    ///
+    /// The columns' content doesn't flow into this band. Instead, the
+    /// @columns.reserved[reserved content] is placed into it in every region.
+    /// In contrast to a @page.footer[page footer], this doesn't depend on the
+    /// page, so it also works for columns within a container.
+    ///
+    /// #example(
+    /// ```
+    /// #set page(height: 5cm)
+    /// #columns(
+    ///   2,
+    ///   reserve-bottom: 1em,
+    ///   reserved: align(center)[_Continued_],
+    /// )[
+    ///   #lorem(40)
+    /// ]
+    /// ```
+    /// )
+    pub reserve_bottom: Length,
+
+    /// The content to place into the space reserved at the bottom of every
+    /// region of the columns. It spans all columns and is laid out anew for
+    /// each region. The reserved space grows to be at least as tall as this
+    /// content.
+    pub reserved: Option<Content>,
+
     /// The content that should be layouted into the columns.
     #[required]
     pub body: Content,
 }
 
+#[scope]
+impl ColumnsElem {
+    #[elem]
+    type BalancePointElem;
+
+    /// Determines the width of the column at the current location.
+    ///
+    /// This is the width available to content in the current column, which is
+    /// useful for content that should size itself to the column, like a rule
+    /// spanning the full column. Within nested columns, the width of the
+    /// innermost columns is reported. Outside of any columns, the width of the
+    /// page's content area is reported.
+    ///
+    /// The width is determined from the result of the previous layout pass. It
+    /// is thus only an estimate that stabilizes as Typst performs additional
+    /// passes until the document converges.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #columns(2)[
+    ///   #context line(length: columns.width())
+    ///   #lorem(10)
+    /// ]
+    /// ```
+    #[func(contextual, since = "0.16.0")]
+    pub fn width(
+        engine: &mut Engine,
+        context: Tracked<Context>,
+        span: Span,
+    ) -> SourceResult<Length> {
+        let loc = context.location().at(span)?;
+        let position = engine.introspect(PositionIntrospection(loc, span));
+
+        // The records of nested columns come after those of the surrounding
+        // ones, so the last record that contains the location is the
+        // innermost one.
+        let regions =
+            engine.introspect(QueryIntrospection(ColumnRegion::ELEM.select(), span));
+        let mut width = Length::zero();
+        for region in regions {
+            let Some(region_loc) = region.location() else { continue };
+            let start = engine.introspect(PositionIntrospection(region_loc, span));
+            let region = region.to_packed::<ColumnRegion>().unwrap();
+            let delta = position.point - start.point;
+            if start.page == position.page
+                && (Abs::zero()..=region.full.abs).contains(&delta.x)
+                && (Abs::zero()..=region.height.abs).contains(&delta.y)
+            {
+                width = region.width;
+            }
+        }
+
+        Ok(width)
+    }
+}
+
 /// Forces a column break.
 ///
 /// The function will behave like a @pagebreak[page break] when used in a single
@@ -124,3 +424,172 @@ pub struct ColbreakElem {
     #[default(false)]
     pub weak: bool,
 }
+
+/// A manual balance point for balanced columns.
+///
+/// In @columns.balanced[balanced] columns, the column height is usually
+/// determined automatically such that the content is distributed evenly. A
+/// balance point instead ends the column it is in and fixes the height of all
+/// columns to the height of the content before it. This gives precise control
+/// over where the columns split and takes precedence over automatic balancing.
+/// Only the first balance point in a region is taken into account. A balance
+/// point in the last column or in columns that aren't balanced has no effect.
+///
+/// Balancing only applies when the content ends in the region. If it continues
+/// in the next region, the balance point simply ends its column.
+///
+/// ```example
+/// #set page(height: 120pt)
+/// #columns(2, balanced: true)[
+///   #lorem(5)
+///   #columns.balance-here()
+///   #lorem(15)
+/// ]
+/// ```
+#[elem(name = "balance-here", title = "Column Balance Point", since = "0.16.0")]
+pub struct BalancePointElem {}
+
+/// A record of the column width of a flow region.
+///
+/// The layout emits one record at the start of each region of a page or of
+/// columns. It is queried by [`ColumnsElem::width`].
+#[elem(Construct, Unqueriable, Locatable)]
+pub struct ColumnRegion {
+    /// The width of each column in the region.
+    #[internal]
+    #[required]
+    pub width: Length,
+
+    /// The width of the whole region.
+    #[internal]
+    #[required]
+    pub full: Length,
+
+    /// The height of the whole region.
+    #[internal]
+    #[required]
+    pub height: Length,
+}
+
+impl Construct for ColumnRegion {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
+
+/// The spacing between columns.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ColumnGutter<T = Rel<Length>> {
+    /// The gaps after the first and before the last column.
+    pub outer: T,
+    /// The gaps between the remaining columns.
+    pub inner: T,
+}
+
+impl<T: Clone> ColumnGutter<T> {
+    /// Creates a gutter that is the same for all gaps.
+    pub fn splat(value: T) -> Self {
+        Self { outer: value.clone(), inner: value }
+    }
+
+    /// Maps both gaps with the given function.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> ColumnGutter<U> {
+        ColumnGutter { outer: f(self.outer), inner: f(self.inner) }
+    }
+}
+
+impl ColumnGutter<Abs> {
+    /// The gap after the column with the given index, out of `count` columns.
+    pub fn after(&self, i: usize, count: usize) -> Abs {
+        if i == 0 || i + 2 == count { self.outer } else { self.inner }
+    }
+
+    /// The sum of all gaps between `count` columns.
+    pub fn total(&self, count: usize) -> Abs {
+        (0..count.saturating_sub(1)).map(|i| self.after(i, count)).sum()
+    }
+}
+
+impl Resolve for ColumnGutter {
+    type Output = ColumnGutter<Rel<Abs>>;
+
+    fn resolve(self, styles: StyleChain) -> Self::Output {
+        self.map(|v| v.resolve(styles))
+    }
+}
+
+cast! {
+    ColumnGutter,
+    self => if self.outer == self.inner {
+        self.outer.into_value()
+    } else {
+        dict! { "outer" => self.outer, "inner" => self.inner }.into_value()
+    },
+    v: Rel<Length> => Self::splat(v),
+    mut v: Dict => {
+        let ret = Self {
+            outer: v.take("outer")?.cast()?,
+            inner: v.take("inner")?.cast()?,
+        };
+        v.finish(&["outer", "inner"])?;
+        ret
+    },
+}
+
+/// What happens with content that exceeds the maximum height of columns.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum ColumnOverflow {
+    /// The content continues in a new set of columns in the next region.
+    #[default]
+    Spill,
+    /// The content is cut off at the maximum height.
+    Clip,
+}
+
+/// How far the fill of a column gutter extends vertically.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum GutterFillExtent {
+    /// The fill spans the full height of the columns.
+    #[default]
+    Full,
+    /// The fill only spans the height filled with content in both adjacent
+    /// columns.
+    ContentOverlap,
+}
+
+/// The direction in which content progresses within a column.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ColumnProgression(pub Dir);
+
+impl Default for ColumnProgression {
+    fn default() -> Self {
+        Self(Dir::TTB)
+    }
+}
+
+cast! {
+    ColumnProgression,
+    self => self.0.into_value(),
+    v: Dir => {
+        if v.axis() != Axis::Y {
+            bail!("column progression must be vertical");
+        }
+        Self(v)
+    },
+}
+
+/// How many regions content laid out in a single column may span for the
+/// columns to collapse.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct ColumnCollapse(pub Option<NonZeroUsize>);
+
+cast! {
+    ColumnCollapse,
+    self => match self.0 {
+        None => false.into_value(),
+        Some(n) if n.get() == 1 => true.into_value(),
+        Some(n) => n.into_value(),
+    },
+    v: bool => Self(v.then_some(NonZeroUsize::ONE)),
+    v: NonZeroUsize => Self(Some(v)),
+}
@@ -2,7 +2,7 @@ use crate::diag::{HintedStrResult, SourceResult, bail};
 use crate::engine::Engine;
 use crate::foundations::{
     Args, AutoValue, Construct, Content, Dict, Fold, FromValue, IntoValue, NativeElement,
-    Packed, Smart, StyleChain, Value, cast, elem,
+    Packed, Smart, StyleChain, Value, cast, elem, scope,
 };
 use crate::introspection::Locator;
 use crate::layout::{
@@ -247,7 +247,7 @@ pub enum InlineItem {
 /// = Blocky
 /// More text.
 /// ```
-#[elem(since = "forever")]
+#[elem(scope, since = "forever")]
 pub struct BlockElem {
     /// The block's width.
     ///
@@ -277,6 +277,23 @@ pub struct BlockElem {
     /// ```
     pub height: Sizing,
 
+    /// The block's minimum height. A block whose content is shorter is
+    /// extended to this height, which is useful to reserve space, e.g. for
+    /// answers on an exam sheet. Like the @block.height[`height`], it includes
+    /// the block's inset and takes precedence over a smaller explicit height.
+    ///
+    /// If a breakable block would fit into the remaining space of a page, but
+    /// its minimum height wouldn't, the block moves to the next page as a
+    /// whole. When a block breaks across pages, the minimum height applies to
+    /// all of its parts together.
+    ///
+    /// ```example
+    /// #set block(stroke: 0.5pt, width: 100%)
+    /// #block(min-height: 40pt)[Answer:]
+    /// #block(min-height: 40pt)[Answer:]
+    /// ```
+    pub min_height: Rel<Length>,
+
     /// Whether the block can be broken and continue on the next page.
     ///
     /// ```example
@@ -369,6 +386,27 @@ pub struct BlockElem {
     #[default(false)]
     pub clip: bool,
 
+    /// Whether to scale the block's content down if it is too wide to fit.
+    ///
+    /// This is useful for a wide table that would otherwise overflow the
+    /// column, without having to scale the whole document. Content that fits
+    /// is left as is. Content that is too wide is scaled down uniformly until
+    /// it fits the width available to the block's content, so only the pages
+    /// on which it appears are affected. If the content has to shrink to less
+    /// than half its size, a warning is emitted as it may become hard to read.
+    ///
+    /// A block whose content is fitted is laid out as a whole and thus never
+    /// breaks across pages.
+    ///
+    /// ```example
+    /// #block(fit-width: true, table(
+    ///   columns: (60pt,) * 4,
+    ///   [Alpha], [Beta], [Gamma], [Delta],
+    /// ))
+    /// ```
+    #[default(false)]
+    pub fit_width: bool,
+
     /// Whether this block must stick to the following one, with no break in
     /// between.
     ///
@@ -387,11 +425,79 @@ pub struct BlockElem {
     #[default(false)]
     pub sticky: bool,
 
+    /// Content that is shown at the top of every region the block is broken
+    /// into.
+    ///
+    /// Space for the header is reserved at the top of each region, so the
+    /// block's body never overlaps with it. This repeats arbitrary content at
+    /// the start of each part of an embedded flow, for example a running title
+    /// for a long listing. In contrast to a @page.header[page header], this
+    /// works for any breakable block. Has no effect if the block isn't
+    /// breakable.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #block(
+    ///   stroke: 0.5pt,
+    ///   inset: 4pt,
+    ///   header: text(0.8em)[_Transcript_],
+    ///   lorem(30),
+    /// )
+    /// ```
+    pub header: Option<Content>,
+
+    /// Content that is shown at the bottom of every region the block is
+    /// broken into.
+    ///
+    /// Space for the footer is reserved at the bottom of each region, so the
+    /// block's body never overlaps with it. In contrast to a @page.footer[page
+    /// footer], this works for any breakable block, for example one that is
+    /// embedded into another container. Has no effect if the block isn't
+    /// breakable.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #block(
+    ///   stroke: 0.5pt,
+    ///   inset: 4pt,
+    ///   footer: align(right, text(0.8em)[_Excerpt_]),
+    ///   lorem(30),
+    /// )
+    /// ```
+    pub footer: Option<Content>,
+
+    /// Content that is shown at the end of every region after which the block
+    /// continues in another one.
+    ///
+    /// This is useful for markers like "(continued)" that point readers to
+    /// the rest of the block. In contrast to the @block.footer[`footer`], the
+    /// content only appears where the block is actually split and not in the
+    /// region the block ends in. Still, space for it is reserved at the bottom
+    /// of each region, as the split points are only known after layout. Has no
+    /// effect if the block isn't breakable.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #block(
+    ///   stroke: 0.5pt,
+    ///   inset: 4pt,
+    ///   on-split: align(right, text(0.8em)[_(continued)_]),
+    ///   lorem(30),
+    /// )
+    /// ```
+    pub on_split: Option<Content>,
+
     /// The contents of the block.
     #[positional]
     pub body: Option<BlockBody>,
 }
 
+#[scope]
+impl BlockElem {
+    #[elem]
+    type KeepElem;
+}
+
 impl BlockElem {
     /// Creates a new block element with a normal content body and directly
     /// packs it into type-erased content.
@@ -437,6 +543,50 @@ impl BlockElem {
     }
 }
 
+/// Keeps adjacent blocks together, such that there is no break between them.
+///
+/// Unlike wrapping the content into a `{block(breakable: false)}`, this does
+/// not create a container: The paragraphs and blocks in the body keep their
+/// own spacing, and breakable blocks among them still break internally if
+/// need be. If the group doesn't fit into the rest of the current region, it
+/// moves to the next one as a whole. A group that is too tall to fit into any
+/// region breaks like its content would without it.
+///
+/// ```example
+/// #set page(height: 120pt)
+/// #lorem(18)
+///
+/// #block.keep[
+///   #lorem(5)
+///
+///   #rect(width: 100%, height: 20pt)
+/// ]
+/// ```
+#[elem(name = "keep", title = "Keep Together", since = "0.16.0")]
+pub struct KeepElem {
+    /// The content to keep together.
+    #[required]
+    pub body: Content,
+}
+
+/// Marks the start or end of a group of blocks that are kept together.
+///
+/// The layout rule of [`KeepElem`] surrounds its body with these markers, so
+/// that the body's children take part in the enclosing flow as usual.
+#[elem(Construct)]
+pub struct KeepBoundary {
+    /// Whether this is the start of the group.
+    #[internal]
+    #[required]
+    pub start: bool,
+}
+
+impl Construct for KeepBoundary {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
+
 /// The contents of a block.
 #[derive(Debug, Clone, PartialEq, Hash)]
 pub enum BlockBody {
@@ -372,6 +372,20 @@ impl Frame {
         }
     }
 
+    /// Mark the frame as the column with the given index of a multi-column
+    /// flow.
+    pub fn set_column(&mut self, index: usize) {
+        if !self.is_empty() {
+            self.group(|g| g.column = Some(index));
+        }
+    }
+
+    /// Mark the frame as the final placement of the floating element with the
+    /// given location.
+    pub fn set_placement(&mut self, location: Location) {
+        self.group(|g| g.placement = Some(location));
+    }
+
     /// Wrap the frame's contents in a group and modify that group with `f`.
     fn group<F>(&mut self, f: F)
     where
@@ -386,6 +400,42 @@ impl Frame {
     }
 }
 
+/// Inspect the frame.
+impl Frame {
+    /// Determines the vertical positions of the text baselines in the frame.
+    ///
+    /// The positions are measured from the top edge of this frame and grow
+    /// downwards. They are sorted and each position is only listed once, even
+    /// if multiple columns or text runs share it. Text that is shifted off its
+    /// line's baseline, like a superscript, contributes its own position. Text
+    /// in groups that are transformed by more than a translation is skipped
+    /// since its baseline isn't horizontal or not at its natural scale.
+    ///
+    /// As this walks the laid out frame, it is only meaningful after layout,
+    /// for instance on the frames of a paged document's pages.
+    pub fn baselines(&self) -> Vec<Abs> {
+        let mut baselines = vec![];
+        self.collect_baselines(&mut baselines, Abs::zero());
+        baselines.sort();
+        baselines.dedup_by(|a, b| a.approx_eq(*b));
+        baselines
+    }
+
+    /// Collects the baselines of this frame's text, offset by `y`.
+    fn collect_baselines(&self, baselines: &mut Vec<Abs>, y: Abs) {
+        for (pos, item) in self.items() {
+            match item {
+                FrameItem::Group(group) if group.transform.is_only_translate() => {
+                    let y = y + pos.y + group.transform.ty;
+                    group.frame.collect_baselines(baselines, y);
+                }
+                FrameItem::Text(_) => baselines.push(y + pos.y),
+                _ => {}
+            }
+        }
+    }
+}
+
 /// Tools for debugging.
 impl Frame {
     /// Add a full size aqua background and a red baseline for debugging.
@@ -527,6 +577,12 @@ pub struct GroupItem {
     /// thought of as inserting the elements at the end but still inside of the
     /// parent.
     pub parent: Option<FrameParent>,
+    /// The index of the column this group hosts, if it is one of the columns
+    /// of a multi-column flow. Used to determine the reading order.
+    pub column: Option<usize>,
+    /// The location of the floating element this group hosts, if it is the
+    /// final placement of a float. Used to resolve where floats ended up.
+    pub placement: Option<Location>,
 }
 
 impl GroupItem {
@@ -538,6 +594,8 @@ impl GroupItem {
             clip: None,
             label: None,
             parent: None,
+            column: None,
+            placement: None,
         }
     }
 }
@@ -2,14 +2,18 @@ use std::num::NonZeroUsize;
 use std::ops::RangeInclusive;
 use std::str::FromStr;
 
+use comemo::Tracked;
+use typst_syntax::Span;
 use typst_utils::{NonZeroExt, Scalar, singleton};
 
-use crate::diag::{HintedStrResult, SourceResult, bail};
+use crate::diag::{At, HintedStrResult, SourceResult, bail};
 use crate::engine::Engine;
 use crate::foundations::{
-    Args, Cast, CastInfo, Construct, Content, Dict, Fold, FromValue, IntoValue,
-    NativeElement, Reflect, Set, Smart, Value, cast, elem,
+    Args, Array, Cast, CastInfo, Construct, Content, Context, Dict, Fold, FromValue,
+    Func, IntoValue, NativeElement, Reflect, Set, Smart, Str, Value, array, cast, dict,
+    elem, func, scope,
 };
+use crate::introspection::{PositionIntrospection, QueryIntrospection};
 use crate::layout::{
     Abs, Alignment, FlushElem, HAlignment, Length, OuterVAlignment, Ratio, Rel, Sides,
     SpecificAlignment,
@@ -50,7 +54,7 @@ use crate::visualize::Paint;
 /// instead configure the @page.header[`header`], @page.footer[`footer`],
 /// @page.background[`background`], and @page.foreground[`foreground`]
 /// properties with a set rule.
-#[elem(since = "forever", Construct)]
+#[elem(scope, since = "forever", Construct)]
 pub struct PageElem {
     /// A standard paper size to set width and height.
     ///
@@ -167,6 +171,20 @@ pub struct PageElem {
     #[ghost]
     pub margin: Smart<Margin<Smart<Rel<Length>>>>,
 
+    /// Additional space to keep free at the top of the first page of a page
+    /// run, on top of the regular top margin.
+    ///
+    /// This is useful for chapter-opening pages whose content should start
+    /// further down the page. Pages that the content continues on use the
+    /// regular margins.
+    ///
+    /// ```example
+    /// #set page(height: 100pt, first-inset: 30pt)
+    /// #lorem(30)
+    /// ```
+    #[ghost]
+    pub first_inset: Length,
+
     /// The page's bleed margin.
     ///
     /// The bleed is the area of content that extends beyond the final trimmed
@@ -254,6 +272,205 @@ pub struct PageElem {
     #[ghost]
     pub columns: NonZeroUsize,
 
+    /// Whether to remove weak spacing at the top of a continuation page or
+    /// column.
+    ///
+    /// When content breaks across pages or columns, spacing that would end up
+    /// at the very top of the next page or column, like the spacing between
+    /// two paragraphs, is removed by default. Disable this to keep it, such
+    /// that the content of all pages except the first one starts with the
+    /// same spacing. Spacing at the very start of the page's content is always
+    /// removed.
+    ///
+    /// ```example
+    /// #set page(height: 80pt, trim-leading: false)
+    /// #lorem(12)
+    ///
+    /// #lorem(6)
+    /// ```
+    #[default(true)]
+    #[ghost]
+    pub trim_leading: bool,
+
+    /// The distance from the top of each page or column to the baseline of
+    /// its first line.
+    ///
+    /// When set, the first line of each page or column is moved down such
+    /// that its baseline sits at this fixed distance from the top of the
+    /// region, no matter the size of the line. This is useful for aligning
+    /// text to a baseline grid across pages. Weak spacing in front of the line
+    /// is absorbed into the offset, so the line ends up at the same position
+    /// regardless of @page.trim-leading[`trim-leading`]. If the line's
+    /// baseline would already be further down, it stays where it is.
+    ///
+    /// ```example
+    /// #set page(height: 80pt, first-baseline: 24pt)
+    /// #set text(size: 8pt)
+    /// #lorem(20)
+    /// ```
+    #[ghost]
+    pub first_baseline: Option<Length>,
+
+    /// A rectangular area at the side of each page that paragraphs flow
+    /// around, for instance to leave room for an image that is placed
+    /// separately.
+    ///
+    /// The area is given as a dictionary with the following keys:
+    /// - `side`: The side of the region at which the area is, `{left}`,
+    ///   `{right}`, `{start}`, or `{end}`.
+    /// - `top`: The distance from the top of the page's content area to the
+    ///   area's top edge. Defaults to `{0pt}`.
+    /// - `width`: The area's width, including any gap to the text.
+    /// - `height`: The area's height.
+    ///
+    /// Lines of paragraphs that run alongside the area are shortened by its
+    /// width. Other content, like blocks, is not affected, and neither are
+    /// paragraphs within containers or nested columns. If the page has columns,
+    /// the area is at the same side of each of them, measured from the top of
+    /// the page's content area. Only one area is supported per page and it can
+    /// only be at the side of the region, not in its middle. Where a
+    /// paragraph's lines end up is determined from the previous layout pass, so
+    /// the document may need additional passes to converge. A paragraph that
+    /// breaks across regions is only shortened in the region where it starts.
+    ///
+    /// ```example
+    /// #set page(
+    ///   height: 120pt,
+    ///   exclusion: (side: right, top: 20pt, width: 50pt, height: 40pt),
+    ///   background: place(
+    ///     right + top, dx: -20pt, dy: 40pt,
+    ///     rect(width: 45pt, height: 40pt, fill: aqua),
+    ///   ),
+    /// )
+    /// #lorem(40)
+    /// ```
+    #[ghost]
+    pub exclusion: Option<PageExclusion>,
+
+    /// Fixed positions at which the pages of a run start.
+    ///
+    /// This locks the pagination of a document, for instance for a print run
+    /// that must not reflow when the document is compiled again with a newer
+    /// version of Typst. Each position is the number of items of the run, like
+    /// lines, blocks, and spacing, that precede a page. The positions of the
+    /// current pagination can be retrieved from the
+    /// @page.region[region records].
+    ///
+    /// When set, every page of the run except for the first starts exactly at
+    /// the next given position. If the content up to a position doesn't fit
+    /// onto a page, the content ends before the last position, or the content
+    /// needs more pages, layout fails with an error instead of reflowing.
+    ///
+    /// ```typ
+    /// // Print the positions of the current pagination.
+    /// #context query(page.region).slice(1).map(it => it.offset)
+    ///
+    /// // Then, lock them.
+    /// #set page(breaks: (14, 29))
+    /// ```
+    #[ghost]
+    pub breaks: Option<Vec<usize>>,
+
+    /// Decides whether the page's content may break at a given point.
+    ///
+    /// When set to a function, it is consulted for the points between the
+    /// blocks and lines of the page's top-level content at which the page or
+    /// column may break. The function receives a dictionary with the keys
+    /// `before` and `after`. `before` holds the last element that started on
+    /// the page or in the column before the point, or `{none}` if there is no
+    /// such element. `after` holds the element that starts right after the
+    /// point, or `{none}` if the point doesn't precede the start of an
+    /// element, for instance because it falls between two lines of the same
+    /// paragraph. It must return a boolean.
+    ///
+    /// If the function returns `{false}` for the point where the content
+    /// naturally breaks, the break is moved up to the closest preceding point
+    /// for which it returns `{true}`. If there is no such point on the page or
+    /// in the column, the content breaks where it naturally would, as nothing
+    /// would fit otherwise. Breaks within a breakable block and within nested
+    /// flows like columns are not subject to the function. With the
+    /// [`{"compact"}`]($page.optimize) optimization, the function may also be
+    /// ignored for individual pages if that saves a page.
+    ///
+    /// ```example
+    /// #set page(
+    ///   height: 100pt,
+    ///   can-break: it => it.before == none or it.before.func() != heading,
+    /// )
+    /// #lorem(18)
+    /// = Heading
+    /// #lorem(10)
+    /// ```
+    #[ghost]
+    pub can_break: Option<Func>,
+
+    /// How the page's content is optimized when it is broken into pages.
+    ///
+    /// - `{none}`: Content is broken into pages one page at a time, honoring
+    ///   all break constraints.
+    /// - `{"compact"}`: The layout additionally explores alternative page
+    ///   breaks to reduce the total number of pages. For each page, it
+    ///   considers breaking where the soft break constraints are relaxed:
+    ///   First, the @page.can-break[`can-break`] predicate is ignored and then,
+    ///   also the stickiness of @block.sticky[sticky] blocks like headings. Of
+    ///   the combinations of breaks that need the fewest pages, the one that
+    ///   relaxes the constraints the least is chosen. Constraints are only
+    ///   relaxed if that saves at least one page. As this requires additional
+    ///   layout passes, it makes compilation slower.
+    ///
+    /// ```example
+    /// #set page(height: 80pt, optimize: "compact")
+    /// #lorem(10)
+    /// = Heading
+    /// #lorem(8)
+    /// ```
+    #[ghost]
+    pub optimize: Option<PageOptimization>,
+
+    /// Whether the page's content flows continuously into a single tall page.
+    ///
+    /// This is useful for web-like output, where the content should be shown
+    /// as one long page instead of being broken into pages. A continuous page
+    /// behaves like a page with `{height: auto}`, but also adapts elements that
+    /// depend on page breaks:
+    ///
+    /// - Column breaks are ignored and the page's
+    ///   @page.columns[`columns`] are laid out as a single column.
+    /// - Floats are placed inline at their position in the content, taking up
+    ///   space like a block, instead of floating to the top or bottom.
+    /// - Footnote entries are gathered at the end of the content, like
+    ///   endnotes.
+    ///
+    /// An explicit @pagebreak still ends the current continuous page and
+    /// starts a new one.
+    ///
+    /// ```example
+    /// #set page(height: 100pt, continuous: true)
+    /// #place(top, float: true)[*Float*]
+    /// Flowing#footnote[A note.] content.
+    /// #colbreak()
+    /// #lorem(20)
+    /// ```
+    #[default(false)]
+    #[ghost]
+    pub continuous: bool,
+
+    /// Whether to mirror the page's content horizontally.
+    ///
+    /// Some binding styles require the whole page content to be printed
+    /// mirrored. When enabled, the content of each page is flipped along its
+    /// vertical axis. This flips the geometry of the content, including the
+    /// glyphs themselves, but leaves the page's header, footer, background,
+    /// and foreground untouched.
+    ///
+    /// ```example
+    /// #set page(height: 60pt, mirrored: true)
+    /// Mirrored for printing.
+    /// ```
+    #[default(false)]
+    #[ghost]
+    pub mirrored: bool,
+
     /// The page's background fill.
     ///
     /// Setting this to something non-transparent instructs the printer to color
@@ -491,6 +708,94 @@ pub struct PageElem {
     #[ghost]
     pub foreground: Option<Content>,
 
+    /// The order of bottom floats and footnotes at the bottom of the page,
+    /// from top to bottom.
+    ///
+    /// By default, floats placed at the `{bottom}` sit directly below the text
+    /// and above the footnotes. With `{("footnotes", "floats")}`, the footnotes
+    /// instead follow the text and the floats are placed at the very bottom of
+    /// the page. In multi-column layouts, this applies to each column.
+    ///
+    /// ```example
+    /// #set page(height: 140pt, bottom-order: ("footnotes", "floats"))
+    ///
+    /// Text #footnote[A note]
+    /// #place(
+    ///   bottom,
+    ///   float: true,
+    ///   rect(width: 100%, height: 20pt, fill: aqua),
+    /// )
+    /// ```
+    #[ghost]
+    pub bottom_order: BottomOrder,
+
+    /// Pads the document with blank pages until its page count is a multiple of
+    /// this number.
+    ///
+    /// This is useful when printing booklets, which are bound from signatures
+    /// of, for instance, four or sixteen pages. The padding pages are appended
+    /// at the very end of the document and take their properties, like
+    /// @page.fill[`fill`] and @page.background[`background`], from the last
+    /// page.
+    ///
+    /// ```example
+    /// #set page(
+    ///   height: 60pt,
+    ///   fill: aqua.lighten(60%),
+    ///   pad-to-multiple: 4,
+    /// )
+    ///
+    /// First page
+    /// #pagebreak()
+    /// Second page
+    /// ```
+    #[ghost]
+    pub pad_to_multiple: Option<NonZeroUsize>,
+
+    /// The maximum number of pages the document may have.
+    ///
+    /// If the document ends up with more pages, including any pages added by
+    /// @page.pad-to-multiple[`pad-to-multiple`], compilation fails with an
+    /// error that reports the actual page count. This is useful for enforcing
+    /// a page budget, for instance in continuous integration. Like
+    /// `pad-to-multiple`, this is taken from the properties of the last page.
+    ///
+    /// ```example
+    /// #set page(height: 60pt, max-pages: 1)
+    /// This document must fit on a single page.
+    /// ```
+    #[ghost]
+    pub max_pages: Option<NonZeroUsize>,
+
+    /// Reorders the finished pages for booklet printing.
+    ///
+    /// The pages are laid out as usual and only reordered at the very end,
+    /// such that the printed and folded sheets end up in reading order. This
+    /// does not place multiple pages onto one sheet, which is left to the
+    /// printer's two-up (or "booklet") setting. Page numbers and all other
+    /// content stay the same, but introspection, like
+    /// @location.page[`location.page`], reports the physical position of a
+    /// page in the reordered document. Like
+    /// @page.pad-to-multiple[`pad-to-multiple`], this is taken from the
+    /// properties of the last page.
+    ///
+    /// The following schemes are supported:
+    /// - `{"saddle-stitch"}`: The sheets are stacked, folded once in the
+    ///   middle, and stitched through the fold. The document is padded with
+    ///   blank pages to a multiple of four. Then, each side of a sheet holds
+    ///   one page from the back and one from the front of the document, in the
+    ///   order `n, 1, 2, n-1, n-2, 3, ...`.
+    ///
+    /// ```example
+    /// #set page(height: 60pt, imposition: "saddle-stitch")
+    /// #for i in range(1, 5) [
+    ///   Page #i
+    ///   #if i < 4 { pagebreak() }
+    /// ]
+    /// ```
+    #[ghost]
+    pub imposition: Option<PageImposition>,
+
     /// The contents of the page(s).
     ///
     /// Multiple pages will be created if the content does not fit on a single
@@ -523,10 +828,121 @@ impl Construct for PageElem {
     }
 }
 
+#[scope]
+impl PageElem {
+    #[elem]
+    type PageRegion;
+
+    /// Determines the height that is left on the current page.
+    ///
+    /// This is the distance from the current location to the bottom of the
+    /// page's content area. It can be used to fill the rest of a page, for
+    /// example with leader dots or lines for handwritten notes.
+    ///
+    /// Only the page-level flow is taken into account: Within a container
+    /// like a block with a fixed height, the result is still the distance to
+    /// the bottom of the page, not of the container. Floats and footnotes at
+    /// the bottom of the page are not subtracted either.
+    ///
+    /// The remaining height is determined from the result of the previous
+    /// layout pass. It is thus only an estimate that stabilizes as Typst
+    /// performs additional passes until the document converges.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #lorem(10)
+    ///
+    /// #context block(
+    ///   height: page.remaining() - 1em,
+    ///   width: 100%,
+    ///   fill: aqua,
+    /// )
+    /// ```
+    #[func(contextual, since = "0.16.0")]
+    pub fn remaining(
+        engine: &mut Engine,
+        context: Tracked<Context>,
+        span: Span,
+    ) -> SourceResult<Length> {
+        let loc = context.location().at(span)?;
+        let position = engine.introspect(PositionIntrospection(loc, span));
+
+        // Find the region record of the page and measure from the current
+        // position to the bottom of the region.
+        let regions =
+            engine.introspect(QueryIntrospection(PageRegion::ELEM.select(), span));
+        for region in regions {
+            let Some(region_loc) = region.location() else { continue };
+            let start = engine.introspect(PositionIntrospection(region_loc, span));
+            if start.page != position.page {
+                continue;
+            }
+
+            let height = region.to_packed::<PageRegion>().unwrap().height.abs;
+            let bottom = start.point.y + height;
+            return Ok((bottom - position.point.y).max(Abs::zero()).into());
+        }
+
+        Ok(Length::zero())
+    }
+}
+
 impl LocalName for PageElem {
     const KEY: &'static str = "page";
 }
 
+/// A record of where a page lies within its run of pages.
+///
+/// Content that is laid out across several pages with the same page
+/// properties forms a run of pages. The layout emits one region record for
+/// each page of a run. Records cannot be created manually, but they can be
+/// queried, for example to show something only on the last page of a run.
+///
+/// Whether a page is the last one of its run is only known once the whole run
+/// has been laid out. Content that depends on it is thus only correct from the
+/// second layout pass onwards, just like other introspection. Typst performs
+/// additional passes automatically until the document converges.
+///
+/// ```example
+/// #set page(height: 60pt, footer: context {
+///   let regions = query(page.region)
+///     .filter(it => it.location().page() == here().page())
+///   if regions.len() > 0 and regions.first().last [The end]
+/// })
+///
+/// #lorem(20)
+/// ```
+#[elem(name = "region", title = "Page Region", since = "0.16.0", Construct, Locatable)]
+pub struct PageRegion {
+    /// Whether this is the first page of the run.
+    #[required]
+    pub first: bool,
+
+    /// Whether this is the last page of the run.
+    #[required]
+    pub last: bool,
+
+    /// The position in the run's content at which this page starts.
+    ///
+    /// The position counts the items of the run, like lines, blocks, and
+    /// spacing, that were laid out on the preceding pages. The positions of all
+    /// pages but the first can be passed to @page.breaks[`breaks`] to fix the
+    /// pagination.
+    #[required]
+    pub offset: usize,
+
+    /// The height of the page's content area.
+    #[internal]
+    #[required]
+    pub height: Length,
+}
+
+impl Construct for PageRegion {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
+
 /// A manual page break.
 ///
 /// Must not be used inside any containers.
@@ -593,6 +1009,85 @@ impl PagebreakElem {
     }
 }
 
+/// How the content of a page is optimized when it is broken into pages.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum PageOptimization {
+    /// Minimize the number of pages by relaxing soft break constraints.
+    Compact,
+}
+
+/// A rectangular area at the side of a region that paragraphs flow around.
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct PageExclusion {
+    /// The side of the region at which the area is.
+    pub side: HAlignment,
+    /// The distance from the top of the region to the area's top edge.
+    pub top: Length,
+    /// The area's width.
+    pub width: Length,
+    /// The area's height.
+    pub height: Length,
+}
+
+cast! {
+    PageExclusion,
+    self => Value::Dict(dict! {
+        "side" => self.side,
+        "top" => self.top,
+        "width" => self.width,
+        "height" => self.height,
+    }),
+    mut dict: Dict => {
+        let side: HAlignment = dict.take("side")?.cast()?;
+        if side == HAlignment::Center {
+            bail!("exclusion must be at the left or right side of the region");
+        }
+        let top = dict.take("top").ok().map(|v| v.cast()).transpose()?.unwrap_or_default();
+        let width = dict.take("width")?.cast()?;
+        let height = dict.take("height")?.cast()?;
+        dict.finish(&["side", "top", "width", "height"])?;
+        Self { side, top, width, height }
+    },
+}
+
+/// How finished pages are reordered for printing.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum PageImposition {
+    /// Reorder for sheets that are folded once and stitched through the fold.
+    SaddleStitch,
+}
+
+/// The order of bottom floats and footnotes at the bottom of a page.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BottomOrder {
+    /// Bottom floats are placed above the footnotes.
+    #[default]
+    FloatsFirst,
+    /// Footnotes are placed above the bottom floats.
+    FootnotesFirst,
+}
+
+cast! {
+    BottomOrder,
+    self => match self {
+        Self::FloatsFirst => array!["floats", "footnotes"],
+        Self::FootnotesFirst => array!["footnotes", "floats"],
+    }.into_value(),
+    v: Array => {
+        let items = v
+            .into_iter()
+            .map(Value::cast::<Str>)
+            .collect::<HintedStrResult<Vec<_>>>()?;
+        match items.iter().map(Str::as_str).collect::<Vec<_>>().as_slice() {
+            ["floats", "footnotes"] => Self::FloatsFirst,
+            ["footnotes", "floats"] => Self::FootnotesFirst,
+            _ => bail!(
+                "expected `(\"floats\", \"footnotes\")` or `(\"footnotes\", \"floats\")`"
+            ),
+        }
+    },
+}
+
 /// Specification of the page's margins.
 #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct Margin<T: PartialEq> {
@@ -1,5 +1,11 @@
-use crate::foundations::{Cast, Content, Smart, elem, scope};
-use crate::layout::{Alignment, Em, Length, Rel};
+use crate::diag::{SourceResult, bail};
+use crate::engine::Engine;
+use crate::foundations::{
+    Args, AutoValue, Cast, Construct, Content, IntoValue, Label, Smart, Value, cast,
+    elem, scope,
+};
+use crate::introspection::Location;
+use crate::layout::{Alignment, Angle, Em, Length, Rel, VAlignment};
 
 /// Places content relatively to its parent container.
 ///
@@ -136,13 +142,249 @@ pub struct PlaceElem {
     /// ```
     pub float: bool,
 
+    /// An element with which the floating element should share a page.
+    ///
+    /// If the labelled element comes after the float, the float is held back
+    /// until the element is laid out and then placed in the same region if it
+    /// fits there. Otherwise, a warning is emitted and the float is placed in
+    /// the next region instead. If the labelled element comes before the float,
+    /// the float is placed as usual. A typical use case is keeping a figure
+    /// close to the paragraph that discusses it.
+    ///
+    /// Since the position of the labelled element is only known after a first
+    /// layout pass, it takes one additional pass for the float to settle in its
+    /// final position. If the labelled element is not part of the same flow as
+    /// the float, the float is placed at the end of the flow.
+    ///
+    /// Has no effect if `float` is `{false}`.
+    ///
+    /// ```example
+    /// #set page(height: 150pt)
+    /// #place(
+    ///   top,
+    ///   float: true,
+    ///   same-page-as: <discussion>,
+    ///   rect(width: 100%)[Figure],
+    /// )
+    ///
+    /// #lorem(30)
+    ///
+    /// #lorem(10) <discussion>
+    /// ```
+    pub same_page_as: Option<Label>,
+
+    /// An earlier element at which the floating element should be placed as
+    /// if it appeared there.
+    ///
+    /// This is useful when the float is declared after the position where it
+    /// belongs, for instance because its content is produced at the end of a
+    /// section. The float is then placed as if it came directly before the
+    /// labelled element, so it can end up in an earlier region than the one
+    /// in which it is declared.
+    ///
+    /// The labelled element must be a direct part of the same flow as the
+    /// float and precede it, like a heading, a figure, or a block. Otherwise,
+    /// a warning is emitted and the float is placed as usual.
+    ///
+    /// Has no effect if `float` is `{false}`.
+    ///
+    /// ```example
+    /// #set page(height: 150pt)
+    /// = Results <results>
+    /// #lorem(40)
+    ///
+    /// #place(
+    ///   top,
+    ///   float: true,
+    ///   anchor: <results>,
+    ///   rect(width: 100%)[Table of results],
+    /// )
+    /// ```
+    pub anchor: Option<Label>,
+
+    /// The side to which a floating element moves if its preferred side is
+    /// already taken.
+    ///
+    /// A float normally stacks up with other floats at its side of the region.
+    /// With a fallback, it is instead placed at the fallback side if another
+    /// float already sits at its preferred side. If both sides are taken, the
+    /// float is deferred to the next region. The preferred side is determined
+    /// by the @place.alignment[`alignment`] as usual, so it takes precedence
+    /// over the fallback. This spreads floats over the regions rather than
+    /// piling them up in one of them.
+    ///
+    /// This must be `{top}` or `{bottom}`. Has no effect if `float` is
+    /// `{false}`.
+    ///
+    /// ```example
+    /// #set page(height: 150pt)
+    /// #let note(body) = place(
+    ///   top,
+    ///   float: true,
+    ///   fallback: bottom,
+    ///   clearance: 6pt,
+    ///   rect(body),
+    /// )
+    ///
+    /// #note[Top]
+    /// #note[Bottom]
+    /// #lorem(20)
+    /// ```
+    pub fallback: Option<VAlignment>,
+
+    /// How a floating element chooses the side of the region it is placed at.
+    ///
+    /// - `{"aligned"}`: The side is determined by the
+    ///   @place.alignment[`alignment`]. An `{auto}` alignment picks the side
+    ///   closer to the element's position in the flow.
+    /// - `{"spacious"}`: The float is placed at the side whose band currently
+    ///   has more free space, that is, at the top if the floats (and
+    ///   footnotes) at the top of the region take up at most as much space as
+    ///   those at the bottom and at the bottom otherwise. This balances the
+    ///   region and reduces the number of deferred floats. An explicit
+    ///   vertical alignment is ignored with a warning, so the float can end up
+    ///   at a different side than with explicit placement.
+    ///
+    /// The @place.fallback[`fallback`] side still applies afterwards. Has no
+    /// effect if `float` is `{false}`.
+    ///
+    /// ```example
+    /// #set page(height: 150pt)
+    /// #let note(body) = place(
+    ///   float: true,
+    ///   placement: "spacious",
+    ///   clearance: 6pt,
+    ///   rect(body),
+    /// )
+    ///
+    /// #note[First]
+    /// #note[Second]
+    /// #lorem(20)
+    /// ```
+    pub placement: FloatPlacement,
+
+    /// Whether the float should be pulled into the earliest region with room.
+    ///
+    /// By default, a float is placed in the region where it appears in the flow
+    /// or, if it doesn't fit there, in one of the following regions. An eager
+    /// float is instead placed in the earliest region of the flow that has room
+    /// for it, even if that region comes before the float's position in the
+    /// flow. It is only pulled across about two regions worth of content, so
+    /// that it stays close to its position. A warning is emitted when this
+    /// moves the float before its reference point. This is mostly useful for
+    /// decorative images whose exact position doesn't matter.
+    ///
+    /// This is a plain switch: How far an eager float may be pulled forward
+    /// cannot be adjusted.
+    ///
+    /// Has no effect if `float` is `{false}` or if
+    /// @place.same-page-as[`same-page-as`] is set.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #lorem(30)
+    ///
+    /// #place(
+    ///   top,
+    ///   float: true,
+    ///   eager: true,
+    ///   rect(width: 100%)[Ornament],
+    /// )
+    /// ```
+    #[default(false)]
+    pub eager: bool,
+
+    /// The page on which a floating element is placed.
+    ///
+    /// - `{auto}`: The float is placed in the region where it appears in the
+    ///   flow or, if it doesn't fit there, in one of the following regions.
+    /// - `{"next"}`: The float is always deferred to the top of the next
+    ///   region, even if there would be room for it in the current one. This
+    ///   is useful for full-page plates that are introduced in the middle of
+    ///   the text.
+    ///
+    /// In a multi-column layout, the next region of a column-scoped float is
+    /// the next column. Use `{scope: "parent"}` to move it to the next page
+    /// instead. A float for the next region is always placed at the top, so
+    /// a warning is emitted if it is combined with bottom alignment, a
+    /// @place.fallback[`fallback`], automatic @place.placement[`placement`],
+    /// `eager`, or @place.same-page-as[`same-page-as`]. These are ignored then.
+    ///
+    /// Has no effect if `float` is `{false}`.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #lorem(10)
+    ///
+    /// #place(
+    ///   top,
+    ///   float: true,
+    ///   page: "next",
+    ///   rect(width: 100%, height: 40pt)[Plate],
+    /// )
+    ///
+    /// #lorem(20)
+    /// ```
+    pub page: FloatPage,
+
+    /// Whether text should wrap around the floating element.
+    ///
+    /// A wrapping float is placed at the left or right edge of its column,
+    /// right where it appears in the flow. The lines of the following
+    /// paragraphs that run alongside the float are shortened to make room for
+    /// it. Content that can't wrap, like a block, is moved below the float.
+    ///
+    /// A wrapping float must be aligned horizontally to the `{left}`, `{right}`,
+    /// `{start}`, or `{end}` and may not be aligned vertically. The float and
+    /// the lines running alongside it are kept in the same region. If they
+    /// don't fit into the current one, they move to the next region together.
+    ///
+    /// Has no effect if `float` is `{false}`.
+    ///
+    /// ```example
+    /// #set page(height: 150pt)
+    /// #place(
+    ///   right,
+    ///   float: true,
+    ///   wrap: true,
+    ///   clearance: 6pt,
+    ///   rect(width: 40pt, height: 50pt),
+    /// )
+    /// #lorem(40)
+    /// ```
+    #[default(false)]
+    pub wrap: bool,
+
     /// The spacing between the placed element and other elements in a floating
     /// layout.
     ///
+    /// For a wrapping float, this is the horizontal distance to the wrapping
+    /// text.
+    ///
     /// Has no effect if `float` is `{false}`.
     #[default(Em::new(1.5).into())]
     pub clearance: Length,
 
+    /// The spacing between the float and an adjacent float in a stack of
+    /// floats.
+    ///
+    /// When multiple floats stack at the top or bottom of a region, this
+    /// spacing separates the float from the next float towards the flow
+    /// content. The spacing to the flow content itself is always determined by
+    /// the @place.clearance[`clearance`]. If set to `{auto}`, the gap is the
+    /// same as the clearance.
+    ///
+    /// Has no effect if `float` is `{false}`.
+    ///
+    /// ```example
+    /// #set page(height: 150pt)
+    /// #set place(gap: 4pt)
+    /// #place(top, float: true, rect(height: 20pt, width: 100%))
+    /// #place(top, float: true, rect(height: 20pt, width: 100%))
+    /// #lorem(10)
+    /// ```
+    pub gap: Smart<Length>,
+
     /// The horizontal displacement of the placed content.
     ///
     /// ```example
@@ -168,6 +410,27 @@ pub struct PlaceElem {
     /// placed content is treated as if it were wrapped in a @move element.
     pub dy: Rel<Length>,
 
+    /// The amount of rotation of the placed content.
+    ///
+    /// In contrast to wrapping the content in a @rotate element, the space
+    /// reserved for the placed content is that of its rotated bounding box.
+    /// For a float, this means that in-flow content is displaced by the
+    /// rotated content's actual extent, which is handy for sideways tables
+    /// that would otherwise be too wide for the page.
+    ///
+    /// ```example
+    /// #set page(height: 200pt)
+    /// #place(
+    ///   top,
+    ///   float: true,
+    ///   rotate: 90deg,
+    ///   table(columns: 4, ..range(8).map(str)),
+    /// )
+    ///
+    /// #lorem(10)
+    /// ```
+    pub rotate: Angle,
+
     /// The content to place.
     #[required]
     pub body: Content,
@@ -177,6 +440,9 @@ pub struct PlaceElem {
 impl PlaceElem {
     #[elem]
     type FlushElem;
+
+    #[elem]
+    type FloatDeferral;
 }
 
 /// Relative to which containing scope something shall be placed.
@@ -189,6 +455,36 @@ pub enum PlacementScope {
     Parent,
 }
 
+/// How a floating element chooses the side of the region it is placed at.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum FloatPlacement {
+    /// Place at the side given by the alignment.
+    #[default]
+    Aligned,
+    /// Place at the side with more free space.
+    Spacious,
+}
+
+/// The page on which a floating element is placed.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FloatPage {
+    /// Place in the current region if there is room.
+    #[default]
+    Auto,
+    /// Defer to the top of the next region.
+    Next,
+}
+
+cast! {
+    FloatPage,
+    self => match self {
+        Self::Auto => Value::Auto,
+        Self::Next => "next".into_value(),
+    },
+    "next" => Self::Next,
+    _: AutoValue => Self::Auto,
+}
+
 /// Asks the layout algorithm to place pending floating elements before
 /// continuing with the content.
 ///
@@ -211,3 +507,45 @@ pub enum PlacementScope {
 /// ```
 #[elem(since = "0.12.0")]
 pub struct FlushElem {}
+
+/// A record of how many regions a float was deferred across before it was
+/// placed.
+///
+/// When a float doesn't fit into the region of its reference, it is queued and
+/// tried again in the following regions (pages or columns). The layout emits
+/// one record for each float that was queued like this, located in the region
+/// where the float was eventually placed. Floats that are placed right away
+/// don't produce a record. Records cannot be created manually, but they can be
+/// queried to find out why a figure ended up far away from its reference.
+///
+/// ```example
+/// #set page(height: 120pt)
+/// #lorem(10)
+/// #place(auto, float: true, rect(height: 80pt))
+///
+/// #context for deferral in query(place.deferral) [
+///   Deferred across #deferral.regions region(s).
+/// ]
+/// ```
+#[elem(
+    name = "deferral",
+    title = "Float Deferral",
+    since = "0.16.0",
+    Construct,
+    Locatable
+)]
+pub struct FloatDeferral {
+    /// The location of the float in the content.
+    #[required]
+    pub float: Location,
+
+    /// The number of regions that the float didn't fit into.
+    #[required]
+    pub regions: usize,
+}
+
+impl Construct for FloatDeferral {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
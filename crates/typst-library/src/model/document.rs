@@ -178,6 +178,27 @@ pub struct DocumentElem {
     /// something other than `{auto}`.
     pub date: Smart<Option<Datetime>>,
 
+    /// Whether to compile the document as a draft.
+    ///
+    /// In a draft, footnote entries are not laid out at the bottom of the
+    /// page. Only the footnote markers are shown in the text. This makes
+    /// compilation faster, which is useful for quick previews. Since the
+    /// footnote entries don't take up any space, the page breaks may differ
+    /// from those of the final document.
+    ///
+    /// A draft also warns about lines that are wider than the space available
+    /// to them, for instance because of a long URL that can't be broken. This
+    /// helps to find and fix them before compiling the final document.
+    ///
+    /// ```example
+    /// #set document(draft: true)
+    ///
+    /// Check the docs for more details.
+    /// #footnote[https://typst.app/docs]
+    /// ```
+    #[default(false)]
+    pub draft: bool,
+
     /// The content that makes up the document.
     ///
     /// This property is only supported in the @reference:bundle[bundle] target.
@@ -302,6 +302,30 @@ pub struct FigureElem {
     #[default(Em::new(0.65).into())]
     pub gap: Length,
 
+    /// Whether the figure's body and caption are always kept together.
+    ///
+    /// Figures aren't breakable by default, but can be made breakable with
+    /// `{show figure: set block(breakable: true)}`. In a breakable figure, the
+    /// caption may end up in a different region than the part of the body it
+    /// is attached to. When this is enabled, the body and caption are instead
+    /// laid out as a single unit that is never broken, regardless of the block
+    /// settings. If the unit doesn't fit into a full region, a warning is
+    /// emitted.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #show figure: set block(breakable: true)
+    ///
+    /// #lorem(12)
+    /// #figure(
+    ///   keep-together: true,
+    ///   rect(height: 50pt),
+    ///   caption: [Not separated],
+    /// )
+    /// ```
+    #[default(false)]
+    pub keep_together: bool,
+
     /// Whether the figure should appear in an @outline of figures.
     #[default(true)]
     pub outlined: bool,
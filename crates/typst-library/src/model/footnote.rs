@@ -1,20 +1,25 @@
 use std::num::NonZeroUsize;
 use std::str::FromStr;
+use std::sync::Arc;
 
-use ecow::{EcoString, eco_format};
+use comemo::{Track, Tracked};
+use ecow::{EcoString, eco_format, eco_vec};
+use rustc_hash::FxHashMap;
+use typst_syntax::Span;
 use typst_utils::{NonZeroExt, singleton};
 
-use crate::diag::{At, SourceResult, StrResult, bail};
+use crate::diag::{At, SourceDiagnostic, SourceResult, StrResult, bail, warning};
 use crate::engine::Engine;
 use crate::foundations::{
-    Content, Label, NativeElement, Packed, ShowSet, Smart, StyleChain, Styles, cast,
-    elem, scope,
+    Args, Cast, Construct, Content, Context, Label, NativeElement, Packed, Selector,
+    ShowSet, Smart, StyleChain, Styles, Value, cast, elem, scope,
 };
 use crate::introspection::{
-    Count, Counter, CounterUpdate, Location, QueryLabelIntrospection,
+    Count, Counter, CounterState, CounterUpdate, History, Introspect, Introspector,
+    Location, QueryFirstIntrospection, QueryIntrospection, QueryLabelIntrospection,
 };
-use crate::layout::{Em, Length, Ratio};
-use crate::model::{DirectLinkElem, Numbering, NumberingPattern, ParElem};
+use crate::layout::{Em, HAlignment, Length, Ratio, Rel, Sides};
+use crate::model::{DirectLinkElem, HeadingElem, Numbering, NumberingPattern, ParElem};
 use crate::text::{LocalName, SuperElem, TextElem, TextSize};
 use crate::visualize::{LineElem, Stroke};
 
@@ -50,6 +55,13 @@ use crate::visualize::{LineElem, Stroke};
 /// And the online app. #footnote(<fn>)
 /// ```
 ///
+/// The properties that shape the footnote area as a whole are read once per
+/// page: @footnote.area-align[`area-align`], @footnote.columns[`columns`],
+/// @footnote.lines-above[`lines-above`], and
+/// @footnote.continuation[`continuation`]. Just like the properties of
+/// @footnote.entry, they must be uniform across each page run, so set them
+/// before any page content.
+///
 /// _Note:_ Set and show rules in the scope where `footnote` is called may not
 /// apply to the footnote's content. See
 /// #link("https://github.com/typst/typst/issues/1467#issuecomment-1588799440")[here]
@@ -79,6 +91,177 @@ pub struct FootnoteElem {
     #[default(Numbering::Pattern(NumberingPattern::from_str("1").unwrap()))]
     pub numbering: Numbering,
 
+    /// Whether footnotes are numbered anew in each section.
+    ///
+    /// When enabled, the footnote numbers restart at each numbered top-level
+    /// @heading[heading]. The number within the section is displayed with the
+    /// footnote's @footnote.numbering[numbering] and prefixed with the
+    /// section's number. Footnotes before the first such heading are numbered
+    /// without a prefix. Displaying the footnote @counter[counter] follows the
+    /// same rules, with an explicit numbering receiving the section's number
+    /// and the number within the section. A footnote that references another
+    /// one resolves this setting together with its numbering.
+    ///
+    /// ```example
+    /// #set heading(numbering: "1.")
+    /// #set footnote(per-section: true)
+    ///
+    /// = Introduction
+    /// First #footnote[Is 1.1]
+    /// and second #footnote[Is 1.2]
+    ///
+    /// = Method
+    /// Third #footnote[Is 2.1]
+    /// ```
+    #[default(false)]
+    pub per_section: bool,
+
+    /// The series this footnote belongs to.
+    ///
+    /// Footnotes of different series are numbered independently of each
+    /// other. This is useful for texts that combine, for instance, numbered
+    /// notes by the author with symbol-marked notes by the editor. By
+    /// combining a show-set rule with a `where` selector, each series can get
+    /// its own numbering. Footnotes of all series share the same listing at
+    /// the bottom of the page.
+    ///
+    /// Only footnotes without a series are tracked by the footnote
+    /// @counter[counter]. Footnotes that reference another footnote take the
+    /// number of the referenced one, regardless of their own series.
+    ///
+    /// ```example
+    /// #show footnote.where(series: "editor"): set footnote(numbering: "*")
+    ///
+    /// Author #footnote[First note]
+    /// and editor #footnote(series: "editor")[Added later]
+    /// notes #footnote[Second note]
+    /// are counted apart. #footnote(series: "editor")[Also added]
+    /// ```
+    pub series: Option<EcoString>,
+
+    /// Whether the footnote is numbered.
+    ///
+    /// An unnumbered footnote has no marker in the text and its entry has no
+    /// number, but it still appears in the footnote listing, in the order of
+    /// the notes in the text. It doesn't step the footnote @counter[counter],
+    /// so numbered notes around it are numbered as if it weren't there. This
+    /// is useful for notes like source attributions. An unnumbered footnote
+    /// can't be referenced by another footnote.
+    ///
+    /// ```example
+    /// Numbered #footnote[First note]
+    /// and unnumbered #footnote(numbered: false)[Source: The archive]
+    /// notes #footnote[Second note]
+    /// ```
+    #[default(true)]
+    pub numbered: bool,
+
+    /// Whether the footnote entry must appear on the same page as the
+    /// footnote's reference.
+    ///
+    /// By default, an entry that doesn't fit on the page of its reference is
+    /// moved to or continued on the next page. With this option enabled, the
+    /// paragraph line holding the reference is moved to the next page together
+    /// with the entry if possible. An entry that has to wait for earlier
+    /// entries may still continue in a later column of the same page. If the
+    /// entry still can't be placed in full next to its reference, compilation
+    /// fails with an error instead.
+    ///
+    /// ```example
+    /// #set footnote(strict-page: true)
+    ///
+    /// A strict note.
+    /// #footnote[Always on this page.]
+    /// ```
+    #[default(false)]
+    pub strict_page: bool,
+
+    /// How to align the footnote area horizontally within its column.
+    ///
+    /// By default, the footnote area spans the full width of the column. When
+    /// an alignment is given, the separator and the entries instead take up
+    /// only as much width as they need and the resulting area is aligned
+    /// within the column. This is useful for designs where the footnotes
+    /// should line up with an offset part of the text block.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #set footnote(area-align: right)
+    ///
+    /// Right-aligned
+    /// #footnote[A short note.]
+    /// ```
+    pub area_align: Smart<HAlignment>,
+
+    /// In how many columns to arrange the footnote entries.
+    ///
+    /// The footnote area can have its own columns, independently of those of
+    /// the text. This is common in critical editions, where a single column of
+    /// text is accompanied by many short notes. The entries fill the columns
+    /// one after another and are balanced such that the columns end up with
+    /// about the same height. An entry that doesn't fit on the page continues
+    /// in the footnote columns of the next page.
+    ///
+    /// ```example
+    /// #set page(height: 120pt)
+    /// #set footnote(columns: 2)
+    ///
+    /// Some text#footnote[First.]
+    /// with notes#footnote[Second.]
+    /// in two#footnote[Third.]
+    /// columns.#footnote[Fourth.]
+    /// ```
+    #[default(NonZeroUsize::ONE)]
+    pub columns: NonZeroUsize,
+
+    /// The size of the gutter space between the footnote area's columns.
+    ///
+    /// Has no effect if there is only one @footnote.columns[column].
+    #[default(Ratio::new(0.04).into())]
+    pub column_gutter: Rel<Length>,
+
+    /// The minimum number of lines of text that precede the footnote area on a
+    /// page.
+    ///
+    /// A long footnote referenced early on a page can otherwise take up so
+    /// much space that only a single line of text remains above it. When the
+    /// page so far holds fewer lines than this, the footnote entries leave
+    /// room for the missing ones and continue on the next page instead. If
+    /// the entry can't be broken, the line referencing it moves to the next
+    /// page along with it.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #set footnote(lines-above: 3)
+    ///
+    /// Text#footnote(lorem(30)) with a long note.
+    /// #lorem(10)
+    /// ```
+    #[default(NonZeroUsize::ONE)]
+    pub lines_above: NonZeroUsize,
+
+    /// Where footnote entries continue in multi-column layouts if they don't
+    /// fit into the column of their reference.
+    ///
+    /// Each column has its own footnote area at its bottom. An entry that
+    /// doesn't fully fit into it is continued in a later footnote area, as are
+    /// entries that don't fit at all.
+    ///
+    /// - `{"next-column"}`: The entry continues in the next column, which may
+    ///   be on the same page.
+    /// - `{"same-column"}`: The entry continues in the same column of the next
+    ///   page. This keeps each column's notes below that column, as is common
+    ///   in multi-column journals.
+    ///
+    /// ```example
+    /// #set page(height: 100pt, columns: 2)
+    /// #set footnote(continuation: "same-column")
+    ///
+    /// Text#footnote(lorem(20))
+    /// #lorem(20)
+    /// ```
+    pub continuation: FootnoteContinuation,
+
     /// The content to put into the footnote. Can also be the label of another
     /// footnote this one should point to.
     #[required]
@@ -89,6 +272,12 @@ pub struct FootnoteElem {
 impl FootnoteElem {
     #[elem]
     type FootnoteEntry;
+
+    #[elem]
+    type FootnoteTally;
+
+    #[elem]
+    type FootnoteArea;
 }
 
 impl LocalName for Packed<FootnoteElem> {
@@ -125,6 +314,12 @@ impl FootnoteElem {
         matches!(self.body, FootnoteBody::Reference(_))
     }
 
+    /// Tests if this footnote takes up a number, i.e. if it is a numbered
+    /// footnote that isn't a reference to another one.
+    pub fn is_counted(&self) -> bool {
+        !self.is_ref() && self.numbered.get(StyleChain::default())
+    }
+
     /// Returns the content of the body of this footnote if it is not a ref.
     pub fn body_content(&self) -> Option<&Content> {
         match &self.body {
@@ -145,13 +340,72 @@ impl Packed<FootnoteElem> {
         let span = self.span();
         let loc = self.declaration_location(engine).at(span)?;
         let numbering = self.numbering.get_ref(styles);
-        let counter = Counter::of(FootnoteElem::ELEM);
-        let num = counter.display_at(engine, loc, styles, numbering, span)?;
+        let num = self.display_number(engine, loc, styles, numbering)?;
         let alt = FootnoteElem::alt_text(styles, &num.plain_text());
         let dest = loc.variant(1);
         Ok(DirectLinkElem::new(dest, num, Some(alt)).pack().spanned(span))
     }
 
+    /// Displays the number of the footnote declared at the given location.
+    ///
+    /// Footnotes without a series are numbered by the footnote counter, while
+    /// footnotes in a series are numbered by their position within it.
+    pub fn display_number(
+        &self,
+        engine: &mut Engine,
+        loc: Location,
+        styles: StyleChain,
+        numbering: &Numbering,
+    ) -> SourceResult<Content> {
+        let span = self.span();
+        let declaration = engine
+            .introspect(QueryFirstIntrospection(Selector::Location(loc), span))
+            .and_then(|elem| elem.into_packed::<FootnoteElem>().ok());
+        if declaration
+            .as_ref()
+            .is_some_and(|note| !note.numbered.get(StyleChain::default()))
+        {
+            bail!(span, "cannot reference an unnumbered footnote");
+        }
+
+        let series = declaration
+            .as_ref()
+            .and_then(|note| note.series.get_cloned(StyleChain::default()));
+        // Like the numbering, whether to number per section is resolved for
+        // this footnote, which may be a reference to another one.
+        let per_section = self.per_section.get(styles);
+
+        let context = Context::new(Some(loc), Some(styles));
+        if per_section {
+            let number =
+                engine.introspect(FootnoteNumberIntrospection(loc, series, true, span));
+            let section = section_number(engine, loc, span)?;
+            return Ok(display_in_section(
+                engine,
+                context.track(),
+                span,
+                numbering,
+                section,
+                number,
+            )?
+            .display());
+        }
+
+        let Some(series) = series else {
+            let counter = Counter::of(FootnoteElem::ELEM);
+            return counter.display_at(engine, loc, styles, numbering, span);
+        };
+
+        let number = engine.introspect(FootnoteNumberIntrospection(
+            loc,
+            Some(series),
+            false,
+            span,
+        ));
+
+        Ok(numbering.apply(engine, context.track(), span, &[number])?.display())
+    }
+
     /// Returns the location of the definition of this footnote.
     pub fn declaration_location(&self, engine: &mut Engine) -> StrResult<Location> {
         match self.body {
@@ -173,8 +427,156 @@ impl Packed<FootnoteElem> {
 
 impl Count for Packed<FootnoteElem> {
     fn update(&self) -> Option<CounterUpdate> {
-        (!self.is_ref()).then(|| CounterUpdate::Step(NonZeroUsize::ONE))
+        // Footnotes in a series are numbered separately.
+        (self.is_counted() && self.series.get_ref(StyleChain::default()).is_none())
+            .then(|| CounterUpdate::Step(NonZeroUsize::ONE))
+    }
+}
+
+/// Retrieves the number of a footnote within its series, optionally counted
+/// anew in each top-level section.
+#[derive(Debug, Clone, PartialEq, Hash)]
+struct FootnoteNumberIntrospection(Location, Option<EcoString>, bool, Span);
+
+impl Introspect for FootnoteNumberIntrospection {
+    type Output = u64;
+
+    fn introspect(
+        &self,
+        _: &mut Engine,
+        introspector: Tracked<dyn Introspector + '_>,
+    ) -> Self::Output {
+        let Self(loc, series, per_section, _) = self;
+        series_numbers(introspector, series.clone(), *per_section)
+            .get(loc)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn diagnose(&self, history: &History<Self::Output>) -> SourceDiagnostic {
+        warning!(self.3, "footnote number did not stabilize")
+            .with_hint(history.hint("numbers", |n| eco_format!("{n}")))
+    }
+}
+
+/// Numbers the counted footnotes of a series in document order. With
+/// `per_section`, the numbers restart at each numbered top-level heading.
+///
+/// This is memoized, so that all footnotes of a series share a single pass
+/// over the document instead of each one counting its predecessors.
+#[comemo::memoize]
+fn series_numbers(
+    introspector: Tracked<dyn Introspector + '_>,
+    series: Option<EcoString>,
+    per_section: bool,
+) -> Arc<FxHashMap<Location, u64>> {
+    let default = StyleChain::default();
+    let selector = if per_section {
+        Selector::Or(eco_vec![FootnoteElem::ELEM.select(), HeadingElem::ELEM.select()])
+    } else {
+        FootnoteElem::ELEM.select()
+    };
+
+    let mut numbers = FxHashMap::default();
+    let mut number = 0;
+    for elem in introspector.query(&selector) {
+        if let Some(heading) = elem.to_packed::<HeadingElem>() {
+            if heading.resolve_level(default) == NonZeroUsize::ONE
+                && heading.numbering.get_ref(default).is_some()
+            {
+                number = 0;
+            }
+            continue;
+        }
+
+        let Some(note) = elem.to_packed::<FootnoteElem>() else { continue };
+        if note.is_counted() && note.series.get_ref(default).as_ref() == series.as_ref() {
+            number += 1;
+            if let Some(loc) = note.location() {
+                numbers.insert(loc, number);
+            }
+        }
+    }
+
+    Arc::new(numbers)
+}
+
+/// The number of the top-level section at the given location, or zero before
+/// the first numbered top-level heading.
+fn section_number(engine: &mut Engine, loc: Location, span: Span) -> SourceResult<u64> {
+    Ok(Counter::of(HeadingElem::ELEM)
+        .at(engine, Context::none().track(), span, loc.into())?
+        .first())
+}
+
+/// Displays a footnote number within a section with the given numbering,
+/// prefixed with the section's number unless it is zero.
+pub(crate) fn display_in_section(
+    engine: &mut Engine,
+    context: Tracked<Context>,
+    span: Span,
+    numbering: &Numbering,
+    section: u64,
+    number: u64,
+) -> SourceResult<Value> {
+    let value = numbering.apply(engine, context, span, &[number])?;
+    if section == 0 {
+        return Ok(value);
     }
+
+    let prefix = eco_format!("{section}.");
+    Ok(match value {
+        Value::Str(number) => Value::Str(eco_format!("{prefix}{number}").into()),
+        value => Value::Content(TextElem::packed(prefix) + value.display()),
+    })
+}
+
+/// The state of the footnote counter within the current section for
+/// per-section numbering.
+///
+/// This consists of the section's number and the number of counted footnotes
+/// since the section's start, given the `state` of the footnote counter at
+/// the same location.
+pub(crate) fn section_state(
+    engine: &mut Engine,
+    loc: Location,
+    span: Span,
+    state: &CounterState,
+) -> SourceResult<(u64, u64)> {
+    let section = section_number(engine, loc, span)?;
+    let default = StyleChain::default();
+    let start = engine
+        .introspect(QueryIntrospection(
+            HeadingElem::ELEM.select().before(loc.into(), true),
+            span,
+        ))
+        .iter()
+        .rev()
+        .filter_map(|elem| elem.to_packed::<HeadingElem>())
+        .find(|heading| {
+            heading.resolve_level(default) == NonZeroUsize::ONE
+                && heading.numbering.get_ref(default).is_some()
+        })
+        .and_then(|heading| heading.location());
+
+    let before = match start {
+        Some(start) => Counter::of(FootnoteElem::ELEM)
+            .at(engine, Context::none().track(), span, start.into())?
+            .first(),
+        None => 0,
+    };
+
+    Ok((section, state.first().saturating_sub(before)))
+}
+
+/// Where footnote entries that don't fit into their column continue.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Cast)]
+pub enum FootnoteContinuation {
+    /// In the next column, which may be on the same page.
+    #[default]
+    NextColumn,
+    /// In the same column of the next page.
+    SameColumn,
 }
 
 /// The body of a footnote can be either some content or a label referencing
@@ -208,6 +610,11 @@ cast! {
 /// has red text!
 /// ```
 ///
+/// Such rules only apply to the entry in the listing, including its number.
+/// The marker in the document body is not affected and can be styled
+/// independently with a show rule on @footnote, for example to give the
+/// entries a smaller text size than the markers.
+///
 /// _Note:_ Footnote entry properties must be uniform across each page run (a
 /// page run is a sequence of pages without an explicit pagebreak in between).
 /// For this reason, set and show rules for footnote entries should be defined
@@ -237,6 +644,30 @@ pub struct FootnoteEntry {
     #[required]
     pub note: Packed<FootnoteElem>,
 
+    /// The number of the line that holds the footnote's marker.
+    ///
+    /// This is only available if the lines of the paragraph holding the
+    /// marker are numbered through @par.line.numbering. Since the line is only
+    /// known after the page is laid out, it takes an additional layout pass
+    /// for this to be available and up to date, for example when the marker
+    /// moved to a different line.
+    ///
+    /// ```example
+    /// >>> #set page(margin: (left: 3em))
+    /// #set par.line(numbering: "1")
+    /// #show footnote.entry: it => {
+    ///   let line = it.at("line", default: none)
+    ///   if line != none [Line #line: ]
+    ///   it.note.body
+    /// }
+    ///
+    /// A claim. \
+    /// Another claim.
+    /// #footnote[See the literature.]
+    /// ```
+    #[synthesized]
+    pub line: usize,
+
     /// The separator between the document body and the footnote listing.
     ///
     /// ```example
@@ -261,6 +692,26 @@ pub struct FootnoteEntry {
     )]
     pub separator: Content,
 
+    /// A rule that precedes each footnote entry.
+    ///
+    /// In contrast to the @footnote.entry.separator[`separator`], which is
+    /// only shown once above the whole footnote listing, the rule is shown
+    /// above every single entry. If an entry breaks across pages, only its
+    /// first part is preceded by the rule.
+    ///
+    /// ```example
+    /// #set footnote.entry(
+    ///   separator: none,
+    ///   rule: line(length: 1em, stroke: 0.5pt),
+    /// )
+    ///
+    /// Each entry has its own
+    /// #footnote[Short rule above]
+    /// rule.
+    /// #footnote[And here again]
+    /// ```
+    pub rule: Option<Content>,
+
     /// The amount of clearance between the document body and the separator.
     ///
     /// ```example
@@ -274,6 +725,48 @@ pub struct FootnoteEntry {
     #[default(Em::new(1.0).into())]
     pub clearance: Length,
 
+    /// Whether the clearance collapses with whitespace at the end of the
+    /// document body.
+    ///
+    /// By default, the full clearance is added below the body, even if the
+    /// body ends with spacing. When this is enabled, such trailing spacing
+    /// counts towards the clearance, so that the footnotes follow the body more
+    /// tightly. This only has an effect where the footnotes directly follow
+    /// the body, i.e. on pages with `{auto}` height. On pages with a fixed
+    /// height, the footnotes are always at the bottom of the page.
+    ///
+    /// ```example
+    /// #set page(height: auto)
+    /// #set footnote.entry(collapse-clearance: true)
+    ///
+    /// Dense typesetting.
+    /// #footnote[Close to the body.]
+    /// #v(1em)
+    /// ```
+    #[default(false)]
+    pub collapse_clearance: bool,
+
+    /// The space around the separator.
+    ///
+    /// The top and bottom insets are added to the
+    /// @footnote.entry.clearance[`clearance`] above the separator and the
+    /// @footnote.entry.gap[`gap`] below it, respectively. This allows to
+    /// fine-tune the spacing of the separator without affecting the spacing
+    /// of the document body or between the entries. The left and right insets
+    /// shift and shrink the separator horizontally.
+    ///
+    /// ```example
+    /// #set footnote.entry(
+    ///   separator-inset: (top: 4pt, bottom: 6pt),
+    /// )
+    ///
+    /// A separator with
+    /// #footnote[Some room]
+    /// some room.
+    /// ```
+    #[fold]
+    pub separator_inset: Sides<Option<Length>>,
+
     /// The gap between footnote entries.
     ///
     /// ```example
@@ -297,6 +790,113 @@ pub struct FootnoteEntry {
     /// ```
     #[default(Em::new(1.0).into())]
     pub indent: Length,
+
+    /// The content between an entry's number and its text.
+    ///
+    /// When `{auto}`, the number is followed by a small amount of space.
+    /// Otherwise, the given content is inserted instead, for example a period
+    /// or some horizontal spacing.
+    ///
+    /// ```example
+    /// #set footnote.entry(
+    ///   separator-between-number-and-text: [.#h(0.3em)],
+    /// )
+    ///
+    /// Footnotes:
+    /// #footnote[Separated],
+    /// #footnote[Clearly]
+    /// ```
+    pub separator_between_number_and_text: Smart<Content>,
+
+    /// The width of the box holding an entry's number.
+    ///
+    /// By default, the number takes up as much space as it needs. When a width
+    /// is given, the number is put into a box of that width, within which it
+    /// is aligned according to @footnote.entry.number-align[`number-align`].
+    /// This lines up the text of all entries, even if their numbers differ in
+    /// width, as some citation styles require.
+    ///
+    /// ```example
+    /// #set footnote(numbering: "1")
+    /// #set footnote.entry(number-width: 1.2em)
+    ///
+    /// #for i in range(10) [#footnote[Note #(i + 1).]]
+    /// ```
+    pub number_width: Option<Length>,
+
+    /// How to align an entry's number within its box.
+    ///
+    /// Has no effect if no @footnote.entry.number-width[`number-width`] is
+    /// set.
+    ///
+    /// ```example
+    /// #set footnote.entry(
+    ///   number-width: 1.5em,
+    ///   number-align: left,
+    /// )
+    ///
+    /// Footnotes:
+    /// #footnote[Left],
+    /// #footnote[Aligned]
+    /// ```
+    #[default(HAlignment::End)]
+    pub number_align: HAlignment,
+
+    /// How far the entries of nested footnotes are indented.
+    ///
+    /// A footnote within the body of another footnote is listed as a
+    /// sub-entry directly below the entry of its parent. Each level of nesting
+    /// shifts the entry further to the right by this amount. Nested footnotes
+    /// are numbered in the same sequence as all other footnotes.
+    ///
+    /// ```example
+    /// #set footnote.entry(nested-indent: 1em)
+    ///
+    /// Footnotes
+    /// #footnote[Outer
+    ///   #footnote[Inner]]
+    /// can nest.
+    /// ```
+    #[default(Length::zero())]
+    pub nested_indent: Length,
+
+    /// Whether a footnote entry may be split across pages.
+    ///
+    /// By default, an entry that doesn't fit in full below its reference is
+    /// continued on the next page. When this is disabled, the paragraph line
+    /// holding the reference moves to the next page together with the entry
+    /// instead, if the entry then fits in full. Otherwise, the entry is split
+    /// as usual.
+    ///
+    /// ```example
+    /// #set footnote.entry(breakable: false)
+    ///
+    /// A note#footnote[Kept in one piece.]
+    /// ```
+    #[default(true)]
+    pub breakable: bool,
+
+    /// Whether to check that each footnote has a matching entry and vice
+    /// versa.
+    ///
+    /// When enabled, the footnote markers and entries of each page run are
+    /// cross-checked once it is laid out. A footnote whose entry didn't end up
+    /// in the same page run, including a label-based footnote whose target
+    /// isn't a placed footnote, and an entry whose footnote marker is missing
+    /// produce an error. This is a safeguard for documents with many
+    /// label-based footnotes, where such mismatches are easy to miss.
+    ///
+    /// This has no effect in @document.draft[draft mode], where no entries
+    /// are placed.
+    ///
+    /// ```example
+    /// #set footnote.entry(validate: true)
+    ///
+    /// A note#footnote[Checked.] <note>
+    /// and a reference to it.#footnote(<note>)
+    /// ```
+    #[default(false)]
+    pub validate: bool,
 }
 
 impl Packed<FootnoteEntry> {
@@ -310,7 +910,6 @@ impl Packed<FootnoteEntry> {
         let span = self.span();
         let default = StyleChain::default();
         let numbering = self.note.numbering.get_ref(default);
-        let counter = Counter::of(FootnoteElem::ELEM);
         let Some(dest) = self.note.location() else {
             bail!(
                 self.span(), "footnote entry must have a location";
@@ -318,11 +917,17 @@ impl Packed<FootnoteEntry> {
             );
         };
 
-        let num = counter.display_at(engine, dest, styles, numbering, span)?;
+        let body = self.note.body_content().unwrap().clone();
+
+        // An unnumbered note's entry has no number to link back with.
+        if !self.note.numbered.get(default) {
+            return Ok((Content::empty(), body));
+        }
+
+        let num = self.note.display_number(engine, dest, styles, numbering)?;
         let alt = num.plain_text();
         let link = DirectLinkElem::new(dest, num, Some(alt)).pack().spanned(span);
         let sup = SuperElem::new(link).pack().spanned(span);
-        let body = self.note.body_content().unwrap().clone();
 
         Ok((sup, body))
     }
@@ -342,6 +947,111 @@ cast! {
     v: Content => v.unpack::<Self>().unwrap_or_else(Self::with_content)
 }
 
+/// A record of how many footnote entries were placed in a region.
+///
+/// The layout emits one tally for each region (typically a page) that holds
+/// footnote entries. Tallies cannot be created manually, but they can be
+/// queried, for example to detect pages that are overloaded with notes. An
+/// entry that breaks across regions counts as new in the region where it
+/// starts and as continued in each following one.
+///
+/// ```example
+/// #set page(height: 100pt)
+///
+/// A #footnote[One] and
+/// B #footnote[Two].
+///
+/// #context for tally in query(footnote.tally) [
+///   Page #tally.location().page():
+///   #tally.new new,
+///   #tally.continued continued
+/// ]
+/// ```
+#[elem(name = "tally", title = "Footnote Tally", since = "0.16.0", Construct, Locatable)]
+pub struct FootnoteTally {
+    /// The number of footnote entries that start in the region.
+    #[required]
+    pub new: usize,
+
+    /// The number of footnote entries continued from a previous region.
+    #[required]
+    pub continued: usize,
+}
+
+impl Construct for FootnoteTally {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
+
+/// A record of where the footnote area of a region ended up.
+///
+/// The layout emits one record for each footnote area, that is, for each
+/// column (typically a page) that holds footnote entries. The area spans from
+/// the top of the separator to the bottom of the lowest entry and covers the
+/// full width of the column, unless the area is aligned, in which case it is
+/// as wide as its widest entry.
+///
+/// The record is located at the top-left corner of the area. Its
+/// @location.position[position] is thus the offset of the area from the
+/// top-left corner of the page, in the same coordinate system as the positions
+/// of all other elements. Together with the area's width and height, this
+/// allows external tools to align annotations with the notes. Records cannot be
+/// created manually.
+///
+/// ```example
+/// #set page(height: 100pt)
+/// Text #footnote[A note].
+///
+/// #context for area in query(footnote.area) {
+///   let pos = area.location().position()
+///   [Area at #pos.y, #area.height tall]
+/// }
+/// ```
+#[elem(name = "area", title = "Footnote Area", since = "0.16.0", Construct, Locatable)]
+pub struct FootnoteArea {
+    /// The width of the area.
+    #[required]
+    pub width: Length,
+
+    /// The height of the area.
+    #[required]
+    pub height: Length,
+}
+
+impl Construct for FootnoteArea {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
+
+/// Records the number of the line that holds a footnote's marker.
+///
+/// The root flow emits this when it lays out line numbers. It is located at a
+/// well-known variant of the footnote's location, such that the footnote's
+/// entry can look it up without a query.
+#[elem(Construct, Locatable)]
+pub struct FootnoteLine {
+    /// The number of the line.
+    #[internal]
+    #[required]
+    pub line: usize,
+}
+
+impl FootnoteLine {
+    /// The location of the line record for the footnote with the given
+    /// location.
+    pub fn location_of(note: Location) -> Location {
+        note.variant(2)
+    }
+}
+
+impl Construct for FootnoteLine {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
+
 /// In HTML export, this is inserted at the end of the body to display
 /// footnotes. In the future, we can expose this to allow customizing where the
 /// footnotes appear. It could also be exposed for paged export.
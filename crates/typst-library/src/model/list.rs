@@ -107,6 +107,22 @@ pub struct ListElem {
     /// lists and paragraph @par.spacing[`spacing`] for wide (non-tight) lists.
     pub spacing: Smart<Length>,
 
+    /// Whether a single item should be kept in one region.
+    ///
+    /// When enabled, an item that would be split across a page or column
+    /// boundary is moved to the next region as a whole if it fits there. Items
+    /// that are taller than a full region are still split.
+    ///
+    /// ```example
+    /// #set page(height: 100pt)
+    /// #set list(keep-items-together: true)
+    /// #v(40pt)
+    /// - First item
+    /// - #lorem(15)
+    /// ```
+    #[default(false)]
+    pub keep_items_together: bool,
+
     /// Alignment to use for list markers.
     ///
     /// Vertical alignment is always relative to the height of the list items
@@ -1,12 +1,15 @@
-use ecow::eco_format;
-use typst_utils::singleton;
+use std::num::NonZeroUsize;
 
-use crate::diag::{HintedStrResult, SourceResult, StrResult, bail};
+use comemo::Track;
+use ecow::{EcoString, eco_format};
+use typst_utils::{NonZeroExt, singleton};
+
+use crate::diag::{At, HintedStrResult, SourceResult, StrResult, bail};
 use crate::engine::Engine;
 use crate::foundations::{
-    AlternativeFold, Args, Cast, CastInfo, Construct, Content, Dict, Fold, FromValue,
-    IntoValue, NativeElement, Packed, Reflect, Smart, Unlabellable, Value, cast, dict,
-    elem, scope,
+    AlternativeFold, Args, Cast, CastInfo, Construct, Content, Context, Dict, Fold,
+    FromValue, Func, IntoValue, NativeElement, Packed, Reflect, Resolve, Smart,
+    StyleChain, Unlabellable, Value, cast, dict, elem, scope,
 };
 use crate::introspection::{Count, CounterUpdate};
 use crate::layout::{Abs, Em, HAlignment, Length, OuterHAlignment, Ratio, Rel};
@@ -374,6 +377,29 @@ pub struct ParElem {
     /// ```
     pub linebreaks: Smart<Linebreaks>,
 
+    /// Whether to avoid a last line that consists of just a single word.
+    ///
+    /// When enabled and the last line of the paragraph would hold only one
+    /// word, the last word of the preceding line is moved down to keep it
+    /// company, provided that it still fits. This never changes the number of
+    /// lines, so it doesn't interact with widow and orphan prevention.
+    ///
+    /// This requires an additional pass over the paragraph's break
+    /// opportunities and relayout of the last two lines, so it is off by
+    /// default.
+    ///
+    /// ```example
+    /// #set page(width: 160pt)
+    /// This paragraph ends with a
+    /// rather lonely word.
+    ///
+    /// #set par(balance-last-line: true)
+    /// This paragraph ends with a
+    /// rather lonely word.
+    /// ```
+    #[default(false)]
+    pub balance_last_line: bool,
+
     /// The indent the first line of a paragraph should have.
     ///
     /// By default, only the first line of a consecutive paragraph will be
@@ -809,6 +835,21 @@ pub struct ParLine {
     ///
     /// #lorem(60)
     /// ```
+    ///
+    /// A function that takes two positional parameters additionally receives
+    /// the absolute line number as its second argument. It is counted across
+    /// the whole document, even if the displayed numbers are
+    /// @par.line.numbering-scope[reset on each page].
+    ///
+    /// ```example
+    /// >>> #set page(width: 200pt, height: 80pt, margin: (left: 4em))
+    /// #set par.line(
+    ///   numbering: (n, total) => [#n (#total)],
+    ///   numbering-scope: "page",
+    /// )
+    ///
+    /// #lorem(30)
+    /// ```
     #[ghost]
     pub numbering: Option<Numbering>,
 
@@ -898,6 +939,106 @@ pub struct ParLine {
     #[ghost]
     #[default(LineNumberingScope::Document)]
     pub numbering_scope: LineNumberingScope,
+
+    /// Which lines receive a line number.
+    ///
+    /// By default, all lines are numbered. Lines that are skipped receive no
+    /// number and don't advance the line counter.
+    ///
+    /// - A length or ratio only numbers lines whose content is at least as
+    ///   wide. A ratio is relative to the width available to the line.
+    /// - A function receives a dictionary with the `width` of the line's
+    ///   content, the `full` width available to the line, and the line's
+    ///   plain `text`. It must return whether to number the line.
+    ///
+    /// ```example
+    /// >>> #set page(width: 200pt, margin: (left: 3em))
+    /// #set par.line(
+    ///   numbering: "1",
+    ///   number-lines: 50%,
+    /// )
+    ///
+    /// #lorem(20) \
+    /// Short line. \
+    /// #lorem(10)
+    /// ```
+    ///
+    /// ```example
+    /// >>> #set page(width: 200pt, margin: (left: 3em))
+    /// #set par.line(
+    ///   numbering: "1",
+    ///   number-lines: line => line.text.trim() != "",
+    /// )
+    ///
+    /// Text before \
+    /// $ x^2 $ \
+    /// Text after
+    /// ```
+    #[ghost]
+    pub number_lines: Smart<LineNumberFilter>,
+
+    /// Only display the number of every n-th line.
+    ///
+    /// All numbered lines still advance the line counter, but only those whose
+    /// number is a multiple of the step display it. With a step of `{2}`, only
+    /// even lines are numbered. Lines skipped through
+    /// @par.line.number-lines[`number-lines`] don't count. When line numbers
+    /// are reset on every page, the counting starts anew on each page as well.
+    ///
+    /// ```example
+    /// >>> #set page(margin: (left: 3em))
+    /// #set par.line(
+    ///   numbering: "1",
+    ///   number-step: 5,
+    /// )
+    ///
+    /// #lorem(60)
+    /// ```
+    #[ghost]
+    #[default(NonZeroUsize::ONE)]
+    pub number_step: NonZeroUsize,
+
+    /// The width of the line number gutter, measured in digits.
+    ///
+    /// By default, the gutter is as wide as the widest line number in the
+    /// column, so its width may vary between columns and pages. With a fixed
+    /// width, the gutter is always at least as wide as this many zero digits
+    /// and the numbers are aligned within it according to
+    /// @par.line.number-align[`number-align`]. With the default alignment,
+    /// numbers are aligned towards the text, which mirrors for right-to-left
+    /// text.
+    ///
+    /// ```example
+    /// >>> #set page(margin: (left: 4em))
+    /// #set par.line(
+    ///   numbering: "1",
+    ///   numbering-width: 3,
+    /// )
+    ///
+    /// #lorem(20)
+    /// ```
+    #[ghost]
+    pub numbering_width: Option<NonZeroUsize>,
+
+    /// Whether to pad line numbers with leading zeros up to the
+    /// @par.line.numbering-width[`numbering-width`].
+    ///
+    /// This only applies to numberings that produce text, like numbering
+    /// patterns. Has no effect if no numbering width is set.
+    ///
+    /// ```example
+    /// >>> #set page(margin: (left: 4em))
+    /// #set par.line(
+    ///   numbering: "1",
+    ///   numbering-width: 3,
+    ///   number-pad: true,
+    /// )
+    ///
+    /// #lorem(20)
+    /// ```
+    #[ghost]
+    #[default(false)]
+    pub number_pad: bool,
 }
 
 impl Construct for ParLine {
@@ -921,6 +1062,52 @@ pub enum LineNumberingScope {
     Page,
 }
 
+/// Decides which lines receive a line number.
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub enum LineNumberFilter {
+    /// Numbers lines whose content is at least this wide.
+    Width(Rel<Length>),
+    /// Numbers lines for which the function returns `{true}`.
+    Func(Func),
+}
+
+impl LineNumberFilter {
+    /// Whether to number a line with the given content width, available
+    /// width, and plain text.
+    pub fn accepts(
+        &self,
+        engine: &mut Engine,
+        styles: StyleChain,
+        width: Abs,
+        full: Abs,
+        text: &str,
+    ) -> SourceResult<bool> {
+        match self {
+            Self::Width(min) => Ok(width.fits(min.resolve(styles).relative_to(full))),
+            Self::Func(func) => {
+                let line = dict! {
+                    "width" => width,
+                    "full" => full,
+                    "text" => text,
+                };
+                func.call(engine, Context::none().track(), [line])?
+                    .cast()
+                    .at(func.span())
+            }
+        }
+    }
+}
+
+cast! {
+    LineNumberFilter,
+    self => match self {
+        Self::Width(v) => v.into_value(),
+        Self::Func(v) => v.into_value(),
+    },
+    v: Rel<Length> => Self::Width(v),
+    v: Func => Self::Func(v),
+}
+
 /// A marker used to indicate the presence of a line.
 ///
 /// This element is added to each line in a paragraph and later searched to find
@@ -942,6 +1129,38 @@ pub struct ParLineMarker {
     #[internal]
     #[required]
     pub number_clearance: Smart<Length>,
+
+    #[internal]
+    #[required]
+    pub number_lines: Smart<LineNumberFilter>,
+
+    #[internal]
+    #[required]
+    pub number_step: NonZeroUsize,
+
+    #[internal]
+    #[required]
+    pub numbering_width: Option<NonZeroUsize>,
+
+    #[internal]
+    #[required]
+    pub number_pad: bool,
+
+    /// The natural width of the line's content.
+    #[internal]
+    #[synthesized]
+    pub width: Abs,
+
+    /// The width available to the line.
+    #[internal]
+    #[synthesized]
+    pub full: Abs,
+
+    /// The plain text of the line. Only filled in if a function decides
+    /// which lines to number.
+    #[internal]
+    #[synthesized]
+    pub text: EcoString,
 }
 
 impl Construct for ParLineMarker {
@@ -956,3 +1175,23 @@ impl Count for Packed<ParLineMarker> {
         None
     }
 }
+
+/// The key for the counter of absolute line numbers.
+///
+/// In contrast to the line number counter, this counter is never reset, so
+/// that it reflects the number of numbered lines in the whole document.
+#[elem(Construct, Unqueriable, Locatable, Count)]
+pub struct ParLineTotal {}
+
+impl Construct for ParLineTotal {
+    fn construct(_: &mut Engine, args: &mut Args) -> SourceResult<Content> {
+        bail!(args.span, "cannot be constructed manually");
+    }
+}
+
+impl Count for Packed<ParLineTotal> {
+    fn update(&self) -> Option<CounterUpdate> {
+        // The counter is updated manually by the root flow.
+        None
+    }
+}
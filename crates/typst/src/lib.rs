@@ -94,8 +94,23 @@ where
     sink.values()
 }
 
+/// Compiles sources and returns the structured dumps of internal layout state
+/// that were recorded during compilation.
+///
+/// Only available with the `debug-flow` feature, which is meant for testing.
+#[cfg(feature = "debug-flow")]
+#[typst_macros::time]
+pub fn dump<T>(world: &dyn World) -> Vec<EcoString>
+where
+    T: Output,
+{
+    let mut sink = Sink::new();
+    compile_impl::<T>(world.track(), Traced::default().track(), &mut sink).ok();
+    sink.dumps().to_vec()
+}
+
 /// The internal implementation of `compile` with a bit lower-level interface
-/// that is also used by `trace`.
+/// that is also used by `trace` and `dump`.
 fn compile_impl<T: Output>(
     world: Tracked<dyn World + '_>,
     traced: Tracked<Traced>,
@@ -0,0 +1,14 @@
+// A document with many footnotes for benchmarking how much footnote layout is
+// redone after an edit to the text above the footnotes:
+// `cargo testit bench --edit tests/bench/footnotes.typ`
+#set page(height: 200pt)
+
+/* edit */ #lorem(20)
+
+#for i in range(40) [
+  #lorem(30 + calc.rem(i * 13, 20))
+  #footnote[#lorem(10 + calc.rem(i * 7, 15))]
+  #lorem(10)
+  #footnote[#lorem(5)]
+
+]
@@ -0,0 +1,4 @@
+// The pagination benchmark with compact pagination, which explores
+// alternative page breaks to save pages.
+#set page(optimize: "compact")
+#include "pagination.typ"
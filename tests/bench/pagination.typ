@@ -0,0 +1,18 @@
+// A representative document for benchmarking pagination: Many pages of
+// numbered sections with paragraphs, lists, and figures.
+#set page(height: 200pt)
+#set heading(numbering: "1.")
+
+#for i in range(30) [
+  = Section #(i + 1)
+  #lorem(60 + calc.rem(i * 37, 50))
+
+  #if calc.rem(i, 3) == 0 [
+    - #lorem(8)
+    - #lorem(12)
+  ]
+
+  #if calc.rem(i, 4) == 0 {
+    figure(rect(width: 80%, height: 40pt), caption: lorem(6))
+  }
+]
@@ -146,6 +146,8 @@ pub enum Command {
     Pdftags(PdftagsCommand),
     /// Open the last generated HTML test report in a browser.
     OpenReport,
+    /// Measures how long it takes to compile a document.
+    Bench(BenchCommand),
 }
 
 #[derive(Debug, Clone, Parser)]
@@ -154,6 +156,20 @@ pub struct PdftagsCommand {
     pub path: PathBuf,
 }
 
+#[derive(Debug, Clone, Parser)]
+pub struct BenchCommand {
+    /// The Typst file to compile, relative to the repository root.
+    pub path: PathBuf,
+    /// How many times to compile the document.
+    #[arg(short = 'n', long, default_value_t = 10)]
+    pub iterations: usize,
+    /// Measures recompilation after an edit instead of compilation from
+    /// scratch. Before each iteration, a word is inserted where the document
+    /// contains `/* edit */`.
+    #[arg(long)]
+    pub edit: bool,
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, ValueEnum)]
 pub enum TestStage {
     Eval,
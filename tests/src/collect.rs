@@ -655,6 +655,7 @@ impl<'a> Parser<'a> {
             }
 
             if !ARGS.implied_stages().intersects(attrs.implied_stages())
+                || !features_enabled(&name)
                 || !selected(&name, self.path.canonicalize().unwrap())
             {
                 self.collector.skipped += 1;
@@ -797,6 +798,12 @@ impl<'a> Parser<'a> {
     }
 }
 
+/// Whether the features of the test crate that a test needs are enabled. Tests
+/// of flow events only check something with the `debug-flow` feature.
+fn features_enabled(name: &str) -> bool {
+    !name.starts_with("flow-events-") || cfg!(feature = "debug-flow")
+}
+
 /// Whether a test is within the selected set to run.
 fn selected(name: &str, abs: PathBuf) -> bool {
     static SKIPPED: LazyLock<FxHashSet<&'static str>> = LazyLock::new(|| {
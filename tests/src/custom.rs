@@ -1,11 +1,23 @@
 use std::fmt::Write;
+use std::num::NonZeroUsize;
 
+use comemo::Track;
 use typst::World;
-use typst::foundations::Smart;
-use typst::introspection::{Location, Tag};
-use typst::layout::{Frame, FrameItem};
+use typst::diag::SourceResult;
+use typst::engine::{Engine, Route, Sink, Traced};
+use typst::foundations::{Content, NativeElement, Smart, StyleChain};
+use typst::introspection::{EmptyIntrospector, Location, Locator, Tag};
+use typst::layout::{
+    Abs, Axes, BlockElem, Fragment, Frame, FrameItem, Length, Point, Ratio, Region,
+    Regions, Rel, Size, Sizing,
+};
 use typst::model::{Document, DocumentInfo};
+use typst::routines::Arenas;
+use typst::utils::Protected;
 use typst_layout::PagedDocument;
+use typst_layout::testing::{
+    ColumnOptions, FlowCursor, FlowMeasurement, FlowMode, FlowRegions,
+};
 
 use crate::collect::Test;
 use crate::world::TestWorld;
@@ -47,11 +59,278 @@ pub fn check(test: &Test, world: &TestWorld, doc: Option<&PagedDocument>) -> Str
                 sink.push_str("missing document");
             }
         }
+        "flow-baselines" => {
+            let baselines = doc
+                .and_then(|doc| doc.pages().first())
+                .map(|page| page.frame.baselines())
+                .unwrap_or_default();
+            test_eq!(sink, baselines, [10.0, 27.0, 44.0].map(Abs::pt));
+        }
+        "flow-layout-into" => {
+            // Content that doesn't fit into the given regions continues in
+            // regions of the last size.
+            let heights = layout(world, |engine, styles| {
+                let sizes = [size(100.0, 30.0), size(100.0, 50.0)];
+                let expand = Axes::splat(false);
+                typst_layout::testing::layout_flow_into(
+                    engine,
+                    &block(120.0),
+                    Locator::root(),
+                    styles,
+                    &sizes,
+                    expand,
+                )
+            });
+            test_eq!(sink, heights, [30.0, 50.0, 40.0]);
+        }
+        "flow-layout-shaped" => {
+            // Content that doesn't fit into the rectangles of one page
+            // continues on further pages.
+            let heights = layout(world, |engine, styles| {
+                let rects = [
+                    (Point::zero(), size(100.0, 40.0)),
+                    (Point::with_x(Abs::pt(100.0)), size(100.0, 40.0)),
+                ];
+                typst_layout::testing::layout_flow_shaped(
+                    engine,
+                    &block(120.0),
+                    Locator::root(),
+                    styles,
+                    size(200.0, 100.0),
+                    &rects,
+                )
+            });
+            test_eq!(sink, heights, [100.0, 100.0]);
+        }
+        "flow-layout-columns" => {
+            // The first region has a single column and the following ones have
+            // two, so the second block fits into the second region.
+            let heights = layout(world, |engine, styles| {
+                let blocks = [block(50.0), block(100.0)];
+                let children: Vec<_> =
+                    blocks.iter().map(|block| (block, styles)).collect();
+                let regions = Regions::repeat(size(200.0, 50.0), Axes::splat(false));
+                let two = ColumnOptions {
+                    count: NonZeroUsize::new(2).unwrap(),
+                    ..ColumnOptions::single()
+                };
+                typst_layout::testing::layout_flow_columns(
+                    engine,
+                    &children,
+                    &mut Locator::root().split(),
+                    styles,
+                    regions,
+                    ColumnOptions::single(),
+                    &[two],
+                    FlowMode::Block,
+                )
+            });
+            test_eq!(sink, heights, [50.0, 50.0]);
+        }
+        "flow-measure-intrinsic" => {
+            // A fixed width contributes to both widths, a relative width only
+            // to the maximum, where it is relative to the base.
+            let widths = run(world, |engine, styles| {
+                let width = |width: Rel<Length>| {
+                    BlockElem::new().with_width(Smart::Custom(width)).pack()
+                };
+                let content = Content::sequence([
+                    width(Length::from(Abs::pt(30.0)).into()),
+                    width(Ratio::new(0.5).into()),
+                ]);
+                typst_layout::testing::measure_intrinsic(
+                    engine,
+                    &content,
+                    Locator::root(),
+                    styles,
+                    Abs::pt(200.0),
+                )
+            });
+            test_eq!(sink, widths.ok(), Some((Abs::pt(30.0), Abs::pt(100.0))));
+        }
+        "flow-measure-flow" => {
+            // The content needs two further regions, with 70pt in total.
+            let measurement = run(world, |engine, styles| {
+                let region = Region::new(size(100.0, 50.0), Axes::splat(false));
+                typst_layout::testing::measure_flow(
+                    engine,
+                    &block(120.0),
+                    Locator::root(),
+                    styles,
+                    region,
+                )
+            });
+            test_eq!(
+                sink,
+                measurement.ok(),
+                Some(FlowMeasurement { regions: 3, overflow: Abs::pt(70.0) })
+            );
+        }
+        "flow-fit-to-region" => {
+            // The content is scaled down to at most the region's height, but
+            // not by much more than needed.
+            let heights = layout(world, |engine, styles| {
+                let region = Region::new(size(100.0, 50.0), Axes::splat(false));
+                typst_layout::testing::fit_to_region(
+                    engine,
+                    &block(120.0),
+                    Locator::root(),
+                    styles,
+                    region,
+                )
+                .map(Fragment::frame)
+            });
+            let fits = heights.len() == 1 && heights[0] <= 50.0 && heights[0] > 49.0;
+            test_eq!(sink, fits, true);
+        }
+        "flow-layout-synced" => {
+            // A break is forced before the third block, but not before the
+            // fourth one, which doesn't exist.
+            let heights = layout(world, |engine, styles| {
+                let content = Content::sequence([block(20.0), block(20.0), block(20.0)]);
+                let regions = Regions::repeat(size(100.0, 100.0), Axes::splat(false));
+                let (fragment, breaks) = typst_layout::testing::layout_flow_synced(
+                    engine,
+                    &content,
+                    Locator::root(),
+                    styles,
+                    regions,
+                    &[2, 3],
+                )?;
+                test_eq!(sink, breaks, [2]);
+                Ok(fragment)
+            });
+            test_eq!(sink, heights, [40.0, 20.0]);
+        }
+        "flow-regions-relayout" => {
+            // A region that is laid out again from its cursor is unchanged.
+            let heights = layout(world, |engine, styles| {
+                let content = block(120.0);
+                let children = [(&content, styles)];
+                let arenas = Arenas::default();
+                let regions = Regions::repeat(size(100.0, 50.0), Axes::splat(false));
+                let mut flow = FlowRegions::new(
+                    engine,
+                    &arenas,
+                    &children,
+                    Locator::root(),
+                    styles,
+                    regions,
+                    ColumnOptions::single(),
+                    FlowMode::Block,
+                    &[],
+                )?;
+                let (first, cursor) =
+                    flow.layout(engine, regions, &FlowCursor::default())?;
+                let (second, _) = flow.layout(engine, regions, &cursor)?;
+                let (again, cursor) = flow.layout(engine, regions, &cursor)?;
+                let (third, cursor) = flow.layout(engine, regions, &cursor)?;
+                test_eq!(sink, cursor.is_done(), true);
+                Ok(Fragment::frames(vec![first, second, again, third]))
+            });
+            test_eq!(sink, heights, [50.0, 50.0, 50.0, 20.0]);
+        }
+        "flow-regions-forced" => {
+            // Breaks are forced before the second and the third block. A flow
+            // created without them keeps them when resuming from a cursor.
+            let heights = layout(world, |engine, styles| {
+                let blocks = [block(20.0), block(20.0), block(20.0)];
+                let children: Vec<_> =
+                    blocks.iter().map(|block| (block, styles)).collect();
+                let arenas = Arenas::default();
+                let regions = Regions::repeat(size(100.0, 100.0), Axes::splat(false));
+                let flow = |engine: &mut Engine, breaks: &[usize]| {
+                    FlowRegions::new(
+                        engine,
+                        &arenas,
+                        &children,
+                        Locator::root(),
+                        styles,
+                        regions,
+                        ColumnOptions::single(),
+                        FlowMode::Block,
+                        breaks,
+                    )
+                };
+                let (first, cursor) = flow(engine, &[1, 2])?.layout(
+                    engine,
+                    regions,
+                    &FlowCursor::default(),
+                )?;
+                test_eq!(sink, cursor.sync_point(), Some(1));
+                let mut rest = flow(engine, &[])?;
+                let (second, cursor) = rest.layout(engine, regions, &cursor)?;
+                let (third, _) = rest.layout(engine, regions, &cursor)?;
+                Ok(Fragment::frames(vec![first, second, third]))
+            });
+            test_eq!(sink, heights, [20.0, 20.0, 20.0]);
+        }
+        #[cfg(feature = "debug-flow")]
+        "flow-events-colbreak" => {
+            test_eq!(sink, flow_events(world), ["finish(forced)", "region"]);
+        }
         _ => {}
     }
     sink
 }
 
+/// The control flow events of the flow that was laid out last. For documents
+/// without headers and footers, this is the root flow of the last page run.
+#[cfg(feature = "debug-flow")]
+fn flow_events(world: &TestWorld) -> Vec<String> {
+    typst::dump::<PagedDocument>(world)
+        .iter()
+        .rev()
+        .find_map(|dump| typst_layout::testing::flow_events(dump))
+        .unwrap_or_default()
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+/// Lays out content with a temporary engine and returns the heights of the
+/// resulting frames in points.
+fn layout(
+    world: &TestWorld,
+    f: impl FnOnce(&mut Engine, StyleChain) -> SourceResult<Fragment>,
+) -> Vec<f64> {
+    run(world, f)
+        .map(|fragment| fragment.iter().map(|frame| frame.height().to_pt()).collect())
+        .unwrap_or_default()
+}
+
+/// Runs a layout routine with a temporary engine and the default styles.
+fn run<T>(
+    world: &TestWorld,
+    f: impl FnOnce(&mut Engine, StyleChain) -> SourceResult<T>,
+) -> SourceResult<T> {
+    let introspector = EmptyIntrospector;
+    let traced = Traced::default();
+    let mut sink = Sink::new();
+    let mut engine = Engine {
+        library: world.library(),
+        world: (world as &dyn World).track(),
+        introspector: Protected::new(introspector.track()),
+        traced: traced.track(),
+        sink: sink.track_mut(),
+        route: Route::default(),
+    };
+
+    let styles = StyleChain::new(&world.library().styles);
+    f(&mut engine, styles)
+}
+
+/// A size given in points.
+fn size(width: f64, height: f64) -> Size {
+    Size::new(Abs::pt(width), Abs::pt(height))
+}
+
+/// A breakable block of the given height in points.
+fn block(height: f64) -> Content {
+    let height = Sizing::Rel(Length::from(Abs::pt(height)).into());
+    BlockElem::new().with_height(height).pack()
+}
+
 /// Extract the document information.
 fn info(doc: Option<&PagedDocument>) -> DocumentInfo {
     doc.map(|doc| doc.info().clone()).unwrap_or_default()
@@ -15,17 +15,21 @@ mod world;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::LazyLock;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use clap::Parser;
 use parking_lot::{Mutex, RwLock};
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use rustc_hash::FxHashMap;
+use typst::diag::Warned;
+use typst_layout::PagedDocument;
+use typst_syntax::{RootedPath, Source, VirtualPath, VirtualRoot};
 
-use crate::args::{CliArguments, Command, PdftagsCommand};
+use crate::args::{BenchCommand, CliArguments, Command, PdftagsCommand};
 use crate::collect::{Test, TestParseErrorKind};
 use crate::logger::{Logger, TestResult};
 use crate::output::{HASH_OUTPUTS, HashedRefs};
+use crate::world::TestWorld;
 
 /// The parsed command line arguments.
 static ARGS: LazyLock<CliArguments> = LazyLock::new(CliArguments::parse);
@@ -57,6 +61,7 @@ fn main() {
         Some(Command::Undangle) => undangle(),
         Some(Command::Pdftags(command)) => pdftags(command),
         Some(Command::OpenReport) => open_report(),
+        Some(Command::Bench(command)) => bench(command),
     }
 }
 
@@ -231,6 +236,76 @@ fn pdftags(command: &PdftagsCommand) {
     }
 }
 
+fn bench(command: &BenchCommand) {
+    let text = match std::fs::read_to_string(&command.path) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("error: {err}");
+            return;
+        }
+    };
+
+    let Ok(vpath) = VirtualPath::virtualize(Path::new(""), &command.path) else {
+        eprintln!("error: the path must be relative to the repository root");
+        return;
+    };
+    let edit = text.find("/* edit */");
+    if command.edit && edit.is_none() {
+        eprintln!("error: the document must contain `/* edit */`");
+        return;
+    }
+
+    let mut source =
+        Source::new(RootedPath::new(VirtualRoot::Project, vpath).intern(), text);
+    let compile = |source: &Source| {
+        let world = TestWorld::new(source.clone());
+        let start = Instant::now();
+        let Warned { output, .. } = typst::compile::<PagedDocument>(&world);
+        let duration = start.elapsed();
+        match output {
+            Ok(doc) => Some((doc.pages().len(), duration)),
+            Err(errors) => {
+                for error in errors {
+                    eprintln!("error: {}", error.message);
+                }
+                None
+            }
+        }
+    };
+
+    // When measuring recompilation, warm up the cache first.
+    comemo::evict(0);
+    if command.edit && compile(&source).is_none() {
+        return;
+    }
+
+    let mut pages = 0;
+    let mut durations = vec![];
+    for _ in 0..command.iterations.max(1) {
+        // Edit the document or, when measuring compilation from scratch,
+        // start with a cold cache, so that the iteration does all the work.
+        match edit {
+            Some(pos) if command.edit => {
+                source.edit(pos..pos, "edit ");
+            }
+            _ => comemo::evict(0),
+        }
+
+        let Some((count, duration)) = compile(&source) else { return };
+        pages = count;
+        durations.push(duration);
+    }
+
+    durations.sort();
+    println!(
+        "{}: {pages} pages, min {:?}, median {:?} ({} iterations)",
+        command.path.display(),
+        durations[0],
+        durations[durations.len() / 2],
+        durations.len(),
+    );
+}
+
 fn open_report() {
     let res = open::that("tests/store/report.html");
     if let Err(err) = res {
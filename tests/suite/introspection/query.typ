@@ -349,6 +349,35 @@ What's *up* with *you?*
   (1, "a", "b", "c", "d", 2)
 )
 
+--- query-order-reading paged ---
+// Test that results can be returned in reading order.
+#set page(height: 100pt, columns: 2)
+#metadata("a")
+#v(20pt)
+#metadata("b")
+#colbreak()
+#metadata("c")
+#place(top, float: true, scope: "parent", metadata("d"))
+#context {
+  let values(order) = query(metadata, order: order).map(it => it.value)
+  test(values("document"), ("a", "b", "c", "d"))
+  test(values("reading"), ("d", "a", "b", "c"))
+}
+
+--- query-order-reading-pages paged ---
+// Test that the page takes precedence over the column in reading order.
+#set page(height: 60pt, columns: 2)
+#metadata("a")
+#colbreak()
+#metadata("b")
+#pagebreak()
+#metadata("c")
+#context test(query(metadata, order: "reading").map(it => it.value), ("a", "b", "c"))
+
+--- query-order-invalid paged ---
+// Error: 32-40 expected "document" or "reading"
+#context query(heading, order: "visual")
+
 --- issue-3726-query-show-set paged ---
 // Test that show rules apply to queried elements, i.e. that the content
 // returned from `query` isn't yet marked as prepared.
@@ -219,6 +219,12 @@ B
 #pagebreak()
 #lorem(18)
 
+--- columns-balanced-multi-page paged ---
+// Only the columns on the last page are balanced.
+#set page(height: 80pt, columns: 2)
+#set columns(balanced: true)
+#lines(15)
+
 --- columns-balanced-figures paged ---
 #set page(width: 300pt, height: 190pt, margin: 5pt, columns: 3)
 #set columns(balanced: true)
@@ -275,3 +281,382 @@ B
   8 * [- #lorem(5)]
 }
 
+
+--- columns-sync-baselines paged ---
+#set page(height: 120pt, columns: 2)
+#set columns(sync-baselines: true)
+
+#lines(3)
+#text(1.5em)[Big]
+#lines(9)
+
+--- columns-sync-baselines-figure paged ---
+// The line after a figure is moved down onto the baseline grid.
+#set page(height: 120pt, columns: 2)
+#set columns(sync-baselines: true)
+
+#lines(2)
+#rect(width: 100%, height: 17pt)
+#lines(10)
+
+--- columns-gutter-em paged ---
+// The gutter can be relative to the font size.
+#set page(width: 120pt, height: 40pt, margin: 0pt)
+#set text(size: 5pt)
+#columns(2, gutter: 4em)[
+  A
+  #colbreak()
+  #context test(here().position().x, 70pt)
+]
+
+--- columns-gutter-em-page paged ---
+// The gutter of page-level columns is relative to the font size of the page.
+#set text(size: 10pt)
+#set page(width: 120pt, height: 40pt, margin: 0pt, columns: 2)
+#set columns(gutter: 2em)
+A
+#colbreak()
+#context test(here().position().x, 70pt)
+
+--- columns-gutter-outer-inner paged ---
+// The gaps next to the first and last column can differ from the inner ones.
+#set page(width: 120pt, height: 40pt, margin: 0pt, columns: 4)
+#set columns(gutter: (outer: 10pt, inner: 4pt), fill: aqua)
+A
+#colbreak()
+#context test(here().position().x, 34pt)
+#colbreak()
+#context test(here().position().x, 62pt)
+#colbreak()
+#context test(here().position().x, 96pt)
+
+--- columns-gutter-outer-inner-three paged empty ---
+// With three columns, both gaps are outer ones, so the layout is symmetric.
+#set page(width: 110pt, height: 40pt, margin: 0pt)
+#columns(3, gutter: (outer: 10pt, inner: 5pt))[
+  #context test(here().position().x, 0pt)
+  #colbreak()
+  #context test(here().position().x, 40pt)
+  #colbreak()
+  #context test(here().position().x, 80pt)
+]
+
+--- columns-gutter-outer-inner-four paged empty ---
+// With four columns, the gaps next to the first and last column are outer
+// ones.
+#set page(width: 145pt, height: 40pt, margin: 0pt)
+#columns(4, gutter: (outer: 10pt, inner: 5pt))[
+  #context test(here().position().x, 0pt)
+  #colbreak()
+  #context test(here().position().x, 40pt)
+  #colbreak()
+  #context test(here().position().x, 75pt)
+  #colbreak()
+  #context test(here().position().x, 115pt)
+]
+
+--- columns-gutter-outer-inner-field eval ---
+#set columns(gutter: (outer: 10pt, inner: 4pt))
+#context test(columns.gutter, (outer: 10pt, inner: 4pt))
+#set columns(gutter: (outer: 5pt, inner: 5pt))
+#context test(columns.gutter, 5pt)
+
+--- columns-gutter-missing-key eval ---
+// Error: 22-34 dictionary does not contain key "inner"
+#set columns(gutter: (outer: 1em))
+
+--- columns-gutter-unexpected-key eval ---
+// Error: 22-59 unexpected key "middle", valid keys are "outer" and "inner"
+#set columns(gutter: (outer: 1em, inner: 2pt, middle: 3pt))
+
+--- columns-lines-per-column paged ---
+// Each column ends after the given number of lines.
+#set page(height: 120pt, width: 160pt)
+#columns(3, gutter: 6pt, lines-per-column: 4)[
+  #for i in range(1, 11) [
+    Verse #i \
+  ]
+]
+
+--- columns-lines-per-column-page paged ---
+// Page-level columns also respect the line limit and continue on the next
+// page once all columns are full.
+#set page(height: 80pt, width: 120pt, columns: 2)
+#set columns(lines-per-column: 3)
+#for i in range(1, 10) [
+  Line #i \
+]
+
+--- columns-lines-per-column-blocks paged ---
+// Blocks don't count towards the limit, but still take up space.
+#set page(height: 120pt, width: 160pt, columns: 2)
+#set columns(lines-per-column: 3)
+A \ B
+#rect(height: 20pt, width: 100%)
+C \ D \ E \ F
+
+--- columns-lines-per-column-space-limited paged ---
+// A column still ends early if it runs out of space.
+#set page(height: 40pt, width: 120pt, columns: 2)
+#set columns(lines-per-column: 10)
+#lines(6)
+
+--- columns-align-narrow-block paged ---
+// Blocks narrower than a column are aligned within the column.
+#set page(height: 80pt, columns: 2)
+#align(center, rect(width: 50%, height: 10pt))
+#align(right, rect(width: 20pt, height: 10pt))
+#block(width: 20pt, height: 10pt, fill: aqua)
+#colbreak()
+#align(center, block(width: 20pt, height: 10pt, fill: aqua))
+#align(end, figure(rect(width: 20pt, height: 10pt)))
+
+--- columns-align-narrow-block-rtl paged ---
+// In right-to-left text, blocks start on the right of a column.
+#set page(height: 60pt, columns: 2)
+#set text(dir: rtl)
+#block(width: 20pt, height: 10pt, fill: aqua)
+#align(center, block(width: 20pt, height: 10pt, fill: aqua))
+#colbreak()
+#align(left, block(width: 20pt, height: 10pt, fill: aqua))
+
+--- columns-fill paged ---
+// Each column gets its own background, cycling through the array.
+#set page(height: 60pt, width: 120pt)
+#columns(3, fill: (luma(230), aqua))[
+  #lines(6)
+]
+
+--- columns-fill-func paged ---
+// A fill function receives the column and region index.
+#set page(height: 60pt, width: 120pt, columns: 2)
+#set columns(fill: (x, y) => if calc.even(x + y) { luma(230) })
+#lines(12)
+
+--- columns-rtl-order paged ---
+// In right-to-left text, the first column is on the right.
+#set page(width: 120pt, height: 40pt, margin: 0pt, columns: 2)
+#set columns(gutter: 20pt)
+#set text(dir: rtl)
+A
+#colbreak()
+#block(width: 100%) <second>
+#context test(locate(<second>).position().x, 0pt)
+
+--- columns-progression-btt paged ---
+// Content fills the columns from the bottom up.
+#set page(height: 60pt, width: 120pt, columns: 2)
+#set columns(progression: btt)
+#lines(7)
+
+--- columns-progression-btt-rtl paged ---
+// Bottom-up progression combines with right-to-left column order.
+#set page(height: 60pt, width: 120pt, columns: 2)
+#set columns(progression: btt)
+#set text(dir: rtl)
+#block(width: 20pt, height: 10pt, fill: aqua)
+#block(width: 20pt, height: 10pt, fill: conifer)
+#colbreak()
+#block(width: 20pt, height: 10pt, fill: eastern)
+
+--- columns-progression-position paged ---
+// The first line of a bottom-up column is at its bottom.
+#set page(height: 60pt, width: 120pt, margin: 0pt)
+#place(context test(locate(<first>).position().y, 50pt))
+#columns(2, progression: btt)[
+  #block(height: 10pt) <first>
+]
+
+--- columns-progression-horizontal eval ---
+// Error: 27-30 column progression must be vertical
+#set columns(progression: ltr)
+
+--- columns-block-too-wide paged ---
+#set page(height: 60pt, width: 120pt, columns: 2)
+// Warning: 2-35 block is wider than its column
+// Hint: 2-35 try using fewer or wider columns, or reduce the block's width
+#block(width: 100pt, height: 10pt)
+
+--- columns-colbreak-in-grid-cell paged ---
+// A column break in a grid cell breaks the enclosing columns.
+#set page(height: 80pt, width: 120pt, columns: 2)
+#grid(
+  columns: 2,
+  stroke: 0.5pt,
+  [A #colbreak() B], [C],
+)
+
+--- columns-colbreak-weak-in-grid-cell paged ---
+// A weak column break at the start of a grid cell still breaks if the
+// enclosing column already has content.
+#set page(height: 80pt, width: 120pt, columns: 2)
+Before
+#grid(
+  stroke: 0.5pt,
+  [
+    #colbreak(weak: true)
+    #context test(here().position().x > 50pt, true)
+    After
+  ],
+)
+
+--- columns-colbreak-weak-in-block-empty paged ---
+// A weak column break at the start of a block in an empty column is skipped.
+#set page(height: 80pt, width: 120pt, columns: 2)
+#block[
+  #colbreak(weak: true)
+  #context test(here().position().x < 50pt, true)
+  Start
+]
+
+--- columns-resume-after-full-width paged ---
+// Columns resume right below full-width content on the same page, even after
+// multiple interruptions.
+#set page(height: 200pt, width: 180pt)
+#columns(2)[#lines(2) #colbreak() #lines(2)]
+#block(width: 100%, fill: aqua)[Full width A]
+#columns(2)[#lines(2) #colbreak() #lines(2)]
+#block(width: 100%, fill: aqua)[Full width B]
+#columns(2)[#lines(2) #colbreak() #lines(2) #metadata(none) <end>]
+#context test(locate(<end>).page(), 1)
+
+--- columns-resume-after-parent-floats paged ---
+// Page columns start right below multiple parent-scoped floats and end above
+// a bottom one, all within the same page.
+#set page(height: 200pt, width: 180pt, columns: 2)
+#let band(fill) = rect(width: 100%, height: 20pt, fill: fill)
+#place(top, scope: "parent", float: true, band(aqua))
+#place(bottom, scope: "parent", float: true, band(eastern))
+#place(top, scope: "parent", float: true, band(conifer))
+#context test(here().position().y > 50pt, true)
+#lines(6)
+#metadata(none) <end>
+#context test(locate(<end>).page(), 1)
+
+--- columns-collapse-if-fits paged ---
+#set page(height: 120pt)
+#columns(2, collapse-if-fits: true)[
+  This text is short enough to fit into a single column.
+]
+
+--- columns-collapse-if-fits-too-long paged ---
+// The content doesn't fit into the rest of the page, so it is split into
+// columns as usual.
+#set page(height: 80pt)
+#columns(2, collapse-if-fits: true, lorem(40))
+
+--- columns-collapse-if-fits-count paged ---
+// The single-column content may span up to two pages.
+#set page(height: 80pt)
+#columns(2, collapse-if-fits: 2, lorem(40))
+
+--- columns-collapse-if-fits-zero paged ---
+// Error: 31-32 number must be positive
+#columns(2, collapse-if-fits: 0)[A]
+
+--- columns-max-height paged ---
+// Content beyond the maximum height spills into a new set of columns on the
+// next page.
+#set page(height: 120pt)
+#columns(2, max-height: 40pt, lorem(40))
+After.
+
+--- columns-max-height-clip paged ---
+// Content beyond the maximum height is cut off.
+#set page(height: 120pt)
+#columns(2, max-height: 40pt, overflow: "clip", lorem(40))
+After.
+
+--- columns-max-height-clip-introspection paged ---
+// Cut off content can still be located.
+#set page(height: 120pt)
+#columns(2, max-height: 20pt, overflow: "clip")[
+  #lorem(30)
+  #metadata("end") <end>
+]
+#context test(locate(<end>).page(), 1)
+
+--- columns-max-height-in-box paged ---
+#rect(columns(2, max-height: 30pt, overflow: "clip", lorem(20)))
+
+--- columns-gutter-fill paged ---
+#set page(height: 100pt, width: 180pt)
+#columns(
+  3,
+  gutter-fill: align(center, text(gray, 8pt)[✦]),
+  lorem(40),
+)
+
+--- columns-gutter-fill-clipped paged ---
+// The last tile is clipped at the bottom of the columns.
+#set page(height: 80pt, width: 150pt, columns: 2)
+#set columns(gutter-fill: align(center, rect(width: 2pt, height: 25pt, fill: aqua)))
+#lorem(30)
+
+--- columns-gutter-fill-content-overlap paged ---
+// The fill stops where the shorter column ends.
+#set page(height: 120pt, width: 150pt)
+#columns(
+  2,
+  gutter-fill: align(center, rect(width: 1pt, height: 6pt, fill: gray)),
+  gutter-fill-extent: "content-overlap",
+)[
+  #lorem(20)
+  #colbreak()
+  #lorem(5)
+]
+
+--- columns-width paged ---
+// Nested columns report the innermost width.
+#set page(width: 120pt, height: 100pt, margin: 10pt, columns: 2)
+#set columns(gutter: 10pt)
+#context test(columns.width(), 45pt)
+#columns(2, gutter: 5pt)[
+  #context test(columns.width(), 20pt)
+  #context line(length: columns.width())
+]
+
+--- columns-balance-here paged ---
+// The first column ends at the balance point, and the second one is at most
+// as tall.
+#set page(height: 150pt)
+#columns(2, balanced: true)[
+  #lorem(5)
+  #columns.balance-here()
+  #lorem(15)
+]
+#rect(width: 100%, height: 10pt, fill: aqua)
+
+--- columns-balance-here-unbalanced paged ---
+// Without balancing, the balance point has no effect.
+#set page(height: 100pt)
+#columns(2)[
+  #lorem(5)
+  #columns.balance-here()
+  #lorem(5)
+]
+
+--- columns-reserve-bottom paged ---
+// The reserved content is placed at the bottom of every region and is laid
+// out anew for each of them.
+#set page(height: 80pt, width: 120pt)
+#columns(
+  2,
+  reserve-bottom: 12pt,
+  reserved: [#metadata(none) <band> #line(length: 100%)],
+)[
+  #lorem(40)
+]
+
+#context {
+  let bands = query(<band>)
+  test(bands.len() > 1, true)
+  test(bands.map(it => it.location()).dedup().len(), bands.len())
+}
+
+--- columns-reserve-bottom-auto paged ---
+// Without a reserved height, the reserved content determines it.
+#set page(height: 80pt, width: 120pt)
+#columns(2, reserved: align(center)[_Continued_])[
+  #lorem(30)
+]
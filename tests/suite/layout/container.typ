@@ -212,6 +212,46 @@ C
 #block(sticky: true, lines(4))
 E
 
+--- block-keep paged ---
+// The three blocks don't fit below the lines, so they move to the next page
+// together, while keeping their spacing.
+#set page(height: 100pt)
+#lines(3)
+#block.keep[
+  #block[A] <a>
+  #block[B]
+  #block[C] <c>
+]
+D
+
+#context test(locate(<a>).page(), 2)
+#context test(locate(<c>).page(), 2)
+
+--- block-keep-fits paged ---
+// A group that fits stays where it is.
+#set page(height: 100pt)
+#lines(2)
+#block.keep[
+  #block[A] <a>
+  #block[B]
+  #block[C]
+]
+
+#context test(locate(<a>).page(), 1)
+
+--- block-keep-too-tall paged ---
+// A group that is taller than a page still breaks.
+#set page(height: 60pt)
+A
+#block.keep[
+  #block(height: 15pt, width: 100%, fill: aqua) <b>
+  #block(height: 15pt, width: 100%, fill: teal)
+  #block(height: 15pt, width: 100%, fill: aqua) <d>
+]
+
+#context test(locate(<b>).page(), 2)
+#context test(locate(<d>).page(), 3)
+
 --- box-clip-rect paged ---
 // Test box clipping with a rectangle
 Hello #box(width: 1em, height: 1em, clip: false)[#rect(width: 3em, height: 3em, fill: red)]
@@ -241,6 +281,19 @@ Emoji: #box(height: 0.5em, stroke: 1pt + black)[🐪, 🌋, 🏞]
 
 Emoji: #box(height: 0.5em, clip: true, stroke: 1pt + black)[🐪, 🌋, 🏞]
 
+--- block-fit-width paged ---
+// The wide table is scaled down to the page's width, the narrow one isn't.
+#set page(width: 200pt)
+#block(fit-width: true, table(columns: (60pt,) * 4, [A], [B], [C], [D]))
+#block(fit-width: true, table(columns: 2, [A], [B]))
+
+--- block-fit-width-illegible paged ---
+#set page(width: 100pt, margin: 10pt)
+// Warning: 2-73 block content was scaled down to 33% to fit
+// Hint: 2-73 the content may be hard to read
+// Hint: 2-73 try reducing the width of the content instead
+#block(fit-width: true, table(columns: (60pt,) * 4, [A], [B], [C], [D]))
+
 --- block-clipping-multiple-pages paged ---
 // Test block clipping over multiple pages.
 #set page(height: 60pt)
@@ -334,6 +387,84 @@ Paragraph
 --- box-block-html html ---
 Text #box(block(html.strong[A]))
 
+--- block-footer paged ---
+// Test that the footer is shown at the bottom of every region of a block.
+#set page(height: 100pt)
+#block(
+  width: 100%,
+  stroke: 0.5pt,
+  inset: 4pt,
+  footer: line(length: 100%, stroke: red),
+  lines(10),
+)
+
+--- block-footer-fixed-height paged ---
+// Test a footer in a block with a fixed height. The space for the footer is
+// taken from the block's body.
+#block(
+  width: 100%,
+  height: 40pt,
+  fill: aqua,
+  footer: align(right)[End],
+  lines(1),
+)
+
+--- block-footer-located paged ---
+// The footer is laid out anew for every region, so the elements within it
+// have a distinct location in each one.
+#set page(height: 60pt)
+#block(footer: [#metadata(none) <foot>], lines(8))
+
+#context {
+  let feet = query(<foot>)
+  test(feet.len() > 1, true)
+  test(feet.map(it => it.location()).dedup().len(), feet.len())
+}
+
+--- block-on-split-located paged ---
+// The split marker is laid out anew for every region it is shown in.
+#set page(height: 60pt)
+#block(on-split: [#metadata(none) <mark>], lines(8))
+
+#context {
+  let marks = query(<mark>)
+  test(marks.len() > 1, true)
+  test(marks.map(it => it.location()).dedup().len(), marks.len())
+}
+
+--- block-on-split paged ---
+// Test that the split marker is only shown where the block continues.
+#set page(height: 100pt)
+#block(
+  width: 100%,
+  stroke: 0.5pt,
+  inset: 4pt,
+  on-split: align(right, text(0.8em)[_(continued)_]),
+  lines(12),
+)
+
+--- block-on-split-no-split paged ---
+// Test that the split marker isn't shown if the block fits.
+#set page(height: 100pt)
+#block(
+  stroke: 0.5pt,
+  inset: 4pt,
+  on-split: text(red)[Split],
+  lines(2),
+)
+
+--- block-on-split-with-footer paged ---
+// Test that the split marker is placed above the footer.
+#set page(height: 100pt)
+#block(
+  width: 100%,
+  stroke: 0.5pt,
+  inset: 4pt,
+  on-split: align(right)[→],
+  footer: line(length: 100%, stroke: red),
+  lines(10),
+)
+
 --- block-box-html html ---
 Text #block(box(html.div[A]))
 Text #block(box(html.strong[B]))
@@ -462,3 +593,86 @@ c
   radius: 100%,
   rect(fill: gray, height: 1cm, width: 1cm),
 )
+
+--- block-inset-breakable-three-regions paged ---
+// The top inset only applies to the first and the bottom inset only to the
+// last fragment, while the side insets apply to all of them.
+#set page(height: 80pt)
+#block(
+  breakable: true,
+  inset: (x: 6pt, top: 12pt, bottom: 18pt),
+  fill: aqua,
+  stroke: 1pt,
+  lines(14),
+)
+
+--- block-inset-breakable-fixed-height paged ---
+#set page(height: 80pt)
+#block(
+  breakable: true,
+  height: 150pt,
+  inset: 8pt,
+  fill: aqua,
+  lines(8),
+)
+
+--- block-min-height paged ---
+// Short content is extended to the minimum height.
+#set block(stroke: 0.5pt, width: 100%)
+#block(min-height: 40pt)[Answer:]
+#block(min-height: 40pt, breakable: false)[Answer:]
+#block(min-height: 10pt)[#lines(3)]
+
+--- block-min-height-inset paged ---
+// The minimum height includes the inset.
+#block(min-height: 30pt, inset: 10pt, fill: aqua)[A]
+#context test(measure(block(min-height: 30pt, inset: 10pt)[A]).height, 30pt)
+
+--- block-min-height-break paged ---
+// The block moves to the next page because its minimum height doesn't fit.
+#set page(height: 100pt)
+#v(50pt)
+#block(min-height: 40pt, stroke: 0.5pt, width: 100%)[Answer:]
+
+--- block-min-height-split paged ---
+// The minimum height applies to all parts of a split block together.
+#set page(height: 80pt)
+#block(min-height: 120pt, stroke: 0.5pt, width: 100%, lines(5))
+
+--- block-too-tall-warning paged ---
+#set page(height: 60pt)
+// Warning: 2-52 block does not fit into any page
+// Hint: 2-52 it needs 100.0pt of height, but only 40.0pt are available
+// Hint: 2-52 try reducing its height or making it breakable with `block(breakable: true)`
+#block(height: 100pt, breakable: false, fill: aqua)
+
+--- block-header-located paged ---
+// The header is laid out anew for every region, so the elements within it
+// have a distinct location in each one.
+#set page(height: 60pt)
+#block(header: [#metadata(none) <head>], lines(8))
+
+#context {
+  let heads = query(<head>)
+  test(heads.len() > 1, true)
+  test(heads.map(it => it.location()).dedup().len(), heads.len())
+}
+
+--- block-header paged ---
+#set page(height: 100pt)
+#block(
+  stroke: 0.5pt,
+  inset: 4pt,
+  header: text(0.8em)[_Transcript_],
+  lorem(30),
+)
+
+--- block-header-footer paged ---
+// Header and footer both reserve space in every region.
+#set page(height: 90pt)
+#block(
+  fill: aqua.lighten(60%),
+  header: line(length: 100%),
+  footer: line(length: 100%),
+  lorem(30),
+)
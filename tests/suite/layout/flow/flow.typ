@@ -80,3 +80,47 @@ Hi
 --- issue-5024-spill-backlog paged ---
 #set page(columns: 2, height: 50pt)
 #columns(2)[Hello]
+
+--- flow-events-colbreak paged ---
+// Checks that the column break finishes the first column. Only runs with the
+// `debug-flow` feature.
+#set page(height: 60pt, columns: 2)
+A
+#colbreak()
+B
+
+--- flow-baselines paged ---
+// The custom check verifies the baseline positions of the page's lines.
+#set page(height: 60pt, margin: 0pt)
+#set text(top-edge: 10pt, bottom-edge: -2pt)
+#set par(leading: 5pt)
+A \
+B \
+C
+
+--- flow-layout-into paged empty ---
+// The custom check lays out content into individually sized regions.
+
+--- flow-layout-shaped paged empty ---
+// The custom check lays out content through several rectangles per page.
+
+--- flow-layout-columns paged empty ---
+// The custom check lays out content with a different column count per region.
+
+--- flow-measure-intrinsic paged empty ---
+// The custom check measures the intrinsic widths of content.
+
+--- flow-measure-flow paged empty ---
+// The custom check measures how content overflows a region.
+
+--- flow-fit-to-region paged empty ---
+// The custom check shrinks content to fit a single region.
+
+--- flow-layout-synced paged empty ---
+// The custom check lays out content with breaks synced to another flow.
+
+--- flow-regions-relayout paged empty ---
+// The custom check lays out a region of a flow again from its cursor.
+
+--- flow-regions-forced paged empty ---
+// The custom check resumes a flow with forced breaks from a cursor.
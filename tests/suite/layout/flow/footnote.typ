@@ -19,6 +19,17 @@ Fourth #footnote[F]
 A #footnote([I: ] + lines(6) + footnote[II])
 B #footnote[III]
 
+--- footnote-nested-indent paged ---
+#set footnote.entry(nested-indent: 1em)
+First #footnote[A, #footnote[B, #footnote[C]]]
+Second #footnote[D]
+
+--- footnote-nested-indent-break-across-pages paged ---
+#set page(height: 80pt)
+#set footnote.entry(nested-indent: 1em)
+A #footnote([I: ] + lines(6) + footnote[II])
+B #footnote[III]
+
 --- footnote-entry paged ---
 // Test customization.
 #show footnote: set text(red)
@@ -32,6 +43,135 @@ B #footnote[III]
 
 Beautiful footnotes. #footnote[Wonderful, aren't they?]
 
+--- footnote-entry-number-separator paged html ---
+#set footnote.entry(separator-between-number-and-text: [.#h(0.5em)])
+Separated #footnote[First] and #footnote[Second].
+
+--- footnote-entry-line paged ---
+#set page(margin: (left: 3em))
+#set par.line(numbering: "1")
+#show footnote.entry: it => {
+  let line = it.at("line", default: none)
+  if line != none [L#line: ]
+  it.note.body
+}
+
+First line \
+Second line #footnote[On line two] \
+Third line #footnote[On line three]
+
+#context {
+  let entries = query(footnote.entry)
+  test(entries.map(it => it.at("line", default: none)), (2, 3))
+}
+
+--- footnote-entry-line-unnumbered paged ---
+#show footnote.entry: it => {
+  test(it.has("line"), false)
+  it
+}
+
+Unnumbered #footnote[No line]
+
+--- footnote-entry-size-independent paged ---
+// The entry's text size doesn't affect the marker and vice versa.
+#show footnote: set text(14pt)
+#show footnote: it => it + context test(text.size, 14pt)
+#show footnote.entry: set text(8pt)
+#show footnote.entry: it => it + context test(text.size, 8pt)
+
+Sized #footnote[Entry]
+
+--- footnote-entry-separator-inset paged ---
+// Test space around the separator.
+#set footnote.entry(
+  separator: line(length: 100%, stroke: 0.5pt),
+  separator-inset: (top: 4pt, bottom: 6pt, left: 10pt, right: 10pt),
+)
+
+A #footnote[Inset]
+
+--- footnote-entry-rule paged ---
+// Test a rule preceding each entry.
+#set footnote.entry(rule: line(length: 1em, stroke: 0.5pt))
+
+A #footnote[One] \
+B #footnote[Two]
+
+--- footnote-entry-rule-spill paged ---
+// Test that only the first part of a broken entry is preceded by the rule.
+#set page(height: 80pt)
+#set footnote.entry(rule: line(length: 1em, stroke: 0.5pt))
+
+A #footnote[#lines(6)]
+
+--- footnote-series paged ---
+// Test that footnote series are numbered independently.
+#show footnote.where(series: "editor"): set footnote(numbering: "*")
+
+A #footnote[One] \
+B #footnote(series: "editor")[Star] \
+C #footnote[Two] \
+D #footnote(series: "editor")[Dagger]
+
+--- footnote-series-counter paged ---
+// Test that the footnote counter only tracks footnotes without a series.
+#footnote[One]
+#footnote(series: "other")[Other]
+#footnote[Two]
+#context test(counter(footnote).get(), (2,))
+
+--- footnote-series-ref paged ---
+// Test that references take the number of the referenced footnote.
+#show footnote.where(series: "editor"): set footnote(numbering: "a")
+
+A #footnote[One]
+B #footnote(series: "editor")[Editor] <note>
+C #footnote(<note>)
+
+--- footnote-strict-page paged ---
+// Test that the reference of a strict footnote migrates along with the entry.
+#set page(height: 80pt)
+#set footnote(strict-page: true)
+#lines(3)
+A #footnote[#lines(2)]
+
+--- footnote-strict-page-error paged ---
+// Test that a strict footnote that can't be placed next to its reference
+// fails.
+#set page(height: 60pt)
+// Error: 4-51 footnote entry does not fit on the same page as its reference
+// Hint: 4-51 try shortening the footnote or disabling `strict-page`
+// Hint: 33-50 the entry's content is defined here
+A #footnote(strict-page: true)[#box(height: 80pt)]
+
+--- footnote-strict-page-queued paged ---
+// Test that a strict footnote can queue behind another one as long as it
+// still ends up on the same page, here in the next column.
+#set page(height: 100pt, columns: 2)
+A #footnote[#lines(8)] B #footnote(strict-page: true)[Short]
+
+--- footnote-auto-height-many paged ---
+// Test that on an auto-height page, all footnote entries are placed after the
+// content without breaking the page.
+#set page(height: auto)
+#for i in range(12) [
+  Note #footnote[Entry #i with some more text to wrap around.]
+]
+
+--- footnote-auto-height-long paged ---
+// Test a long footnote entry on an auto-height page.
+#set page(height: auto)
+A #footnote[#lines(20)]
+B #footnote[Nested #footnote[Inner]]
+
+--- footnote-auto-height-columns paged ---
+// Test footnotes in columns on an auto-height page.
+#set page(height: auto, columns: 2)
+#for i in range(6) [
+  Note #footnote[Entry #i] \
+]
+
 --- footnote-entry-html html ---
 #show footnote.entry: it => {
   if it.note.body == [A] {
@@ -112,6 +252,15 @@ An "A" #footnote[A "B"]
   ]
 ]
 
+--- footnote-continuation-same-column paged ---
+// The long entry of the first column continues in the first column of the
+// next page, not below the second column.
+#set page(height: 100pt, columns: 2)
+#set footnote(continuation: "same-column")
+A#footnote(lines(6, "1"))
+#colbreak()
+B#footnote[Short]
+
 --- footnote-in-columns paged ---
 #set page(height: 120pt, columns: 2)
 
@@ -194,6 +343,89 @@ A
   rect(),
 )
 
+--- footnote-in-place-top-right paged ---
+// Footnotes in absolutely placed content end up in the region the content is
+// placed in, even when the placement is inside of a nested container.
+#set page(height: 100pt, columns: 2)
+#lines(2)
+#place(top + right, dx: -5pt, box(fill: aqua)[X#footnote[Top right]])
+#block(height: 20pt, place(bottom + right, footnote[In block]))
+#lines(3)
+#colbreak()
+#place(top + right, footnote[Second column])
+#lines(2)
+
+--- footnote-per-section paged ---
+#set page(height: 120pt)
+#set heading(numbering: "1.")
+#set footnote(per-section: true)
+
+Before #footnote[No section]
+
+= Intro
+A #footnote[One] B #footnote[Two]
+
+== Subsection
+C #footnote[Three]
+
+= Method
+D #footnote[Restarted]
+
+--- footnote-per-section-no-heading paged ---
+// Without a numbered heading, footnotes are numbered as usual.
+#set footnote(per-section: true)
+= Unnumbered
+A #footnote[One] B #footnote[Two]
+
+--- footnote-per-section-numbering paged ---
+// The number within the section uses the footnote's numbering.
+#set heading(numbering: "1.")
+#set footnote(per-section: true, numbering: "a")
+= Intro
+A #footnote[Is 1.a] B #footnote[Is 1.b]
+
+--- footnote-per-section-reference paged ---
+// A reference resolves per-section numbering like its numbering.
+#set heading(numbering: "1.")
+#set footnote(per-section: true)
+= Intro
+A #footnote[Is 1.1] <note> B #footnote(<note>)
+
+--- footnote-per-section-counter paged ---
+// Displaying the footnote counter follows the per-section numbering.
+#set heading(numbering: "1.")
+#set footnote(per-section: true)
+A #footnote[One]
+#context test(counter(footnote).display(), "1")
+= Intro
+B #footnote[Two] C #footnote[Three]
+#context test(counter(footnote).display(), "1.2")
+#context test(counter(footnote).display("1-a"), "1-b")
+#context test(counter(footnote).get(), (3,))
+
+--- footnote-bottom-order-floats-first paged ---
+// By default, bottom floats are placed above the footnotes.
+#set page(height: 120pt)
+A #footnote[Note]
+#place(bottom, float: true, rect(width: 100%, height: 20pt, fill: aqua))
+
+--- footnote-bottom-order-footnotes-first paged ---
+#set page(height: 120pt, bottom-order: ("footnotes", "floats"))
+A #footnote[Note]
+#place(bottom, float: true, rect(width: 100%, height: 20pt, fill: aqua))
+#place(bottom, float: true, rect(width: 100%, height: 10pt, fill: teal))
+
+--- footnote-bottom-order-footnotes-first-columns paged ---
+#set page(height: 120pt, columns: 2, bottom-order: ("footnotes", "floats"))
+A #footnote[Note]
+#place(bottom, float: true, rect(width: 100%, height: 20pt, fill: aqua))
+#colbreak()
+B #footnote[Other]
+
+--- footnote-bottom-order-invalid eval ---
+// Error: 25-45 expected `("floats", "footnotes")` or `("footnotes", "floats")`
+#set page(bottom-order: ("floats", "floats"))
+
 --- footnote-duplicate paged ---
 // Test duplicate footnotes.
 #let lang = footnote[Languages.]
@@ -296,6 +528,14 @@ Ref @fn
 A #footnote[a]
 B #footnote[b]
 
+--- footnote-reserve-space paged ---
+// Test that a line whose unbreakable footnote entry would have to be split
+// across pages moves to the next page together with the entry instead.
+#set page(height: 100pt)
+#set footnote.entry(breakable: false)
+#v(40pt)
+A #footnote(lorem(12))
+
 --- issue-1433-footnote-in-list paged ---
 // Test that footnotes in lists do not produce extraneous page breaks. The list
 // layout itself does not currently react to the footnotes layout, weakening the
@@ -419,3 +659,179 @@ C
 #document("standalone/c.html")[#footnote[C1], #footnote[C2]]
 #document("standalone/d.html")[#footnote[D1], #footnote[D2]]
 #document("standalone/e.pdf" )[#footnote[E1], #footnote[E2]]
+
+--- footnote-tally paged ---
+#set page(height: 80pt)
+
+A #footnote[One] B #footnote[Two]
+#pagebreak()
+C #footnote[Three]
+
+#context test(
+  query(footnote.tally).map(t => (t.location().page(), t.new, t.continued)),
+  ((1, 2, 0), (2, 1, 0)),
+)
+
+--- footnote-tally-continued paged ---
+#set page(height: 60pt)
+
+A #footnote[#lorem(30)]
+
+#context {
+  let tallies = query(footnote.tally)
+  test(tallies.first().new, 1)
+  test(tallies.first().continued, 0)
+  test(tallies.at(1).new, 0)
+  test(tallies.at(1).continued, 1)
+}
+
+--- footnote-tally-construct paged ---
+// Error: 2-22 cannot be constructed manually
+#footnote.tally(1, 0)
+
+--- footnote-area paged ---
+// The area spans the full column width and ends at the bottom of the page's
+// content area.
+#set page(width: 120pt, height: 100pt, margin: 10pt)
+
+A #footnote[One] B #footnote[Two]
+#pagebreak()
+C
+
+#context {
+  let areas = query(footnote.area)
+  test(areas.len(), 1)
+
+  let area = areas.first()
+  let pos = area.location().position()
+  test(pos.page, 1)
+  test(pos.x, 10pt)
+  test(area.width, 100pt)
+  assert(calc.abs((pos.y + area.height - 90pt).pt()) < 0.01)
+}
+
+--- footnote-area-construct paged ---
+// Error: 2-25 cannot be constructed manually
+#footnote.area(1pt, 1pt)
+
+--- footnote-draft paged ---
+#set page(height: 80pt)
+#set document(draft: true)
+
+Check the docs for more details.
+#footnote[https://typst.app/docs]
+#lorem(8) #footnote[Another note]
+
+--- footnote-draft-no-tally paged ---
+// Draft footnotes aren't placed anywhere, so there are no tallies.
+#set document(draft: true)
+A #footnote[One]
+#context test(query(footnote.tally).len(), 0)
+
+--- footnote-collapse-clearance paged ---
+#set page(height: auto)
+#set footnote.entry(clearance: 2em, collapse-clearance: true)
+
+Dense typesetting.
+#footnote[Close to the body.]
+#v(1.5em)
+
+--- footnote-collapse-clearance-fixed-height paged ---
+// On a page with a fixed height, the footnotes stay at the bottom.
+#set page(height: 100pt)
+#set footnote.entry(collapse-clearance: true)
+
+Body text.
+#footnote[At the bottom.]
+#v(1em)
+
+--- footnote-area-align paged ---
+#set page(height: 100pt)
+#set footnote(area-align: right)
+Right-aligned
+#footnote[A short note.]
+#footnote[Another one.]
+
+--- footnote-area-align-center-columns paged ---
+#set page(height: 120pt, columns: 2)
+#set footnote(area-align: center)
+First #footnote[One.]
+#colbreak()
+Second #footnote[Two.]
+
+--- footnote-entry-number-width paged ---
+#set page(height: 200pt)
+#set footnote.entry(number-width: 1.2em)
+#for i in range(10) [#footnote[Note #(i + 1).]]
+
+--- footnote-entry-number-width-wrap paged ---
+// A long entry wraps below its number box.
+#set page(width: 120pt, height: 150pt)
+#set footnote.entry(number-width: 1.5em, number-align: right)
+A #footnote[#lorem(12)]
+
+--- footnote-columns paged ---
+#set page(height: 140pt)
+#set footnote(columns: 2)
+Some text#footnote[First.]
+with notes#footnote[Second, which is a bit longer.]
+in two#footnote[Third.]
+columns.#footnote[Fourth.]
+
+--- footnote-columns-spill paged ---
+// A long entry continues in the footnote columns of the next page.
+#set page(height: 100pt)
+#set footnote(columns: 2, column-gutter: 8pt)
+Text#footnote[Short.]
+#v(30pt)
+More#footnote[#lorem(40)]
+
+--- footnote-columns-rtl paged ---
+#set page(height: 120pt)
+#set text(dir: rtl)
+#set footnote(columns: 3)
+A#footnote[1]
+B#footnote[2]
+C#footnote[3]
+
+--- footnote-lines-above paged ---
+// The long entry continues on the next page such that three lines of text
+// precede the footnote area.
+#set page(height: 100pt)
+#set footnote(lines-above: 3)
+Text#footnote(lorem(40)) with a long note.
+#lorem(12)
+
+--- footnote-lines-above-last-line paged ---
+// No lines follow the marker, so no room is reserved for them and the entry
+// stays on the marker's page.
+#set page(height: 100pt)
+#set footnote(lines-above: 3)
+Text#footnote(block(height: 40pt, width: 100%, fill: aqua))
+
+--- footnote-validate paged ---
+// Matching markers and entries pass the check, including label-based and
+// nested footnotes.
+#set page(height: 100pt)
+#set footnote.entry(validate: true)
+A#footnote[First #footnote[Nested]] <first>
+B#footnote(<first>)
+#pagebreak()
+C#footnote(<first>)
+
+--- footnote-unnumbered paged ---
+// The unnumbered note has no marker and doesn't take up a number.
+#set page(height: 120pt)
+Numbered #footnote[First note]
+and unnumbered #footnote(numbered: false)[Source: The archive]
+notes #footnote[Second note]
+
+--- footnote-unnumbered-counter paged ---
+A #footnote(numbered: false)[Unnumbered]
+B #footnote[Numbered]
+#context test(counter(footnote).get(), (1,))
+
+--- footnote-unnumbered-ref paged ---
+A #footnote(numbered: false)[Unnumbered] <unnumbered>
+// Error: 2-24 cannot reference an unnumbered footnote
+#footnote(<unnumbered>)
@@ -165,6 +165,43 @@ C
   place(auto, float: true, block(width: 100%, height: 100%, fill: aqua))
 )
 
+--- place-float-gap paged ---
+// Stacked floats are separated by the gap, but by the clearance from the text.
+#set page(height: 150pt)
+#set place(clearance: 12pt, gap: 3pt)
+#place(top, float: true, rect(width: 100%, height: 15pt, fill: aqua))
+#place(top, float: true, rect(width: 100%, height: 15pt, fill: aqua))
+#place(bottom, float: true, rect(width: 100%, height: 15pt, fill: teal))
+#place(bottom, float: true, rect(width: 100%, height: 15pt, fill: teal))
+#lorem(10)
+
+--- place-float-deferral paged ---
+// Only floats that don't fit right away are recorded with the number of
+// regions they were deferred across.
+#set page(height: 80pt)
+#set place(float: true)
+#place(top, rect(height: 5pt))
+#lines(3)
+#place(auto, rect(height: 40pt, fill: aqua))
+
+#context {
+  let deferrals = query(place.deferral)
+  test(deferrals.len(), 1)
+  test(deferrals.first().regions, 1)
+  test(deferrals.first().location().page(), 2)
+}
+
+--- place-float-gap-fit paged ---
+// The second float fits because it only needs the small gap to the first one
+// rather than its full clearance.
+#set page(height: 100pt, margin: 0pt)
+#set text(top-edge: 8pt, bottom-edge: 0pt)
+#set place(clearance: 30pt, gap: 2pt)
+A
+#place(top, float: true, rect(width: 100%, height: 30pt, fill: aqua))
+#place(top, float: true, rect(width: 100%, height: 25pt, fill: teal))
+#context test(counter(page).final(), (1,))
+
 --- place-float-column-align-auto paged ---
 #set page(height: 150pt, columns: 2)
 #set place(auto, float: true, clearance: 10pt)
@@ -351,6 +388,139 @@ A
 #place.flush()
 B // Should be on the second page.
 
+--- place-float-trailing paged ---
+// A float that is still queued once all children are processed ends up on the
+// last page if it fits there instead of spawning a new page. Here, the float
+// waits for an anchor within absolutely placed content, which doesn't release
+// it, so it is only queued at the end of the flow.
+#set page(height: 100pt)
+#set place(clearance: 5pt)
+
+#lines(4)
+#place(bottom, float: true, same-page-as: <anchor>, rect(height: 10pt)[I])
+#place(top + right)[#box[A] <anchor>]
+
+#context test(counter(page).final(), (1,))
+
+--- place-float-same-page-as paged ---
+// A float is held back until the page containing its anchor.
+#set page(height: 100pt)
+#place(top, float: true, same-page-as: <anchor>, rect(height: 10pt)[F])
+#lines(8)
+
+= Anchor <anchor>
+#lines(2)
+
+--- place-float-same-page-as-before paged ---
+// An anchor before the float doesn't hold the float back.
+#set page(height: 100pt)
+= Anchor <anchor>
+#lines(2)
+#place(bottom, float: true, same-page-as: <anchor>, rect(height: 10pt)[F])
+#lines(2)
+
+--- place-float-same-page-as-no-fit paged ---
+// A float that doesn't fit on the page of its anchor moves to the next page.
+#set page(height: 100pt)
+// Warning: 2-69 float could not be placed on the same page as its anchor
+// Hint: 2-69 it was moved to the next region instead
+#place(top, float: true, same-page-as: <anchor>, rect(height: 60pt))
+#lines(3)
+= Anchor <anchor>
+#lines(2)
+
+--- place-float-same-page-as-sticky paged ---
+// A float moves along with its anchor if the anchor is sticky and migrates to
+// the next page.
+#set page(height: 100pt, margin: 0pt)
+#set block(spacing: 0pt)
+#set place(clearance: 0pt)
+#place(top, float: true, same-page-as: <anchor>, block(height: 10pt)[#box[F] <float>])
+#block(height: 60pt)
+#block(sticky: true, height: 10pt) <anchor>
+#block(height: 40pt)
+
+#context test(locate(<float>).page(), locate(<anchor>).page())
+
+--- place-float-rotate paged ---
+// Test that a rotated float reserves its rotated bounding box.
+#set page(height: 200pt)
+#place(
+  top,
+  float: true,
+  rotate: 90deg,
+  table(columns: 5, ..range(10).map(str)),
+)
+#lines(3)
+
+--- place-float-rotate-bottom paged ---
+// Test a rotated float at the bottom.
+#set page(height: 150pt)
+#lines(2)
+#place(bottom, float: true, rotate: -90deg, rect(width: 60pt, height: 20pt))
+#lines(2)
+
+--- place-float-wrap paged ---
+// Test that text wraps around a side float and resumes below it.
+#set page(width: 150pt, height: 200pt)
+#place(
+  right,
+  float: true,
+  wrap: true,
+  clearance: 6pt,
+  rect(width: 50pt, height: 50pt, fill: aqua),
+)
+#lorem(40)
+
+--- place-float-wrap-left-justified paged ---
+// Test wrapping around a left float with justified text.
+#set page(width: 150pt, height: 200pt)
+#set par(justify: true)
+#place(left, float: true, wrap: true, clearance: 6pt, rect(width: 40pt, height: 60pt))
+#lorem(15)
+
+#lorem(20)
+
+--- place-float-wrap-left-ragged-hanging paged ---
+// Test wrapping around a left float with ragged text and a hanging indent.
+// The narrowed lines start right of the float and keep their indent.
+#set page(width: 150pt, height: 200pt)
+#set par(hanging-indent: 10pt)
+#place(left, float: true, wrap: true, clearance: 6pt, rect(width: 40pt, height: 60pt))
+#lorem(25)
+
+--- place-float-wrap-right-ragged paged ---
+// Test wrapping around a right float with right-aligned ragged text.
+#set page(width: 150pt, height: 200pt)
+#set align(right)
+#place(right, float: true, wrap: true, clearance: 6pt, rect(width: 40pt, height: 60pt))
+#lorem(25)
+
+--- place-float-wrap-block paged ---
+// Test that a block following a wrapping float is moved below it.
+#set page(width: 150pt, height: 200pt)
+#place(left, float: true, wrap: true, rect(width: 40pt, height: 60pt))
+#lorem(5)
+#block(width: 100%, height: 20pt, fill: conifer)
+
+--- place-float-wrap-region-break paged ---
+// The float doesn't fit at the end of the first page, so it moves to the next
+// page together with the lines running alongside it. None of the lines on the
+// first page are narrowed.
+#set page(width: 150pt, height: 120pt)
+#lorem(20)
+#place(right, float: true, wrap: true, rect(width: 50pt, height: 50pt, fill: aqua))
+#lorem(30)
+
+--- place-float-wrap-center paged ---
+// Error: 2-48 wrapping floats must be aligned to the left or right
+// Hint: 2-48 try `place(left, float: true, wrap: true, ..)`
+#place(center, float: true, wrap: true, rect())
+
+--- place-float-wrap-vertical paged ---
+// Error: 2-52 wrapping floats cannot be aligned vertically
+#place(top + left, float: true, wrap: true, rect())
+
 --- issue-place-base paged ---
 // Test that placement is relative to container and not itself.
 #set page(height: 80pt, margin: 0pt)
@@ -387,3 +557,205 @@ Paragraph after place.
 #place(auto, float: true, block(height: 100%, width: 100%, fill: aqua))
 #place(auto, float: true, block(height: 100%, width: 100%, fill: red))
 #lines(7)
+
+--- place-float-placement paged ---
+// A deferred float's placement is on the page where it ended up.
+#set page(height: 100pt)
+#lorem(12)
+#place(auto, float: true, rect(height: 60pt)) <float>
+#context {
+  let loc = locate(<float>)
+  test(loc.page(), 1)
+  test(loc.placement().page, 2)
+}
+
+--- place-float-placement-non-float paged ---
+// For other elements, the placement is the position.
+#context {
+  let loc = here()
+  test(loc.placement(), loc.position())
+}
+
+--- place-float-fallback paged ---
+#set page(height: 150pt)
+#let note(body) = place(
+  top,
+  float: true,
+  fallback: bottom,
+  clearance: 6pt,
+  rect(body),
+)
+
+#note[Top]
+#note[Bottom]
+#lorem(20)
+
+--- place-float-fallback-deferred paged ---
+// With both sides taken, the third float moves to the next page.
+#set page(height: 120pt)
+#let note(body) = place(top, float: true, fallback: bottom, rect(body))
+
+#note[A] <a>
+#note[B]
+#note[C] <c>
+#lorem(30)
+
+#context {
+  test(locate(<a>).placement().page, 1)
+  test(locate(<c>).placement().page, 2)
+}
+
+--- place-float-fallback-horizon paged ---
+// Error: 2-52 fallback placement must be `top` or `bottom`
+#place(top, float: true, fallback: horizon, rect())
+
+--- place-float-placement-spacious paged ---
+// The floats alternate between the sides, starting at the top.
+#set page(height: 150pt)
+#let note(body) = place(
+  float: true,
+  placement: "spacious",
+  clearance: 6pt,
+  rect(body),
+)
+
+#note[A]
+#note[B]
+#note[C]
+#lorem(10)
+
+--- place-float-placement-spacious-ignores-alignment paged ---
+// The explicit alignment is overridden by the free space.
+#set page(height: 150pt)
+#place(top, float: true, rect[Top])
+// Warning: 2-62 spacious placement ignores the vertical alignment
+// Hint: 2-62 use `auto` or a horizontal alignment instead
+#place(top, float: true, placement: "spacious", rect[Bottom])
+#lorem(10)
+
+--- place-float-placement-bad eval ---
+// Error: 32-37 expected "aligned" or "spacious"
+#place(float: true, placement: "top", rect())
+
+--- place-float-eager paged ---
+// The eager float is pulled from the second page to the first one.
+#set page(height: 100pt)
+#lorem(30)
+// Warning: 2-47 eager float was placed before its reference point
+// Hint: 2-47 it was moved to an earlier region that had room for it
+#place(top, float: true, eager: true, rect[A])
+
+--- place-float-eager-same-region paged ---
+// An eager float that is placed in its own region doesn't warn.
+#set page(height: 100pt)
+#lorem(5)
+#place(bottom, float: true, eager: true, rect[A])
+#lorem(5)
+
+--- place-float-eager-too-large paged ---
+// An eager float that doesn't fit anywhere earlier stays in place.
+#set page(height: 100pt)
+#lorem(20)
+#place(top, float: true, eager: true, rect(height: 90pt))
+
+--- place-float-eager-near paged ---
+// An eager float is only pulled forward from nearby content, not from the end
+// of the flow to its start.
+#set page(height: 100pt, margin: 0pt)
+#set block(spacing: 0pt)
+A
+#block(height: 60pt, breakable: false)
+#block(height: 60pt, breakable: false)
+#block(height: 60pt, breakable: false)
+#block(height: 60pt, breakable: false)
+#block(height: 60pt, breakable: false)
+// Warning: 2-90 eager float was placed before its reference point
+// Hint: 2-90 it was moved to an earlier region that had room for it
+#place(top, float: true, eager: true, rect(height: 20pt, context test(here().page(), 3)))
+
+--- place-float-parent-leading-spacing paged ---
+// Columns below a spanning float start flush against its clearance, even if
+// leading spacing is kept otherwise.
+#set page(height: 150pt, columns: 2, trim-leading: false)
+#lorem(30)
+
+#place(
+  top,
+  scope: "parent",
+  float: true,
+  clearance: 6pt,
+  rect(width: 100%)[Title],
+)
+#lorem(20)
+
+--- place-float-next-page paged ---
+// The float is deferred to the top of the next page even though it would fit
+// on the current one.
+#set page(height: 100pt)
+#lorem(5)
+#place(top, float: true, page: "next", rect(width: 100%)[Plate])
+#lorem(20)
+
+--- place-float-next-page-order paged ---
+// A deferred float comes before floats that were queued normally.
+#set page(height: 100pt)
+#lorem(15)
+#place(top, float: true, rect(height: 60pt)[A])
+#place(top, float: true, page: "next", rect[B])
+
+--- place-float-next-page-conflict paged ---
+#set page(height: 100pt)
+#lorem(5)
+// Warning: 2-51 float for the next page cannot be combined with bottom alignment
+// Hint: 2-51 it is placed at the top of the next region instead
+#place(bottom, float: true, page: "next", rect[A])
+// Warning: 2-61 float for the next page cannot be combined with eager placement
+// Hint: 2-61 it is placed at the top of the next region instead
+#place(top, float: true, eager: true, page: "next", rect[B])
+
+--- place-float-next-page-columns paged ---
+// A column-scoped float moves to the next column, a parent-scoped one to the
+// next page.
+#set page(height: 120pt, columns: 2)
+#lorem(5)
+#place(top, float: true, page: "next", rect[Column])
+#place(top, float: true, scope: "parent", page: "next", rect[Page])
+#lorem(40)
+
+--- place-float-next-page-parent-column-break paged ---
+// A parent-scoped float for the next page skips the page's later columns.
+#set page(height: 120pt, columns: 2)
+A
+#place(top, float: true, scope: "parent", page: "next", rect[#box[Page] <plate>])
+#colbreak()
+B
+#colbreak()
+C
+
+#context test(locate(<plate>).page(), 2)
+
+--- place-float-next-page-flush paged ---
+// A flush also waits for floats deferred to the next page.
+#set page(height: 100pt)
+A
+#place(top, float: true, page: "next", rect[Plate])
+#place.flush()
+#box[B] <after>
+
+#context test(locate(<after>).page(), 2)
+
+--- place-float-anchor paged ---
+// The float is declared on the second page, but placed on the first one.
+#set page(height: 100pt)
+= Results <results>
+#lorem(30)
+
+#place(top, float: true, anchor: <results>, rect(width: 100%)[Anchored])
+
+--- place-float-anchor-missing paged ---
+#set page(height: 100pt)
+// Warning: 2-62 float anchor was not found before the float
+// Hint: 2-62 the anchor must be part of the same flow and precede the float
+// Hint: 2-62 the float is placed at its own position instead
+#place(top, float: true, anchor: <later>, rect[Not anchored])
+= Later <later>
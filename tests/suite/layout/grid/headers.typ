@@ -100,6 +100,18 @@
   t(1)
 )
 
+--- grid-header-repeat-in-breakable-block paged ---
+// The header should be repeated in every region a breakable block containing
+// the table continues in, regardless of whether the region is a column or a
+// page.
+#set page(height: 12em, columns: 2)
+#lines(2)
+#block(fill: aqua.lighten(60%), inset: 3pt, breakable: true, table(
+  columns: 2,
+  table.header[*Name*][*Num*],
+  ..range(0, 10).map(i => ([\##i], [#i])).flatten()
+))
+
 --- grid-header-hline-and-vline paged ---
 // Test line positioning in header
 #table(
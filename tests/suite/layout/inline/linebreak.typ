@@ -175,3 +175,35 @@ Et les autres ‹ guillemets › en français & suisse romande.
 
 #set text(lang: "de")
 Alternative »Anführungszeichen« in DE & AT.
+
+--- linebreak-balance-last-line paged ---
+#set page(width: 100pt)
+#set par(balance-last-line: true)
+This paragraph ends with a rather lonely word.
+
+#set par(justify: true)
+This paragraph ends with a rather lonely word.
+
+--- linebreak-balance-last-line-forced paged ---
+// A line after a forced break is left alone.
+#set page(width: 100pt)
+#set par(balance-last-line: true)
+This paragraph has a forced \ break.
+
+--- linebreak-overflow-warning paged ---
+// Overflowing lines are only reported in drafts.
+#set document(draft: true)
+
+// Warning: 21-63 content overflows the line by 30.0pt
+// Hint: 21-63 try wrapping it in a `box` with a fixed width or enabling hyphenation
+#block(width: 50pt, box(width: 80pt, height: 10pt, fill: aqua))
+
+--- linebreak-overflow-no-warning paged ---
+// A line that fits after shrinking doesn't warn.
+#set document(draft: true)
+#set par(justify: true)
+#block(width: 80pt)[Fits exactly into the block]
+
+--- linebreak-overflow-no-draft paged ---
+// Outside of drafts, overflowing lines don't warn.
+#block(width: 50pt, box(width: 80pt, height: 10pt, fill: aqua))
@@ -257,9 +257,109 @@ A
 $ x $
 B
 
+--- line-numbers-number-lines-width paged ---
+#set page(width: 200pt, margin: (left: 2.5em))
+#set par.line(numbering: "1", number-lines: 50%)
+
+#lorem(12) \
+Short. \
+#lorem(6)
+
+--- line-numbers-number-lines-func paged ---
+#set page(margin: (left: 2.5em))
+#set par.line(
+  numbering: "1",
+  number-lines: line => line.text.starts-with("Yes"),
+)
+
+Yes \
+No \
+Yes again
+
+--- line-numbers-number-lines-func-bad-return paged ---
+// Error: 45-54 expected boolean, found integer
+#set par.line(numbering: "1", number-lines: line => 5)
+A
+
+--- line-numbers-number-step paged ---
+#set page(margin: (left: 2.5em))
+#set par.line(numbering: "1", number-step: 2)
+
+A \
+B \
+C \
+D \
+E
+
+--- line-numbers-number-step-page-scope paged ---
+// The step counting restarts with the page-scoped counter.
+#set page(margin: (left: 2.5em), height: 60pt)
+#set par.line(numbering: "1", number-step: 2, numbering-scope: "page")
+
+A \
+B \
+C \
+D \
+E \
+F
+
+--- line-numbers-number-step-columns paged ---
+// The step counting continues across columns.
+#set page(margin: (x: 2.5em), height: 60pt, columns: 2)
+#set par.line(numbering: "1", number-step: 3)
+
+A \
+B \
+C \
+D \
+E \
+F
+
 --- line-numbers-pdf-2-0 pdf pdfstandard(2.0) ---
 #set page(margin: (left: 2.5em))
 #set par.line(numbering: "1")
 
 First line \
 Second line
+
+--- line-numbers-absolute paged ---
+#set page(margin: (left: 4em), width: 160pt, height: 80pt)
+#set par.line(
+  numbering: (n, total) => [#n (#total)],
+  numbering-scope: "page",
+)
+
+#lorem(30)
+
+--- line-numbers-absolute-single-argument paged ---
+// A function with one parameter only receives the displayed number.
+#set page(margin: (left: 4em), width: 160pt, height: 80pt)
+#set par.line(numbering: n => [#n], numbering-scope: "page")
+
+#lorem(30)
+
+--- line-numbers-numbering-width paged ---
+// The gutter is as wide as three digits, even if all numbers are shorter.
+#set page(margin: (left: 3em), width: 120pt)
+#set par.line(numbering: "1", numbering-width: 3)
+
+A \
+B \
+C
+
+--- line-numbers-numbering-width-pad paged ---
+#set page(margin: (left: 3em), width: 120pt)
+#set par.line(numbering: "1", numbering-width: 3, number-pad: true)
+
+A \
+B \
+C
+
+--- line-numbers-numbering-width-rtl paged ---
+#set page(margin: (right: 3em), width: 120pt)
+#set text(dir: rtl)
+#set par.line(numbering: "1", numbering-width: 3)
+
+A \
+B \
+C
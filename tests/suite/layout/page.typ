@@ -137,6 +137,117 @@
 // Error: 20-23 must be `left` or `right`
 #set page(binding: top)
 
+--- page-trim-leading-disabled paged ---
+// Test that spacing cut off at a page break is kept at the top of the next
+// page.
+#set page(height: 60pt, trim-leading: false)
+#lines(3)
+#v(12pt, weak: true)
+#lines(2)
+
+--- page-trim-leading-columns paged ---
+// Test that this also applies to columns, but not to the start of the flow.
+#set page(height: 60pt, columns: 2, trim-leading: false)
+#v(12pt, weak: true)
+#lines(4)
+#v(12pt, weak: true)
+#lines(2)
+
+--- page-mirrored paged ---
+// Test that the page's content is mirrored, but not its header and footer.
+#set page(height: 80pt, mirrored: true, header: [Header], numbering: "1")
+#lines(7)
+
+--- page-mirrored-columns paged ---
+#set page(height: 60pt, columns: 2, mirrored: true)
+#lines(6)
+
+--- page-can-break paged ---
+// Test that a break right after a heading is moved up.
+#set page(
+  height: 80pt,
+  can-break: it => it.before == none or it.before.func() != heading,
+)
+#lines(3)
+= Heading
+#lines(3)
+
+--- page-can-break-never paged ---
+// Test that a predicate forbidding all breaks still breaks the content.
+#set page(height: 60pt, can-break: it => false)
+#lines(10)
+
+--- page-can-break-bad-return paged ---
+// Error: 36-43 expected boolean, found integer
+#set page(height: 60pt, can-break: it => 1)
+#lines(10)
+
+--- page-optimize-compact paged ---
+// Test that the break predicate is ignored if that saves a page.
+#set page(
+  height: 60pt,
+  optimize: "compact",
+  can-break: it => it.after == none or it.after.func() != heading,
+)
+#lines(4)
+= Heading
+#lines(2)
+
+--- page-optimize-compact-sticky paged ---
+// Stickiness is only relaxed if that saves a page.
+#set page(height: 60pt, optimize: "compact")
+#lines(2)
+= Heading
+#lines(2)
+
+--- page-optimize-compact-per-page paged ---
+// Constraints are only relaxed for the pages where that helps. Here, relaxing
+// stickiness on the first page saves a page, while on the second page, it
+// would only separate C from what follows it.
+#set page(height: 100pt, margin: 0pt, optimize: "compact")
+#set block(spacing: 0pt)
+#block(height: 70pt)
+#block(sticky: true, height: 10pt)[A] <a>
+#block(height: 30pt)
+#block(height: 50pt)
+#block(height: 15pt)
+#block(sticky: true, height: 5pt)[C] <c>
+#block(height: 30pt)
+#block(height: 55pt)
+
+#context test(counter(page).final(), (3,))
+#context test(locate(<a>).page(), 1)
+#context test(locate(<c>).page(), 3)
+
+--- page-optimize-bad eval ---
+// Error: 21-29 expected "compact" or none
+#set page(optimize: "greedy")
+
+--- page-region paged ---
+#set page(height: 60pt, footer: context {
+  let regions = query(page.region).filter(it => it.location().page() == here().page())
+  let region = regions.first()
+  if region.first [First]
+  if region.last [Last]
+})
+#lines(8)
+
+--- page-region-runs paged ---
+// Each run of pages has its own first and last page.
+#lines(1)
+#set page(height: 60pt)
+#lines(6)
+#context {
+  let regions = query(page.region)
+  test(regions.filter(it => it.first).len(), 2)
+  test(regions.filter(it => it.last).len(), 2)
+  test(regions.last().last, true)
+}
+
+--- page-region-construct eval ---
+// Error: 2-15 cannot be constructed manually
+#page.region()
+
 --- page-marginals paged ---
 #set page(
   paper: "a8",
@@ -452,3 +563,174 @@ text
 --- page-bleed-inner-auto eval ---
 // Error: 18-30 expected relative length, found auto
 #set page(bleed: (left: auto))
+
+--- page-pad-to-multiple paged ---
+#set page(
+  width: 80pt,
+  height: 30pt,
+  fill: aqua,
+  background: place(center + horizon, circle(radius: 5pt)),
+  pad-to-multiple: 4,
+)
+
+First
+#pagebreak()
+Second
+
+--- page-pad-to-multiple-already-full paged ---
+#set page(width: 80pt, height: 30pt, pad-to-multiple: 2)
+First
+#pagebreak()
+Second
+
+--- page-pad-to-multiple-zero eval ---
+// Error: 28-29 number must be positive
+#set page(pad-to-multiple: 0)
+
+--- page-max-pages paged ---
+#set page(height: 30pt, max-pages: 2)
+First
+#pagebreak()
+Second
+
+--- page-max-pages-exceeded paged ---
+// Error: 2-38 document has 3 pages, but at most 2 are allowed
+// Hint: 2-38 try shortening the content or raising `max-pages`
+#set page(height: 30pt, max-pages: 2)
+First
+#pagebreak()
+Second
+#pagebreak()
+Third
+
+--- page-max-pages-with-padding paged ---
+// Error: 2-58 document has 4 pages, but at most 3 are allowed
+// Hint: 2-58 try shortening the content or raising `max-pages`
+#set page(height: 30pt, pad-to-multiple: 4, max-pages: 3)
+First
+
+--- page-first-inset paged ---
+#set page(height: 100pt, first-inset: 30pt)
+#lorem(40)
+
+--- page-first-inset-per-run paged ---
+#set page(height: 80pt, first-inset: 20pt)
+= Chapter one
+#lorem(10)
+
+// A new page run starts with a fresh first region.
+#set page(first-inset: 20pt)
+= Chapter two
+#lorem(10)
+
+--- page-first-baseline paged ---
+// The first line of each page sits at the same baseline, independent of its
+// font size.
+#set page(height: 80pt, first-baseline: 20pt)
+#text(6pt, lorem(12))
+#pagebreak()
+#text(12pt)[Larger]
+
+--- page-first-baseline-trim-leading paged ---
+// Kept leading spacing is absorbed into the offset.
+#set page(height: 80pt, first-baseline: 20pt, trim-leading: false)
+#set par(spacing: 16pt)
+#lorem(12)
+
+#lorem(6)
+
+--- page-first-baseline-columns paged ---
+#set page(height: 80pt, columns: 2, first-baseline: 16pt)
+#lorem(30)
+
+--- page-exclusion paged ---
+#set page(
+  height: 120pt,
+  exclusion: (side: right, top: 20pt, width: 40pt, height: 40pt),
+  background: place(top + right, dx: -10pt, dy: 30pt, square(size: 35pt, fill: aqua)),
+)
+#lorem(50)
+
+--- page-exclusion-start paged ---
+// The side follows the text direction.
+#set page(height: 100pt, exclusion: (side: start, width: 30pt, height: 30pt))
+#set text(dir: rtl)
+#lorem(25)
+
+--- page-exclusion-columns paged ---
+// With page columns, the area is at the same side of each column. Paragraphs
+// in containers are not affected.
+#set page(
+  height: 120pt,
+  columns: 2,
+  exclusion: (side: right, top: 10pt, width: 20pt, height: 40pt),
+)
+#lorem(30)
+#block(stroke: 0.5pt, lorem(10))
+
+--- page-exclusion-center paged ---
+// Error: 22-63 exclusion must be at the left or right side of the region
+#set page(exclusion: (side: center, width: 10pt, height: 10pt))
+
+--- page-breaks paged ---
+// The second paragraph starts on a new page, although it would fit onto the
+// first one.
+#set page(height: 60pt, breaks: (5,))
+A
+
+B
+
+--- page-breaks-exceeded paged ---
+// Error: 2-51 content needs more pages than the breaks allow
+// Hint: 2-51 try adding a break at position 10
+#set page(height: 20pt, margin: 0pt, breaks: (5,))
+A
+
+B
+
+C
+
+--- page-continuous paged ---
+// A single tall page with an inline float, the column break ignored, and the
+// footnote at the end.
+#set page(height: 60pt, columns: 2, continuous: true)
+#place(top, float: true, rect(width: 100%, height: 10pt, fill: aqua))
+Flowing#footnote[A note.] content.
+#colbreak()
+#lorem(30)
+
+--- page-imposition-saddle-stitch paged ---
+// Six pages are padded to eight and ordered 8, 1, 2, 7, 6, 3, 4, 5.
+#set page(height: 40pt, width: 60pt, imposition: "saddle-stitch")
+#set page(footer: context align(center, counter(page).display()))
+#for i in range(1, 7) [
+  Page #i
+  #if i < 6 { pagebreak() }
+]
+
+--- page-imposition-saddle-stitch-pad-to-multiple paged ---
+// Five pages are padded to twelve, a multiple of both three and four.
+#set page(height: 40pt, width: 60pt, pad-to-multiple: 3)
+#set page(imposition: "saddle-stitch")
+#set page(footer: context align(center, counter(page).display()))
+#for i in range(1, 6) [
+  Page #i
+  #if i < 5 { pagebreak() }
+]
+
+--- page-remaining paged ---
+// The block fills the rest of the page.
+#set page(height: 120pt, margin: 10pt)
+#lorem(10)
+
+#context block(height: page.remaining(), width: 100%, fill: aqua)
+
+--- page-remaining-top paged ---
+// At the top of the page, the full content area remains.
+#set page(height: 100pt, margin: 10pt)
+#context test(page.remaining(), 80pt)
+
+--- page-remaining-nested paged empty ---
+// Only the page-level flow is considered, not the containing block.
+#set page(height: 100pt, margin: 10pt)
+#block(height: 40pt, context test(page.remaining(), 80pt))
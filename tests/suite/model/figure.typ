@@ -308,3 +308,20 @@ HI#footnote.entry(clearance: 2.5em)[There]
 )
 
 #c
+
+--- figure-keep-together paged ---
+#set page(height: 120pt)
+#show figure: set block(breakable: true)
+
+#lorem(12)
+#figure(
+  keep-together: true,
+  rect(height: 50pt),
+  caption: [Not separated],
+)
+
+--- figure-keep-together-too-large paged ---
+#set page(height: 60pt)
+// Warning: 2-71 figure does not fit into a region even though it is kept together
+// Hint: 2-71 try making the figure smaller or disabling `keep-together`
+#figure(keep-together: true, rect(height: 80pt), caption: [Too large])
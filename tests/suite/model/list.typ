@@ -631,3 +631,17 @@ d
 + #lines(3)
 + #lines(8)
 + #lines(2)
+
+--- list-keep-items-together paged ---
+// The second item moves to the next page as a whole.
+#set page(height: 80pt)
+#set list(keep-items-together: true)
+- #lines(3)
+- #lines(3)
+
+--- list-keep-items-together-too-large paged ---
+// An item taller than a page is still split.
+#set page(height: 80pt)
+#set list(keep-items-together: true)
+- A
+- #lines(8)